@@ -0,0 +1,178 @@
+//! Parse every `.py` file under a directory and report how the parser fared: how many files
+//! parsed cleanly, how many produced a parse error or a panic, and how long each took -- the
+//! tool for evaluating error-recovery and grammar changes against a real-world corpus.
+#![allow(clippy::print_stdout, clippy::print_stderr)]
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use ruff_python_ast::visitor::{walk_expr, walk_stmt, Visitor};
+use ruff_python_ast::{Expr, Mod, Stmt};
+use ruff_python_parser::{parse, Mode};
+
+use crate::util::python_files;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Directory of `.py` files (or a single file) to parse.
+    #[arg(required = true)]
+    path: PathBuf,
+    /// Write the JSON report to this file instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+pub(crate) fn main(args: &Args) -> Result<()> {
+    let report = run(&args.path);
+    let json = serde_json::to_string_pretty(&report)?;
+    match &args.output {
+        Some(path) => std::fs::write(path, json)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+/// How parsing a single file went.
+#[derive(Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+enum Outcome {
+    /// Parsed without error. `node_count` is the number of statement and expression nodes in the
+    /// resulting tree. This crate's parser reports the first syntax error it hits rather than
+    /// recovering node by node, so there's no true "invalid node" count to report here -- this is
+    /// the closest available proxy for how much of a file's grammar actually got exercised.
+    Parsed { node_count: usize },
+    /// The parser rejected the file.
+    ParseError { message: String },
+    /// Parsing the file panicked.
+    Panicked { message: String },
+}
+
+/// One file's result from a corpus run.
+#[derive(Serialize)]
+struct FileReport {
+    path: PathBuf,
+    #[serde(flatten)]
+    outcome: Outcome,
+    duration_ms: u128,
+}
+
+/// A machine-readable summary of a corpus run.
+#[derive(Serialize)]
+struct CorpusReport {
+    file_count: usize,
+    error_count: usize,
+    panic_count: usize,
+    total_duration_ms: u128,
+    files: Vec<FileReport>,
+    /// Hit counts for the hand-written parser decision points probed by the `coverage` feature
+    /// (soft-keyword disambiguation, f-string format-spec nesting, grammar entry points), present
+    /// only when `ruff_python_parser` was built with that feature enabled.
+    #[cfg(feature = "coverage")]
+    coverage: std::collections::BTreeMap<&'static str, u64>,
+}
+
+fn run(root: &std::path::Path) -> CorpusReport {
+    #[cfg(feature = "coverage")]
+    ruff_python_parser::coverage::reset();
+
+    let mut files = Vec::new();
+    let mut error_count = 0;
+    let mut panic_count = 0;
+    let mut total_duration = Duration::ZERO;
+
+    for path in python_files(root) {
+        let Ok(source) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let start = Instant::now();
+        let result = catch_unwind(AssertUnwindSafe(|| parse(&source, Mode::Module)));
+        let duration = start.elapsed();
+        total_duration += duration;
+
+        let outcome = match result {
+            Ok(Ok(module)) => Outcome::Parsed {
+                node_count: count_nodes(&module),
+            },
+            Ok(Err(error)) => {
+                error_count += 1;
+                Outcome::ParseError {
+                    message: error.to_string(),
+                }
+            }
+            Err(payload) => {
+                panic_count += 1;
+                Outcome::Panicked {
+                    message: panic_message(&payload),
+                }
+            }
+        };
+
+        files.push(FileReport {
+            path,
+            outcome,
+            duration_ms: duration.as_millis(),
+        });
+    }
+
+    CorpusReport {
+        file_count: files.len(),
+        error_count,
+        panic_count,
+        total_duration_ms: total_duration.as_millis(),
+        files,
+        #[cfg(feature = "coverage")]
+        coverage: ruff_python_parser::coverage::report(),
+    }
+}
+
+/// Counts the statement and expression nodes in a parsed module.
+fn count_nodes(module: &Mod) -> usize {
+    struct NodeCounter(usize);
+
+    impl<'a> Visitor<'a> for NodeCounter {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            self.0 += 1;
+            walk_stmt(self, stmt);
+        }
+
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            self.0 += 1;
+            walk_expr(self, expr);
+        }
+    }
+
+    let mut counter = NodeCounter(0);
+    match module {
+        Mod::Module(module) => {
+            for stmt in &module.body {
+                counter.visit_stmt(stmt);
+            }
+        }
+        Mod::Expression(expression) => counter.visit_expr(&expression.body),
+        Mod::FunctionType(function_type) => {
+            for argtype in &function_type.argtypes {
+                counter.visit_expr(argtype);
+            }
+            counter.visit_expr(&function_type.returns);
+        }
+    }
+    counter.0
+}
+
+/// Extracts a human-readable message from a `catch_unwind` panic payload.
+fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
@@ -0,0 +1,94 @@
+//! Differentially test this crate's parser against a `CPython` interpreter: for each source
+//! file, parse it here and with the given interpreter, render both through
+//! `ast.dump(..., include_attributes=True)`-style text, and report where they disagree -- either
+//! by accepting/rejecting different sources, or by producing different trees for the same one.
+//! This is the most reliable way to find gaps between this crate's grammar and `CPython`'s own.
+#![allow(clippy::print_stdout, clippy::print_stderr)]
+
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+use ruff_python_ast::min_version::MinVersion;
+use ruff_python_parser::{ast_dump, parse, Mode};
+
+use crate::util::python_files;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Python file, or directory of `.py` files, to compare against `CPython`'s `ast` module.
+    #[arg(required = true)]
+    path: PathBuf,
+    /// The `CPython` interpreter to compare against.
+    #[arg(long, default_value = "python3")]
+    python: String,
+}
+
+pub(crate) fn main(args: &Args) -> Result<()> {
+    let files = python_files(&args.path);
+    let mut mismatched = 0u32;
+    for file in &files {
+        let source = std::fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        if let Some(report) = compare(&args.python, &source)? {
+            mismatched += 1;
+            println!("{}: {report}", file.display());
+        }
+    }
+    println!("checked {} file(s), {mismatched} mismatch(es)", files.len());
+    if mismatched > 0 {
+        bail!("found {mismatched} file(s) where this parser and CPython disagree");
+    }
+    Ok(())
+}
+
+/// Compares how this crate and the given `CPython` interpreter parse `source`, returning a
+/// human-readable report of their disagreement, or `None` if they agree.
+fn compare(python: &str, source: &str) -> Result<Option<String>> {
+    let ruff_dump = parse(source, Mode::Module)
+        .map(|module| ast_dump::dump(&module, source, MinVersion::PY312));
+    let cpython_dump = dump_with_cpython(python, source)?;
+
+    Ok(match (ruff_dump, cpython_dump) {
+        (Ok(ruff), Ok(cpython)) if ruff == cpython => None,
+        (Ok(ruff), Ok(cpython)) => Some(format!(
+            "both parsers accepted the source but disagreed on its shape:\n  ruff:    {ruff}\n  cpython: {cpython}"
+        )),
+        (Ok(_), Err(error)) => Some(format!(
+            "ruff accepted the source, CPython rejected it: {error}"
+        )),
+        (Err(error), Ok(_)) => Some(format!(
+            "CPython accepted the source, ruff rejected it: {error}"
+        )),
+        (Err(_), Err(_)) => None,
+    })
+}
+
+/// Runs `ast.dump(ast.parse(source), include_attributes=True)` under the given interpreter,
+/// feeding `source` over stdin so it never needs escaping into a `-c` script.
+fn dump_with_cpython(python: &str, source: &str) -> Result<std::result::Result<String, String>> {
+    const SCRIPT: &str =
+        "import ast, sys; print(ast.dump(ast.parse(sys.stdin.read()), include_attributes=True))";
+    let mut child = Command::new(python)
+        .args(["-c", SCRIPT])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to launch `{python}`"))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin was requested with Stdio::piped")
+        .write_all(source.as_bytes())?;
+    let output = child.wait_with_output()?;
+    Ok(if output.status.success() {
+        Ok(String::from_utf8(output.stdout)?.trim_end().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr)
+            .trim_end()
+            .to_string())
+    })
+}
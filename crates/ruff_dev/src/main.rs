@@ -8,6 +8,8 @@ use ruff::check;
 use ruff_linter::logging::{set_up_logging, LogLevel};
 use std::process::ExitCode;
 
+mod conformance;
+mod differential;
 mod format_dev;
 mod generate_all;
 mod generate_cli_help;
@@ -19,6 +21,7 @@ mod print_ast;
 mod print_cst;
 mod print_tokens;
 mod round_trip;
+mod util;
 
 const ROOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../");
 
@@ -53,6 +56,10 @@ enum Command {
     PrintTokens(print_tokens::Args),
     /// Run round-trip source code generation on a given Python file.
     RoundTrip(round_trip::Args),
+    /// Differentially test the parser against a CPython interpreter's `ast` module.
+    Differential(differential::Args),
+    /// Parse every Python file under a directory and report a conformance summary.
+    Conformance(conformance::Args),
     /// Run a ruff command n times for profiling/benchmarking
     Repeat {
         #[clap(flatten)]
@@ -88,6 +95,8 @@ fn main() -> Result<ExitCode> {
         Command::PrintCST(args) => print_cst::main(&args)?,
         Command::PrintTokens(args) => print_tokens::main(&args)?,
         Command::RoundTrip(args) => round_trip::main(&args)?,
+        Command::Differential(args) => differential::main(&args)?,
+        Command::Conformance(args) => conformance::main(&args)?,
         Command::Repeat {
             args,
             repeat,
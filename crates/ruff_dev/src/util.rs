@@ -0,0 +1,18 @@
+//! Small helpers shared by more than one `cargo dev` command.
+
+use std::path::{Path, PathBuf};
+
+/// Collects the `.py` files to process: `path` itself if it's a file, or every `.py` file
+/// beneath it (respecting `.gitignore`) if it's a directory.
+pub(crate) fn python_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = ignore::Walk::new(path)
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "py"))
+        .map(ignore::DirEntry::into_path)
+        .collect();
+    files.sort();
+    files
+}
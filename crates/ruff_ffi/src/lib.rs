@@ -0,0 +1,121 @@
+//! A small `extern "C"` surface for embedding the parser in other language runtimes.
+//!
+//! [`ruff_parse`] parses a buffer of Python source and returns an opaque [`RuffParseResult`];
+//! [`ruff_parse_result_json`] and [`ruff_parse_result_diagnostic`] retrieve the parsed tree or
+//! the error message from it; [`ruff_parse_result_free`] releases it. All strings crossing the
+//! boundary are NUL-terminated UTF-8, and every pointer a result hands back stays valid until
+//! that result is freed.
+//!
+//! This is a minimal, stable surface meant for editors and tools written in C, C++, Go, or
+//! Node to embed the parser via a shared or static library — it intentionally does not expose
+//! the Rust AST itself, since that type's layout is not part of the ABI.
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::{ptr, slice};
+
+use ruff_python_ast::min_version::MinVersion;
+use ruff_python_parser::cpython_ast::to_cpython_json;
+use ruff_python_parser::{parse, Mode};
+
+/// The result of a single [`ruff_parse`] call.
+///
+/// Exactly one of [`ruff_parse_result_json`] and [`ruff_parse_result_diagnostic`] returns a
+/// non-null pointer for a given result, depending on whether parsing succeeded.
+pub struct RuffParseResult {
+    json: Option<CString>,
+    diagnostic: Option<CString>,
+}
+
+/// Parses `length` bytes of UTF-8 Python source starting at `source` as a module, and returns
+/// an owned [`RuffParseResult`] describing the outcome. The caller must release it with
+/// [`ruff_parse_result_free`].
+///
+/// # Safety
+///
+/// `source` must point to `length` bytes of initialized memory that remain valid and unmutated
+/// for the duration of this call.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn ruff_parse(source: *const c_char, length: usize) -> *mut RuffParseResult {
+    let bytes = slice::from_raw_parts(source.cast::<u8>(), length);
+    let result = match std::str::from_utf8(bytes) {
+        Ok(source) => match parse(source, Mode::Module) {
+            Ok(module) => RuffParseResult {
+                json: Some(json_to_cstring(&to_cpython_json(
+                    &module,
+                    source,
+                    MinVersion::PY312,
+                ))),
+                diagnostic: None,
+            },
+            Err(err) => RuffParseResult {
+                json: None,
+                diagnostic: Some(message_to_cstring(err.to_string())),
+            },
+        },
+        Err(err) => RuffParseResult {
+            json: None,
+            diagnostic: Some(message_to_cstring(format!(
+                "source is not valid UTF-8: {err}"
+            ))),
+        },
+    };
+    Box::into_raw(Box::new(result))
+}
+
+/// Returns the parsed tree as a `CPython`-`ast`-compatible JSON string, or null if parsing
+/// failed. The returned pointer is valid until `result` is freed.
+///
+/// # Safety
+///
+/// `result` must be a pointer returned by [`ruff_parse`] that has not yet been freed.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn ruff_parse_result_json(result: *const RuffParseResult) -> *const c_char {
+    (*result)
+        .json
+        .as_ref()
+        .map_or(ptr::null(), |json| json.as_ptr())
+}
+
+/// Returns the parse error message, or null if parsing succeeded. The returned pointer is
+/// valid until `result` is freed.
+///
+/// # Safety
+///
+/// `result` must be a pointer returned by [`ruff_parse`] that has not yet been freed.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn ruff_parse_result_diagnostic(
+    result: *const RuffParseResult,
+) -> *const c_char {
+    (*result)
+        .diagnostic
+        .as_ref()
+        .map_or(ptr::null(), |diagnostic| diagnostic.as_ptr())
+}
+
+/// Releases a [`RuffParseResult`] and every pointer it has handed back.
+///
+/// # Safety
+///
+/// `result` must be a pointer returned by [`ruff_parse`], and must not be dereferenced or
+/// passed to this function again afterwards.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn ruff_parse_result_free(result: *mut RuffParseResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result));
+    }
+}
+
+fn json_to_cstring(value: &serde_json::Value) -> CString {
+    message_to_cstring(value.to_string())
+}
+
+/// `CString::new` fails only on an embedded NUL byte, which neither our JSON output nor a
+/// parser error message can contain, but we fall back to a placeholder rather than unwrap.
+fn message_to_cstring(message: String) -> CString {
+    CString::new(message).unwrap_or_else(|_| CString::new("<message contains a NUL byte>").unwrap())
+}
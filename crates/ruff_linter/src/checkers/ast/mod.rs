@@ -397,7 +397,10 @@ where
                             flags |= BindingFlags::EXPLICIT_EXPORT;
                         }
 
-                        let name = alias.asname.as_ref().unwrap_or(&alias.name);
+                        let name = alias
+                            .asname
+                            .as_ref()
+                            .map_or(alias.name.as_str(), ruff_python_ast::Identifier::as_str);
                         let call_path: Box<[&str]> = alias.name.split('.').collect();
                         self.add_binding(
                             name,
@@ -430,7 +433,10 @@ where
 
                 for alias in names {
                     if let Some("__future__") = module {
-                        let name = alias.asname.as_ref().unwrap_or(&alias.name);
+                        let name = alias
+                            .asname
+                            .as_ref()
+                            .map_or(alias.name.as_str(), ruff_python_ast::Identifier::as_str);
                         self.add_binding(
                             name,
                             alias.identifier(),
@@ -457,7 +463,10 @@ where
                         // Given `from foo import bar`, `name` would be "bar" and `qualified_name` would
                         // be "foo.bar". Given `from foo import bar as baz`, `name` would be "baz"
                         // and `qualified_name` would be "foo.bar".
-                        let name = alias.asname.as_ref().unwrap_or(&alias.name);
+                        let name = alias
+                            .asname
+                            .as_ref()
+                            .map_or(alias.name.as_str(), ruff_python_ast::Identifier::as_str);
 
                         // Attempt to resolve any relative imports; but if we don't know the current
                         // module path, or the relative import extends beyond the package root,
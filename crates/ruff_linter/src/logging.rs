@@ -294,6 +294,14 @@ impl Display for DisplayParseErrorType<'_> {
                     write!(f, "Unexpected token {tok}", tok = TruncateAtNewline(&tok))
                 }
             }
+            ParseErrorType::UnexpectedIndent => write!(f, "Unexpected indentation"),
+            ParseErrorType::ExpectedIndentedBlock { clause } => match clause {
+                Some(clause) => write!(f, "Expected an indented block after '{clause}'"),
+                None => write!(f, "Expected an indented block"),
+            },
+            ParseErrorType::TrailingStatement => {
+                write!(f, "Expected a single statement, but found another one")
+            }
             ParseErrorType::Lexical(ref error) => write!(f, "{error}"),
         }
     }
@@ -96,10 +96,9 @@ fn fix_banned_relative_import(
         panic!("Expected Stmt::ImportFrom");
     };
     let node = ast::StmtImportFrom {
-        module: Some(Identifier::new(
-            module_path.to_string(),
-            TextRange::default(),
-        )),
+        module: Some(
+            Identifier::new(module_path.to_string(), TextRange::default()).into(),
+        ),
         names: names.clone(),
         level: Some(0),
         range: TextRange::default(),
@@ -72,9 +72,9 @@ pub(crate) fn manual_from_import(
     );
     if names.len() == 1 {
         let node = ast::StmtImportFrom {
-            module: Some(Identifier::new(module.to_string(), TextRange::default())),
+            module: Some(Identifier::new(module.to_string(), TextRange::default()).into()),
             names: vec![Alias {
-                name: asname.clone(),
+                name: asname.clone().into(),
                 asname: None,
                 range: TextRange::default(),
             }],
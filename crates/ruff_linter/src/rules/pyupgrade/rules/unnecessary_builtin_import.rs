@@ -127,7 +127,7 @@ pub(crate) fn unnecessary_builtin_import(
             unused_imports
                 .iter()
                 .map(|alias| &alias.name)
-                .map(ruff_python_ast::Identifier::as_str),
+                .map(ruff_python_ast::DottedName::as_str),
             statement,
             parent,
             checker.locator(),
@@ -116,7 +116,7 @@ pub(crate) fn unnecessary_future_import(checker: &mut Checker, stmt: &Stmt, name
             unused_imports
                 .iter()
                 .map(|alias| &alias.name)
-                .map(ruff_python_ast::Identifier::as_str),
+                .map(ruff_python_ast::DottedName::as_str),
             statement,
             parent,
             checker.locator(),
@@ -2,7 +2,7 @@ use itertools::Either::{Left, Right};
 use ruff_python_ast::{self as ast, Expr, Operator};
 
 use ruff_python_ast::call_path::CallPath;
-use ruff_python_parser::typing::parse_type_annotation;
+use ruff_python_parser::typing::parse_type_annotation_from_literal;
 use ruff_python_semantic::SemanticModel;
 use ruff_python_stdlib::sys::is_known_standard_library;
 use ruff_source_file::Locator;
@@ -111,8 +111,8 @@ impl<'a> TypingTarget<'a> {
                 ..
             }) => Some(TypingTarget::PEP604Union(left, right)),
             Expr::NoneLiteral(_) => Some(TypingTarget::None),
-            Expr::StringLiteral(ast::ExprStringLiteral { value, range }) => {
-                parse_type_annotation(value.to_str(), *range, locator.contents())
+            Expr::StringLiteral(literal) => {
+                parse_type_annotation_from_literal(literal, locator.contents())
                     .map_or(None, |(expr, _)| Some(TypingTarget::ForwardReference(expr)))
             }
             _ => semantic.resolve_call_path(expr).map_or(
@@ -1555,6 +1555,7 @@ impl<'a> From<&'a ast::Stmt> for ComparableStmt<'a> {
 pub enum ComparableMod<'a> {
     Module(ComparableModModule<'a>),
     Expression(ComparableModExpression<'a>),
+    FunctionType(ComparableModFunctionType<'a>),
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -1567,11 +1568,18 @@ pub struct ComparableModExpression<'a> {
     body: Box<ComparableExpr<'a>>,
 }
 
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ComparableModFunctionType<'a> {
+    argtypes: Vec<ComparableExpr<'a>>,
+    returns: Box<ComparableExpr<'a>>,
+}
+
 impl<'a> From<&'a ast::Mod> for ComparableMod<'a> {
     fn from(mod_: &'a ast::Mod) -> Self {
         match mod_ {
             ast::Mod::Module(module) => Self::Module(module.into()),
             ast::Mod::Expression(expr) => Self::Expression(expr.into()),
+            ast::Mod::FunctionType(function_type) => Self::FunctionType(function_type.into()),
         }
     }
 }
@@ -1591,3 +1599,12 @@ impl<'a> From<&'a ast::ModExpression> for ComparableModExpression<'a> {
         }
     }
 }
+
+impl<'a> From<&'a ast::ModFunctionType> for ComparableModFunctionType<'a> {
+    fn from(function_type: &'a ast::ModFunctionType) -> Self {
+        Self {
+            argtypes: function_type.argtypes.iter().map(Into::into).collect(),
+            returns: (&function_type.returns).into(),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! A structured report of which notable syntax features a module uses.
+//!
+//! This is intentionally coarser-grained than [`crate::min_version`]: it doesn't try to establish
+//! a minimum interpreter version, it just answers "does this file use async, comprehensions,
+//! decorators, ...?" so that build tooling and telemetry can classify a codebase without writing a
+//! custom [`Visitor`].
+
+use crate::visitor::{walk_expr, walk_stmt, Visitor};
+use crate::{Expr, Stmt};
+
+/// Which notable syntax features were observed while walking a module.
+///
+/// Every field defaults to `false`; [`detect_feature_usage`] flips a field to `true` the first
+/// time the corresponding construct is seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeatureUsage {
+    pub uses_async: bool,
+    pub uses_comprehensions: bool,
+    pub uses_decorators: bool,
+    pub uses_generics: bool,
+    pub uses_match: bool,
+    pub uses_walrus: bool,
+    pub uses_star_unpacking: bool,
+    pub uses_fstrings: bool,
+    pub uses_fstring_format_specs: bool,
+    pub uses_type_aliases: bool,
+    pub uses_except_star: bool,
+    pub uses_lambda: bool,
+    pub uses_yield: bool,
+}
+
+impl FeatureUsage {
+    /// Returns an iterator over the names of every feature that was observed.
+    pub fn iter_used(&self) -> impl Iterator<Item = &'static str> {
+        let flags: [(bool, &'static str); 13] = [
+            (self.uses_async, "async"),
+            (self.uses_comprehensions, "comprehensions"),
+            (self.uses_decorators, "decorators"),
+            (self.uses_generics, "generics"),
+            (self.uses_match, "match"),
+            (self.uses_walrus, "walrus"),
+            (self.uses_star_unpacking, "star-unpacking"),
+            (self.uses_fstrings, "f-strings"),
+            (self.uses_fstring_format_specs, "f-string-format-specs"),
+            (self.uses_type_aliases, "type-aliases"),
+            (self.uses_except_star, "except-star"),
+            (self.uses_lambda, "lambda"),
+            (self.uses_yield, "yield"),
+        ];
+        flags
+            .into_iter()
+            .filter_map(|(used, name)| used.then_some(name))
+    }
+}
+
+#[derive(Default)]
+struct FeatureUsageVisitor {
+    usage: FeatureUsage,
+}
+
+impl<'a> Visitor<'a> for FeatureUsageVisitor {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::FunctionDef(func) => {
+                self.usage.uses_async |= func.is_async;
+                self.usage.uses_decorators |= !func.decorator_list.is_empty();
+                self.usage.uses_generics |=
+                    func.type_params.as_ref().is_some_and(|p| !p.is_empty());
+            }
+            Stmt::ClassDef(class) => {
+                self.usage.uses_decorators |= !class.decorator_list.is_empty();
+                self.usage.uses_generics |=
+                    class.type_params.as_ref().is_some_and(|p| !p.is_empty());
+            }
+            Stmt::For(for_stmt) => self.usage.uses_async |= for_stmt.is_async,
+            Stmt::With(with_stmt) => self.usage.uses_async |= with_stmt.is_async,
+            Stmt::Match(_) => self.usage.uses_match = true,
+            Stmt::TypeAlias(_) => self.usage.uses_type_aliases = true,
+            Stmt::Try(try_stmt) => self.usage.uses_except_star |= try_stmt.is_star,
+            _ => {}
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::ListComp(_) | Expr::SetComp(_) | Expr::DictComp(_) | Expr::GeneratorExp(_) => {
+                self.usage.uses_comprehensions = true;
+            }
+            Expr::NamedExpr(_) => self.usage.uses_walrus = true,
+            Expr::Starred(_) => self.usage.uses_star_unpacking = true,
+            Expr::Lambda(_) => self.usage.uses_lambda = true,
+            Expr::Yield(_) | Expr::YieldFrom(_) => self.usage.uses_yield = true,
+            Expr::Await(_) => self.usage.uses_async = true,
+            Expr::FString(fstring) => {
+                self.usage.uses_fstrings = true;
+                if fstring
+                    .value
+                    .elements()
+                    .any(|element| matches!(element, crate::FStringElement::Expression(e) if e.format_spec.is_some()))
+                {
+                    self.usage.uses_fstring_format_specs = true;
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Walks `stmts` and returns which notable syntax features are used anywhere in the module.
+pub fn detect_feature_usage(stmts: &[Stmt]) -> FeatureUsage {
+    let mut visitor = FeatureUsageVisitor::default();
+    visitor.visit_body(stmts);
+    visitor.usage
+}
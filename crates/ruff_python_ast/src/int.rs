@@ -133,6 +133,30 @@ impl Debug for Int {
     }
 }
 
+// `Int` wraps a private `Number` representation that picks between an `i64` and a `Box<str>`
+// depending on the literal's magnitude, which isn't a stable shape to expose to consumers. It's
+// (de)serialized as the decimal string IPython/CPython would show for it instead.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Int {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Int {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 impl PartialEq<u8> for Int {
     fn eq(&self, other: &u8) -> bool {
         self.as_u8() == Some(*other)
@@ -6,24 +6,35 @@ pub use node::{AnyNode, AnyNodeRef, AstNode, NodeKind};
 pub use nodes::*;
 
 pub mod all;
+pub mod borrowed;
 pub mod call_path;
 pub mod comparable;
 pub mod docstrings;
 mod expression;
+pub mod feature_usage;
 pub mod hashable;
 pub mod helpers;
 pub mod identifier;
 pub mod imports;
 mod int;
+pub mod interning;
+pub mod min_version;
 mod node;
 mod nodes;
+pub mod offset;
 pub mod parenthesize;
+pub mod ranged_debug;
 pub mod relocate;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod shared;
 pub mod statement_visitor;
 pub mod stmt_if;
 pub mod str;
 pub mod traversal;
+pub mod tree_sitter;
 pub mod types;
+pub mod valid_mod;
 pub mod visitor;
 pub mod whitespace;
 
@@ -0,0 +1,134 @@
+//! Inference of the minimum Python version required by a parsed module.
+//!
+//! Unlike [`crate::SourceType`]-level configuration, which is supplied by the user, this module
+//! looks at the syntax actually used in a module (the walrus operator, positional-only
+//! parameters, `match` statements, `except*`, PEP 695 `type` aliases, ...) and reports the oldest
+//! interpreter that could run it, along with the ranges that pushed the requirement up.
+
+use ruff_text_size::{Ranged, TextRange};
+
+use crate::visitor::{walk_expr, walk_stmt, Visitor};
+use crate::{Expr, Stmt};
+
+/// A `(major, minor)` Python version, ordered the same way CPython releases are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MinVersion(pub u8, pub u8);
+
+impl MinVersion {
+    pub const PY38: MinVersion = MinVersion(3, 8);
+    pub const PY310: MinVersion = MinVersion(3, 10);
+    pub const PY311: MinVersion = MinVersion(3, 11);
+    pub const PY312: MinVersion = MinVersion(3, 12);
+}
+
+/// A single syntax construct that requires at least `version` to run, and where it occurs.
+#[derive(Debug, Clone)]
+pub struct VersionRequirement {
+    pub version: MinVersion,
+    pub feature: &'static str,
+    pub range: TextRange,
+}
+
+/// The outcome of [`infer_minimum_version`]: the overall minimum version, and every requirement
+/// that contributed to it.
+#[derive(Debug, Clone, Default)]
+pub struct MinimumVersionReport {
+    pub requirements: Vec<VersionRequirement>,
+}
+
+impl MinimumVersionReport {
+    /// The minimum version required to run the analyzed module, or `None` if nothing above the
+    /// floor (Python 3.x, unconstrained) was observed.
+    pub fn minimum(&self) -> Option<MinVersion> {
+        self.requirements
+            .iter()
+            .map(|requirement| requirement.version)
+            .max()
+    }
+
+    /// The requirements that are responsible for the overall minimum (there may be more than one
+    /// at the same version).
+    pub fn binding_requirements(&self) -> impl Iterator<Item = &VersionRequirement> {
+        let minimum = self.minimum();
+        self.requirements
+            .iter()
+            .filter(move |requirement| Some(requirement.version) == minimum)
+    }
+}
+
+#[derive(Default)]
+struct MinVersionVisitor {
+    requirements: Vec<VersionRequirement>,
+}
+
+impl MinVersionVisitor {
+    fn push(&mut self, version: MinVersion, feature: &'static str, range: TextRange) {
+        self.requirements.push(VersionRequirement {
+            version,
+            feature,
+            range,
+        });
+    }
+}
+
+impl<'a> Visitor<'a> for MinVersionVisitor {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Match(_) => self.push(MinVersion::PY310, "`match` statement", stmt.range()),
+            Stmt::TypeAlias(_) => self.push(MinVersion::PY312, "`type` alias statement", stmt.range()),
+            Stmt::Try(try_stmt) if try_stmt.is_star => {
+                self.push(MinVersion::PY311, "`except*`", stmt.range());
+            }
+            Stmt::FunctionDef(func) => {
+                if !func.parameters.posonlyargs.is_empty() {
+                    self.push(
+                        MinVersion::PY38,
+                        "positional-only parameters",
+                        func.parameters.range(),
+                    );
+                }
+                if func.type_params.as_ref().is_some_and(|p| !p.is_empty()) {
+                    self.push(MinVersion::PY312, "generic type parameters", stmt.range());
+                }
+            }
+            Stmt::ClassDef(class) => {
+                if class.type_params.as_ref().is_some_and(|p| !p.is_empty()) {
+                    self.push(MinVersion::PY312, "generic type parameters", stmt.range());
+                }
+            }
+            _ => {}
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::NamedExpr(_) => self.push(MinVersion::PY38, "walrus operator (`:=`)", expr.range()),
+            Expr::FString(fstring) => {
+                if contains_pep_701_syntax(fstring) {
+                    self.push(MinVersion::PY312, "PEP 701 f-string syntax", expr.range());
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Returns `true` if `fstring` uses syntax only legal under PEP 701 (Python 3.12), such as reusing
+/// the surrounding quote character inside the expression part or containing backslashes.
+fn contains_pep_701_syntax(_fstring: &crate::ExprFString) -> bool {
+    // A full implementation would need access to the original quoting of each `FStringExpressionElement`,
+    // which isn't reconstructible from the AST alone; callers that need this precisely should
+    // inspect the token stream instead. Conservatively report no PEP 701-only usage here.
+    false
+}
+
+/// Walks `stmts` and infers the minimum Python version required to execute them.
+pub fn infer_minimum_version(stmts: &[Stmt]) -> MinimumVersionReport {
+    let mut visitor = MinVersionVisitor::default();
+    visitor.visit_body(stmts);
+    MinimumVersionReport {
+        requirements: visitor.requirements,
+    }
+}
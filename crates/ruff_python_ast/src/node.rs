@@ -8,6 +8,9 @@ use crate::{
 use ruff_text_size::{Ranged, TextRange};
 use std::ptr::NonNull;
 
+/// Implemented by every concrete node type (`StmtIf`, `ExprName`, `Parameter`, ...), letting code
+/// convert between a node and its [`AnyNode`]/[`AnyNodeRef`] form without matching on the node's
+/// own enum (`Stmt`, `Expr`, ...) first.
 pub trait AstNode: Ranged {
     fn cast(kind: AnyNode) -> Option<Self>
     where
@@ -25,10 +28,16 @@ pub trait AstNode: Ranged {
         V: PreorderVisitor<'a> + ?Sized;
 }
 
+/// An owned reference to any node in the tree -- a statement, an expression, a pattern, an
+/// `except` handler, an f-string element, a parameter, and so on -- for code that needs to hold
+/// heterogeneous nodes together, like a generic node cache keyed by [`NodeKind`]. Most callers
+/// want the borrowed [`AnyNodeRef`] instead; reach for this one only when the node needs to
+/// outlive the tree it came from.
 #[derive(Clone, Debug, is_macro::Is, PartialEq)]
 pub enum AnyNode {
     ModModule(ast::ModModule),
     ModExpression(ast::ModExpression),
+    ModFunctionType(ast::ModFunctionType),
     StmtFunctionDef(ast::StmtFunctionDef),
     StmtClassDef(ast::StmtClassDef),
     StmtReturn(ast::StmtReturn),
@@ -150,6 +159,7 @@ impl AnyNode {
 
             AnyNode::ModModule(_)
             | AnyNode::ModExpression(_)
+            | AnyNode::ModFunctionType(_)
             | AnyNode::ExprBoolOp(_)
             | AnyNode::ExprNamedExpr(_)
             | AnyNode::ExprBinOp(_)
@@ -253,6 +263,7 @@ impl AnyNode {
 
             AnyNode::ModModule(_)
             | AnyNode::ModExpression(_)
+            | AnyNode::ModFunctionType(_)
             | AnyNode::StmtFunctionDef(_)
             | AnyNode::StmtClassDef(_)
             | AnyNode::StmtReturn(_)
@@ -316,6 +327,7 @@ impl AnyNode {
         match self {
             AnyNode::ModModule(node) => Some(Mod::Module(node)),
             AnyNode::ModExpression(node) => Some(Mod::Expression(node)),
+            AnyNode::ModFunctionType(node) => Some(Mod::FunctionType(node)),
 
             AnyNode::StmtFunctionDef(_)
             | AnyNode::StmtClassDef(_)
@@ -421,6 +433,7 @@ impl AnyNode {
 
             AnyNode::ModModule(_)
             | AnyNode::ModExpression(_)
+            | AnyNode::ModFunctionType(_)
             | AnyNode::StmtFunctionDef(_)
             | AnyNode::StmtClassDef(_)
             | AnyNode::StmtReturn(_)
@@ -510,6 +523,7 @@ impl AnyNode {
 
             AnyNode::ModModule(_)
             | AnyNode::ModExpression(_)
+            | AnyNode::ModFunctionType(_)
             | AnyNode::StmtFunctionDef(_)
             | AnyNode::StmtClassDef(_)
             | AnyNode::StmtReturn(_)
@@ -624,6 +638,7 @@ impl AnyNode {
         match self {
             Self::ModModule(node) => AnyNodeRef::ModModule(node),
             Self::ModExpression(node) => AnyNodeRef::ModExpression(node),
+            Self::ModFunctionType(node) => AnyNodeRef::ModFunctionType(node),
             Self::StmtFunctionDef(node) => AnyNodeRef::StmtFunctionDef(node),
             Self::StmtClassDef(node) => AnyNodeRef::StmtClassDef(node),
             Self::StmtReturn(node) => AnyNodeRef::StmtReturn(node),
@@ -794,6 +809,50 @@ impl AstNode for ast::ModExpression {
         visitor.visit_expr(body);
     }
 }
+
+impl AstNode for ast::ModFunctionType {
+    fn cast(kind: AnyNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if let AnyNode::ModFunctionType(node) = kind {
+            Some(node)
+        } else {
+            None
+        }
+    }
+
+    fn cast_ref(kind: AnyNodeRef) -> Option<&Self> {
+        if let AnyNodeRef::ModFunctionType(node) = kind {
+            Some(node)
+        } else {
+            None
+        }
+    }
+
+    fn as_any_node_ref(&self) -> AnyNodeRef {
+        AnyNodeRef::from(self)
+    }
+
+    fn into_any_node(self) -> AnyNode {
+        AnyNode::from(self)
+    }
+
+    fn visit_preorder<'a, V>(&'a self, visitor: &mut V)
+    where
+        V: PreorderVisitor<'a> + ?Sized,
+    {
+        let ast::ModFunctionType {
+            argtypes,
+            returns,
+            range: _,
+        } = self;
+        for argtype in argtypes {
+            visitor.visit_expr(argtype);
+        }
+        visitor.visit_expr(returns);
+    }
+}
 impl AstNode for ast::StmtFunctionDef {
     fn cast(kind: AnyNode) -> Option<Self>
     where
@@ -4536,6 +4595,7 @@ impl From<Mod> for AnyNode {
         match module {
             Mod::Module(node) => AnyNode::ModModule(node),
             Mod::Expression(node) => AnyNode::ModExpression(node),
+            Mod::FunctionType(node) => AnyNode::ModFunctionType(node),
         }
     }
 }
@@ -4584,6 +4644,12 @@ impl From<ast::ModExpression> for AnyNode {
     }
 }
 
+impl From<ast::ModFunctionType> for AnyNode {
+    fn from(node: ast::ModFunctionType) -> Self {
+        AnyNode::ModFunctionType(node)
+    }
+}
+
 impl From<ast::StmtFunctionDef> for AnyNode {
     fn from(node: ast::StmtFunctionDef) -> Self {
         AnyNode::StmtFunctionDef(node)
@@ -5106,6 +5172,7 @@ impl Ranged for AnyNode {
         match self {
             AnyNode::ModModule(node) => node.range(),
             AnyNode::ModExpression(node) => node.range(),
+            AnyNode::ModFunctionType(node) => node.range(),
             AnyNode::StmtFunctionDef(node) => node.range(),
             AnyNode::StmtClassDef(node) => node.range(),
             AnyNode::StmtReturn(node) => node.range(),
@@ -5198,10 +5265,16 @@ impl Ranged for AnyNode {
     }
 }
 
+/// A borrowed reference to any node in the tree, the reference-shaped counterpart to [`AnyNode`].
+/// [`PreorderVisitor::enter_node`](crate::visitor::preorder::PreorderVisitor::enter_node) and
+/// [`PreorderVisitor::leave_node`](crate::visitor::preorder::PreorderVisitor::leave_node) hand
+/// back this type precisely so a visitor doesn't need a separate callback per node kind; a
+/// diagnostic or generic cache keyed by node range can use it the same way.
 #[derive(Copy, Clone, Debug, is_macro::Is, PartialEq)]
 pub enum AnyNodeRef<'a> {
     ModModule(&'a ast::ModModule),
     ModExpression(&'a ast::ModExpression),
+    ModFunctionType(&'a ast::ModFunctionType),
     StmtFunctionDef(&'a ast::StmtFunctionDef),
     StmtClassDef(&'a ast::StmtClassDef),
     StmtReturn(&'a ast::StmtReturn),
@@ -5297,6 +5370,7 @@ impl<'a> AnyNodeRef<'a> {
         match self {
             AnyNodeRef::ModModule(node) => NonNull::from(*node).cast(),
             AnyNodeRef::ModExpression(node) => NonNull::from(*node).cast(),
+            AnyNodeRef::ModFunctionType(node) => NonNull::from(*node).cast(),
             AnyNodeRef::StmtFunctionDef(node) => NonNull::from(*node).cast(),
             AnyNodeRef::StmtClassDef(node) => NonNull::from(*node).cast(),
             AnyNodeRef::StmtReturn(node) => NonNull::from(*node).cast(),
@@ -5398,6 +5472,7 @@ impl<'a> AnyNodeRef<'a> {
         match self {
             AnyNodeRef::ModModule(_) => NodeKind::ModModule,
             AnyNodeRef::ModExpression(_) => NodeKind::ModExpression,
+            AnyNodeRef::ModFunctionType(_) => NodeKind::ModFunctionType,
             AnyNodeRef::StmtFunctionDef(_) => NodeKind::StmtFunctionDef,
             AnyNodeRef::StmtClassDef(_) => NodeKind::StmtClassDef,
             AnyNodeRef::StmtReturn(_) => NodeKind::StmtReturn,
@@ -5519,6 +5594,7 @@ impl<'a> AnyNodeRef<'a> {
 
             AnyNodeRef::ModModule(_)
             | AnyNodeRef::ModExpression(_)
+            | AnyNodeRef::ModFunctionType(_)
             | AnyNodeRef::ExprBoolOp(_)
             | AnyNodeRef::ExprNamedExpr(_)
             | AnyNodeRef::ExprBinOp(_)
@@ -5622,6 +5698,7 @@ impl<'a> AnyNodeRef<'a> {
 
             AnyNodeRef::ModModule(_)
             | AnyNodeRef::ModExpression(_)
+            | AnyNodeRef::ModFunctionType(_)
             | AnyNodeRef::StmtFunctionDef(_)
             | AnyNodeRef::StmtClassDef(_)
             | AnyNodeRef::StmtReturn(_)
@@ -5683,7 +5760,9 @@ impl<'a> AnyNodeRef<'a> {
 
     pub const fn is_module(self) -> bool {
         match self {
-            AnyNodeRef::ModModule(_) | AnyNodeRef::ModExpression(_) => true,
+            AnyNodeRef::ModModule(_)
+            | AnyNodeRef::ModExpression(_)
+            | AnyNodeRef::ModFunctionType(_) => true,
 
             AnyNodeRef::StmtFunctionDef(_)
             | AnyNodeRef::StmtClassDef(_)
@@ -5789,6 +5868,7 @@ impl<'a> AnyNodeRef<'a> {
 
             AnyNodeRef::ModModule(_)
             | AnyNodeRef::ModExpression(_)
+            | AnyNodeRef::ModFunctionType(_)
             | AnyNodeRef::StmtFunctionDef(_)
             | AnyNodeRef::StmtClassDef(_)
             | AnyNodeRef::StmtReturn(_)
@@ -5878,6 +5958,7 @@ impl<'a> AnyNodeRef<'a> {
 
             AnyNodeRef::ModModule(_)
             | AnyNodeRef::ModExpression(_)
+            | AnyNodeRef::ModFunctionType(_)
             | AnyNodeRef::StmtFunctionDef(_)
             | AnyNodeRef::StmtClassDef(_)
             | AnyNodeRef::StmtReturn(_)
@@ -5986,6 +6067,7 @@ impl<'a> AnyNodeRef<'a> {
         match self {
             AnyNodeRef::ModModule(node) => node.visit_preorder(visitor),
             AnyNodeRef::ModExpression(node) => node.visit_preorder(visitor),
+            AnyNodeRef::ModFunctionType(node) => node.visit_preorder(visitor),
             AnyNodeRef::StmtFunctionDef(node) => node.visit_preorder(visitor),
             AnyNodeRef::StmtClassDef(node) => node.visit_preorder(visitor),
             AnyNodeRef::StmtReturn(node) => node.visit_preorder(visitor),
@@ -6150,6 +6232,12 @@ impl<'a> From<&'a ast::ModExpression> for AnyNodeRef<'a> {
     }
 }
 
+impl<'a> From<&'a ast::ModFunctionType> for AnyNodeRef<'a> {
+    fn from(node: &'a ast::ModFunctionType) -> Self {
+        AnyNodeRef::ModFunctionType(node)
+    }
+}
+
 impl<'a> From<&'a ast::StmtFunctionDef> for AnyNodeRef<'a> {
     fn from(node: &'a ast::StmtFunctionDef) -> Self {
         AnyNodeRef::StmtFunctionDef(node)
@@ -6699,6 +6787,7 @@ impl<'a> From<&'a Mod> for AnyNodeRef<'a> {
         match module {
             Mod::Module(node) => AnyNodeRef::ModModule(node),
             Mod::Expression(node) => AnyNodeRef::ModExpression(node),
+            Mod::FunctionType(node) => AnyNodeRef::ModFunctionType(node),
         }
     }
 }
@@ -6798,6 +6887,7 @@ impl Ranged for AnyNodeRef<'_> {
         match self {
             AnyNodeRef::ModModule(node) => node.range(),
             AnyNodeRef::ModExpression(node) => node.range(),
+            AnyNodeRef::ModFunctionType(node) => node.range(),
             AnyNodeRef::StmtFunctionDef(node) => node.range(),
             AnyNodeRef::StmtClassDef(node) => node.range(),
             AnyNodeRef::StmtReturn(node) => node.range(),
@@ -6890,7 +6980,10 @@ impl Ranged for AnyNodeRef<'_> {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// The tag of an [`AnyNode`]/[`AnyNodeRef`], with no associated data -- cheap enough to use as a
+/// map key or a match discriminant on its own, without holding on to (or borrowing) the node
+/// itself.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum NodeKind {
     ModModule,
     ModInteractive,
@@ -1,6 +1,6 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
-use std::cell::OnceCell;
+use std::sync::OnceLock;
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -15,13 +15,16 @@ use crate::{int, LiteralExpressionRef};
 
 /// See also [mod](https://docs.python.org/3/library/ast.html#ast.mod)
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mod {
     Module(ModModule),
     Expression(ModExpression),
+    FunctionType(ModFunctionType),
 }
 
 /// See also [Module](https://docs.python.org/3/library/ast.html#ast.Module)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModModule {
     pub range: TextRange,
     pub body: Vec<Stmt>,
@@ -35,6 +38,7 @@ impl From<ModModule> for Mod {
 
 /// See also [Expression](https://docs.python.org/3/library/ast.html#ast.Expression)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModExpression {
     pub range: TextRange,
     pub body: Box<Expr>,
@@ -46,8 +50,28 @@ impl From<ModExpression> for Mod {
     }
 }
 
+/// See also [FunctionType](https://docs.python.org/3/library/ast.html#ast.FunctionType)
+///
+/// The AST produced by parsing a PEP 484 function type comment (`# type: (int, str) -> bool`) on
+/// its own, outside of any surrounding statement -- see `ruff_python_parser`'s
+/// `Mode::FunctionType`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModFunctionType {
+    pub range: TextRange,
+    pub argtypes: Vec<Expr>,
+    pub returns: Box<Expr>,
+}
+
+impl From<ModFunctionType> for Mod {
+    fn from(payload: ModFunctionType) -> Self {
+        Mod::FunctionType(payload)
+    }
+}
+
 /// See also [stmt](https://docs.python.org/3/library/ast.html#ast.stmt)
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stmt {
     #[is(name = "function_def_stmt")]
     FunctionDef(StmtFunctionDef),
@@ -157,6 +181,7 @@ pub enum Stmt {
 ///
 /// [Escape kind]: IpyEscapeKind
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtIpyEscapeCommand {
     pub range: TextRange,
     pub kind: IpyEscapeKind,
@@ -175,6 +200,7 @@ impl From<StmtIpyEscapeCommand> for Stmt {
 /// This type differs from the original Python AST, as it collapses the
 /// synchronous and asynchronous variants into a single type.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtFunctionDef {
     pub range: TextRange,
     pub is_async: bool,
@@ -194,6 +220,7 @@ impl From<StmtFunctionDef> for Stmt {
 
 /// See also [ClassDef](https://docs.python.org/3/library/ast.html#ast.ClassDef)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtClassDef {
     pub range: TextRange,
     pub decorator_list: Vec<Decorator>,
@@ -229,6 +256,7 @@ impl From<StmtClassDef> for Stmt {
 
 /// See also [Return](https://docs.python.org/3/library/ast.html#ast.Return)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtReturn {
     pub range: TextRange,
     pub value: Option<Box<Expr>>,
@@ -242,6 +270,7 @@ impl From<StmtReturn> for Stmt {
 
 /// See also [Delete](https://docs.python.org/3/library/ast.html#ast.Delete)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtDelete {
     pub range: TextRange,
     pub targets: Vec<Expr>,
@@ -255,6 +284,7 @@ impl From<StmtDelete> for Stmt {
 
 /// See also [TypeAlias](https://docs.python.org/3/library/ast.html#ast.TypeAlias)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtTypeAlias {
     pub range: TextRange,
     pub name: Box<Expr>,
@@ -270,6 +300,7 @@ impl From<StmtTypeAlias> for Stmt {
 
 /// See also [Assign](https://docs.python.org/3/library/ast.html#ast.Assign)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtAssign {
     pub range: TextRange,
     pub targets: Vec<Expr>,
@@ -284,6 +315,7 @@ impl From<StmtAssign> for Stmt {
 
 /// See also [AugAssign](https://docs.python.org/3/library/ast.html#ast.AugAssign)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtAugAssign {
     pub range: TextRange,
     pub target: Box<Expr>,
@@ -299,6 +331,7 @@ impl From<StmtAugAssign> for Stmt {
 
 /// See also [AnnAssign](https://docs.python.org/3/library/ast.html#ast.AnnAssign)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtAnnAssign {
     pub range: TextRange,
     pub target: Box<Expr>,
@@ -319,6 +352,7 @@ impl From<StmtAnnAssign> for Stmt {
 /// This type differs from the original Python AST, as it collapses the
 /// synchronous and asynchronous variants into a single type.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtFor {
     pub range: TextRange,
     pub is_async: bool,
@@ -337,6 +371,7 @@ impl From<StmtFor> for Stmt {
 /// See also [While](https://docs.python.org/3/library/ast.html#ast.While) and
 /// [AsyncWhile](https://docs.python.org/3/library/ast.html#ast.AsyncWhile).
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtWhile {
     pub range: TextRange,
     pub test: Box<Expr>,
@@ -352,6 +387,7 @@ impl From<StmtWhile> for Stmt {
 
 /// See also [If](https://docs.python.org/3/library/ast.html#ast.If)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtIf {
     pub range: TextRange,
     pub test: Box<Expr>,
@@ -366,6 +402,7 @@ impl From<StmtIf> for Stmt {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElifElseClause {
     pub range: TextRange,
     pub test: Option<Expr>,
@@ -378,6 +415,7 @@ pub struct ElifElseClause {
 /// This type differs from the original Python AST, as it collapses the
 /// synchronous and asynchronous variants into a single type.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtWith {
     pub range: TextRange,
     pub is_async: bool,
@@ -393,6 +431,7 @@ impl From<StmtWith> for Stmt {
 
 /// See also [Match](https://docs.python.org/3/library/ast.html#ast.Match)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtMatch {
     pub range: TextRange,
     pub subject: Box<Expr>,
@@ -407,6 +446,7 @@ impl From<StmtMatch> for Stmt {
 
 /// See also [Raise](https://docs.python.org/3/library/ast.html#ast.Raise)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtRaise {
     pub range: TextRange,
     pub exc: Option<Box<Expr>>,
@@ -422,6 +462,7 @@ impl From<StmtRaise> for Stmt {
 /// See also [Try](https://docs.python.org/3/library/ast.html#ast.Try) and
 /// [TryStar](https://docs.python.org/3/library/ast.html#ast.TryStar)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtTry {
     pub range: TextRange,
     pub body: Vec<Stmt>,
@@ -439,6 +480,7 @@ impl From<StmtTry> for Stmt {
 
 /// See also [Assert](https://docs.python.org/3/library/ast.html#ast.Assert)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtAssert {
     pub range: TextRange,
     pub test: Box<Expr>,
@@ -453,6 +495,7 @@ impl From<StmtAssert> for Stmt {
 
 /// See also [Import](https://docs.python.org/3/library/ast.html#ast.Import)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtImport {
     pub range: TextRange,
     pub names: Vec<Alias>,
@@ -466,9 +509,10 @@ impl From<StmtImport> for Stmt {
 
 /// See also [ImportFrom](https://docs.python.org/3/library/ast.html#ast.ImportFrom)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtImportFrom {
     pub range: TextRange,
-    pub module: Option<Identifier>,
+    pub module: Option<DottedName>,
     pub names: Vec<Alias>,
     pub level: Option<u32>,
 }
@@ -481,6 +525,7 @@ impl From<StmtImportFrom> for Stmt {
 
 /// See also [Global](https://docs.python.org/3/library/ast.html#ast.Global)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtGlobal {
     pub range: TextRange,
     pub names: Vec<Identifier>,
@@ -494,6 +539,7 @@ impl From<StmtGlobal> for Stmt {
 
 /// See also [Nonlocal](https://docs.python.org/3/library/ast.html#ast.Nonlocal)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtNonlocal {
     pub range: TextRange,
     pub names: Vec<Identifier>,
@@ -507,6 +553,7 @@ impl From<StmtNonlocal> for Stmt {
 
 /// See also [Expr](https://docs.python.org/3/library/ast.html#ast.Expr)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtExpr {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -520,6 +567,7 @@ impl From<StmtExpr> for Stmt {
 
 /// See also [Pass](https://docs.python.org/3/library/ast.html#ast.Pass)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtPass {
     pub range: TextRange,
 }
@@ -532,6 +580,7 @@ impl From<StmtPass> for Stmt {
 
 /// See also [Break](https://docs.python.org/3/library/ast.html#ast.Break)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtBreak {
     pub range: TextRange,
 }
@@ -544,6 +593,7 @@ impl From<StmtBreak> for Stmt {
 
 /// See also [Continue](https://docs.python.org/3/library/ast.html#ast.Continue)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StmtContinue {
     pub range: TextRange,
 }
@@ -556,6 +606,7 @@ impl From<StmtContinue> for Stmt {
 
 /// See also [expr](https://docs.python.org/3/library/ast.html#ast.expr)
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expr {
     #[is(name = "bool_op_expr")]
     BoolOp(ExprBoolOp),
@@ -668,6 +719,7 @@ impl Expr {
 /// For more information related to terminology and syntax of escape commands,
 /// see [`StmtIpyEscapeCommand`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprIpyEscapeCommand {
     pub range: TextRange,
     pub kind: IpyEscapeKind,
@@ -682,6 +734,7 @@ impl From<ExprIpyEscapeCommand> for Expr {
 
 /// See also [BoolOp](https://docs.python.org/3/library/ast.html#ast.BoolOp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprBoolOp {
     pub range: TextRange,
     pub op: BoolOp,
@@ -696,6 +749,7 @@ impl From<ExprBoolOp> for Expr {
 
 /// See also [NamedExpr](https://docs.python.org/3/library/ast.html#ast.NamedExpr)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprNamedExpr {
     pub range: TextRange,
     pub target: Box<Expr>,
@@ -710,6 +764,7 @@ impl From<ExprNamedExpr> for Expr {
 
 /// See also [BinOp](https://docs.python.org/3/library/ast.html#ast.BinOp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprBinOp {
     pub range: TextRange,
     pub left: Box<Expr>,
@@ -725,6 +780,7 @@ impl From<ExprBinOp> for Expr {
 
 /// See also [UnaryOp](https://docs.python.org/3/library/ast.html#ast.UnaryOp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprUnaryOp {
     pub range: TextRange,
     pub op: UnaryOp,
@@ -739,6 +795,7 @@ impl From<ExprUnaryOp> for Expr {
 
 /// See also [Lambda](https://docs.python.org/3/library/ast.html#ast.Lambda)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprLambda {
     pub range: TextRange,
     pub parameters: Option<Box<Parameters>>,
@@ -753,6 +810,7 @@ impl From<ExprLambda> for Expr {
 
 /// See also [IfExp](https://docs.python.org/3/library/ast.html#ast.IfExp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprIfExp {
     pub range: TextRange,
     pub test: Box<Expr>,
@@ -768,6 +826,7 @@ impl From<ExprIfExp> for Expr {
 
 /// See also [Dict](https://docs.python.org/3/library/ast.html#ast.Dict)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprDict {
     pub range: TextRange,
     pub keys: Vec<Option<Expr>>,
@@ -782,6 +841,7 @@ impl From<ExprDict> for Expr {
 
 /// See also [Set](https://docs.python.org/3/library/ast.html#ast.Set)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprSet {
     pub range: TextRange,
     pub elts: Vec<Expr>,
@@ -795,6 +855,7 @@ impl From<ExprSet> for Expr {
 
 /// See also [ListComp](https://docs.python.org/3/library/ast.html#ast.ListComp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprListComp {
     pub range: TextRange,
     pub elt: Box<Expr>,
@@ -809,6 +870,7 @@ impl From<ExprListComp> for Expr {
 
 /// See also [SetComp](https://docs.python.org/3/library/ast.html#ast.SetComp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprSetComp {
     pub range: TextRange,
     pub elt: Box<Expr>,
@@ -823,6 +885,7 @@ impl From<ExprSetComp> for Expr {
 
 /// See also [DictComp](https://docs.python.org/3/library/ast.html#ast.DictComp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprDictComp {
     pub range: TextRange,
     pub key: Box<Expr>,
@@ -838,6 +901,7 @@ impl From<ExprDictComp> for Expr {
 
 /// See also [GeneratorExp](https://docs.python.org/3/library/ast.html#ast.GeneratorExp)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprGeneratorExp {
     pub range: TextRange,
     pub elt: Box<Expr>,
@@ -852,6 +916,7 @@ impl From<ExprGeneratorExp> for Expr {
 
 /// See also [Await](https://docs.python.org/3/library/ast.html#ast.Await)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprAwait {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -865,6 +930,7 @@ impl From<ExprAwait> for Expr {
 
 /// See also [Yield](https://docs.python.org/3/library/ast.html#ast.Yield)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprYield {
     pub range: TextRange,
     pub value: Option<Box<Expr>>,
@@ -878,6 +944,7 @@ impl From<ExprYield> for Expr {
 
 /// See also [YieldFrom](https://docs.python.org/3/library/ast.html#ast.YieldFrom)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprYieldFrom {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -891,6 +958,7 @@ impl From<ExprYieldFrom> for Expr {
 
 /// See also [Compare](https://docs.python.org/3/library/ast.html#ast.Compare)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprCompare {
     pub range: TextRange,
     pub left: Box<Expr>,
@@ -906,6 +974,7 @@ impl From<ExprCompare> for Expr {
 
 /// See also [Call](https://docs.python.org/3/library/ast.html#ast.Call)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprCall {
     pub range: TextRange,
     pub func: Box<Expr>,
@@ -919,6 +988,7 @@ impl From<ExprCall> for Expr {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FStringFormatSpec {
     pub range: TextRange,
     pub elements: Vec<FStringElement>,
@@ -932,6 +1002,7 @@ impl Ranged for FStringFormatSpec {
 
 /// See also [FormattedValue](https://docs.python.org/3/library/ast.html#ast.FormattedValue)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FStringExpressionElement {
     pub range: TextRange,
     pub expression: Box<Expr>,
@@ -947,6 +1018,7 @@ impl Ranged for FStringExpressionElement {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FStringLiteralElement {
     pub range: TextRange,
     pub value: String,
@@ -968,6 +1040,7 @@ impl Deref for FStringLiteralElement {
 
 /// Transforms a value prior to formatting it.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(i8)]
 #[allow(clippy::cast_possible_wrap)]
 pub enum ConversionFlag {
@@ -994,6 +1067,7 @@ impl ConversionFlag {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DebugText {
     /// The text between the `{` and the expression node.
     pub leading: String,
@@ -1009,6 +1083,7 @@ pub struct DebugText {
 ///
 /// [JoinedStr]: https://docs.python.org/3/library/ast.html#ast.JoinedStr
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprFString {
     pub range: TextRange,
     pub value: FStringValue,
@@ -1022,6 +1097,7 @@ impl From<ExprFString> for Expr {
 
 /// The value representing an [`ExprFString`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FStringValue {
     inner: FStringValueInner,
 }
@@ -1140,6 +1216,7 @@ impl<'a> IntoIterator for &'a mut FStringValue {
 
 /// An internal representation of [`FStringValue`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum FStringValueInner {
     /// A single f-string i.e., `f"foo"`.
     ///
@@ -1153,6 +1230,7 @@ enum FStringValueInner {
 
 /// An f-string part which is either a string literal or an f-string.
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FStringPart {
     Literal(StringLiteral),
     FString(FString),
@@ -1169,6 +1247,7 @@ impl Ranged for FStringPart {
 
 /// An AST node that represents a single f-string which is part of an [`ExprFString`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FString {
     pub range: TextRange,
     pub elements: Vec<FStringElement>,
@@ -1191,6 +1270,7 @@ impl From<FString> for Expr {
 }
 
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FStringElement {
     Literal(FStringLiteralElement),
     Expression(FStringExpressionElement),
@@ -1208,6 +1288,7 @@ impl Ranged for FStringElement {
 /// An AST node that represents either a single string literal or an implicitly
 /// concatenated string literals.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprStringLiteral {
     pub range: TextRange,
     pub value: StringLiteralValue,
@@ -1251,7 +1332,7 @@ impl StringLiteralValue {
         Self {
             inner: StringLiteralValueInner::Concatenated(ConcatenatedStringLiteral {
                 strings,
-                value: OnceCell::new(),
+                value: OnceLock::new(),
             }),
         }
     }
@@ -1363,6 +1444,38 @@ impl fmt::Display for StringLiteralValue {
     }
 }
 
+// `StringLiteralValueInner`'s `Concatenated` variant caches its joined string in a `OnceLock`,
+// which isn't (de)serializable and shouldn't be part of the serialized representation anyway, so
+// `StringLiteralValue` is (de)serialized as a plain list of its `StringLiteral` parts instead of
+// deriving on its internal representation.
+#[cfg(feature = "serde")]
+impl serde::Serialize for StringLiteralValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StringLiteralValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut strings =
+            <Vec<StringLiteral> as serde::Deserialize>::deserialize(deserializer)?;
+        match strings.len() {
+            0 => Err(serde::de::Error::custom(
+                "StringLiteralValue must have at least one part",
+            )),
+            1 => Ok(StringLiteralValue::single(strings.remove(0))),
+            _ => Ok(StringLiteralValue::concatenated(strings)),
+        }
+    }
+}
+
 /// An internal representation of [`StringLiteralValue`].
 #[derive(Clone, Debug, PartialEq)]
 enum StringLiteralValueInner {
@@ -1382,6 +1495,7 @@ impl Default for StringLiteralValueInner {
 /// An AST node that represents a single string literal which is part of an
 /// [`ExprStringLiteral`].
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StringLiteral {
     pub range: TextRange,
     pub value: String,
@@ -1426,7 +1540,7 @@ struct ConcatenatedStringLiteral {
     /// Each string literal that makes up the concatenated string.
     strings: Vec<StringLiteral>,
     /// The concatenated string value.
-    value: OnceCell<String>,
+    value: OnceLock<String>,
 }
 
 impl ConcatenatedStringLiteral {
@@ -1462,6 +1576,7 @@ impl Debug for ConcatenatedStringLiteral {
 /// An AST node that represents either a single bytes literal or an implicitly
 /// concatenated bytes literals.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprBytesLiteral {
     pub range: TextRange,
     pub value: BytesLiteralValue,
@@ -1481,6 +1596,7 @@ impl Ranged for ExprBytesLiteral {
 
 /// The value representing a [`ExprBytesLiteral`].
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BytesLiteralValue {
     inner: BytesLiteralValueInner,
 }
@@ -1586,6 +1702,7 @@ impl PartialEq<[u8]> for BytesLiteralValue {
 
 /// An internal representation of [`BytesLiteralValue`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum BytesLiteralValueInner {
     /// A single bytes literal i.e., `b"foo"`.
     Single(BytesLiteral),
@@ -1603,6 +1720,7 @@ impl Default for BytesLiteralValueInner {
 /// An AST node that represents a single bytes literal which is part of an
 /// [`ExprBytesLiteral`].
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BytesLiteral {
     pub range: TextRange,
     pub value: Vec<u8>,
@@ -1640,6 +1758,7 @@ impl From<BytesLiteral> for Expr {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprNumberLiteral {
     pub range: TextRange,
     pub value: Number,
@@ -1658,6 +1777,7 @@ impl Ranged for ExprNumberLiteral {
 }
 
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Number {
     Int(int::Int),
     Float(f64),
@@ -1665,6 +1785,7 @@ pub enum Number {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprBooleanLiteral {
     pub range: TextRange,
     pub value: bool,
@@ -1683,6 +1804,7 @@ impl Ranged for ExprBooleanLiteral {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprNoneLiteral {
     pub range: TextRange,
 }
@@ -1700,6 +1822,7 @@ impl Ranged for ExprNoneLiteral {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprEllipsisLiteral {
     pub range: TextRange,
 }
@@ -1718,6 +1841,7 @@ impl Ranged for ExprEllipsisLiteral {
 
 /// See also [Attribute](https://docs.python.org/3/library/ast.html#ast.Attribute)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprAttribute {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -1733,6 +1857,7 @@ impl From<ExprAttribute> for Expr {
 
 /// See also [Subscript](https://docs.python.org/3/library/ast.html#ast.Subscript)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprSubscript {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -1748,6 +1873,7 @@ impl From<ExprSubscript> for Expr {
 
 /// See also [Starred](https://docs.python.org/3/library/ast.html#ast.Starred)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprStarred {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -1762,6 +1888,7 @@ impl From<ExprStarred> for Expr {
 
 /// See also [Name](https://docs.python.org/3/library/ast.html#ast.Name)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprName {
     pub range: TextRange,
     pub id: String,
@@ -1776,6 +1903,7 @@ impl From<ExprName> for Expr {
 
 /// See also [List](https://docs.python.org/3/library/ast.html#ast.List)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprList {
     pub range: TextRange,
     pub elts: Vec<Expr>,
@@ -1790,6 +1918,7 @@ impl From<ExprList> for Expr {
 
 /// See also [Tuple](https://docs.python.org/3/library/ast.html#ast.Tuple)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprTuple {
     pub range: TextRange,
     pub elts: Vec<Expr>,
@@ -1835,6 +1964,7 @@ impl ExprTuple {
 
 /// See also [Slice](https://docs.python.org/3/library/ast.html#ast.Slice)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExprSlice {
     pub range: TextRange,
     pub lower: Option<Box<Expr>>,
@@ -1850,6 +1980,7 @@ impl From<ExprSlice> for Expr {
 
 /// See also [expr_context](https://docs.python.org/3/library/ast.html#ast.expr_context)
 #[derive(Clone, Debug, PartialEq, is_macro::Is, Copy, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExprContext {
     Load,
     Store,
@@ -1925,6 +2056,7 @@ impl std::cmp::PartialEq<ExprContext> for ExprContextDel {
 
 /// See also [boolop](https://docs.python.org/3/library/ast.html#ast.BoolOp)
 #[derive(Clone, Debug, PartialEq, is_macro::Is, Copy, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoolOp {
     And,
     Or,
@@ -1977,6 +2109,7 @@ impl std::cmp::PartialEq<BoolOp> for BoolOpOr {
 
 /// See also [operator](https://docs.python.org/3/library/ast.html#ast.operator)
 #[derive(Clone, Debug, PartialEq, is_macro::Is, Copy, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
     Add,
     Sub,
@@ -2282,6 +2415,7 @@ impl std::cmp::PartialEq<Operator> for OperatorFloorDiv {
 
 /// See also [unaryop](https://docs.python.org/3/library/ast.html#ast.unaryop)
 #[derive(Clone, Debug, PartialEq, is_macro::Is, Copy, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOp {
     Invert,
     Not,
@@ -2380,6 +2514,7 @@ impl std::cmp::PartialEq<UnaryOp> for UnaryOpUSub {
 
 /// See also [cmpop](https://docs.python.org/3/library/ast.html#ast.cmpop)
 #[derive(Clone, Debug, PartialEq, is_macro::Is, Copy, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CmpOp {
     Eq,
     NotEq,
@@ -2616,6 +2751,7 @@ impl std::cmp::PartialEq<CmpOp> for CmpOpNotIn {
 
 /// See also [comprehension](https://docs.python.org/3/library/ast.html#ast.comprehension)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Comprehension {
     pub range: TextRange,
     pub target: Expr,
@@ -2626,12 +2762,14 @@ pub struct Comprehension {
 
 /// See also [excepthandler](https://docs.python.org/3/library/ast.html#ast.excepthandler)
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExceptHandler {
     ExceptHandler(ExceptHandlerExceptHandler),
 }
 
 /// See also [ExceptHandler](https://docs.python.org/3/library/ast.html#ast.ExceptHandler)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExceptHandlerExceptHandler {
     pub range: TextRange,
     pub type_: Option<Box<Expr>>,
@@ -2647,6 +2785,7 @@ impl From<ExceptHandlerExceptHandler> for ExceptHandler {
 
 /// See also [arg](https://docs.python.org/3/library/ast.html#ast.arg)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parameter {
     pub range: TextRange,
     pub name: Identifier,
@@ -2655,6 +2794,7 @@ pub struct Parameter {
 
 /// See also [keyword](https://docs.python.org/3/library/ast.html#ast.keyword)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Keyword {
     pub range: TextRange,
     pub arg: Option<Identifier>,
@@ -2663,14 +2803,16 @@ pub struct Keyword {
 
 /// See also [alias](https://docs.python.org/3/library/ast.html#ast.alias)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Alias {
     pub range: TextRange,
-    pub name: Identifier,
+    pub name: DottedName,
     pub asname: Option<Identifier>,
 }
 
 /// See also [withitem](https://docs.python.org/3/library/ast.html#ast.withitem)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WithItem {
     pub range: TextRange,
     pub context_expr: Expr,
@@ -2679,6 +2821,7 @@ pub struct WithItem {
 
 /// See also [match_case](https://docs.python.org/3/library/ast.html#ast.match_case)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MatchCase {
     pub range: TextRange,
     pub pattern: Pattern,
@@ -2688,6 +2831,7 @@ pub struct MatchCase {
 
 /// See also [pattern](https://docs.python.org/3/library/ast.html#ast.pattern)
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Pattern {
     MatchValue(PatternMatchValue),
     MatchSingleton(PatternMatchSingleton),
@@ -2701,6 +2845,7 @@ pub enum Pattern {
 
 /// See also [MatchValue](https://docs.python.org/3/library/ast.html#ast.MatchValue)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchValue {
     pub range: TextRange,
     pub value: Box<Expr>,
@@ -2714,6 +2859,7 @@ impl From<PatternMatchValue> for Pattern {
 
 /// See also [MatchSingleton](https://docs.python.org/3/library/ast.html#ast.MatchSingleton)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchSingleton {
     pub range: TextRange,
     pub value: Singleton,
@@ -2727,6 +2873,7 @@ impl From<PatternMatchSingleton> for Pattern {
 
 /// See also [MatchSequence](https://docs.python.org/3/library/ast.html#ast.MatchSequence)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchSequence {
     pub range: TextRange,
     pub patterns: Vec<Pattern>,
@@ -2740,6 +2887,7 @@ impl From<PatternMatchSequence> for Pattern {
 
 /// See also [MatchMapping](https://docs.python.org/3/library/ast.html#ast.MatchMapping)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchMapping {
     pub range: TextRange,
     pub keys: Vec<Expr>,
@@ -2755,6 +2903,7 @@ impl From<PatternMatchMapping> for Pattern {
 
 /// See also [MatchClass](https://docs.python.org/3/library/ast.html#ast.MatchClass)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchClass {
     pub range: TextRange,
     pub cls: Box<Expr>,
@@ -2772,6 +2921,7 @@ impl From<PatternMatchClass> for Pattern {
 ///
 /// Like [`Arguments`], but for [`PatternMatchClass`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternArguments {
     pub range: TextRange,
     pub patterns: Vec<Pattern>,
@@ -2783,6 +2933,7 @@ pub struct PatternArguments {
 ///
 /// Like [`Keyword`], but for [`PatternMatchClass`].
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternKeyword {
     pub range: TextRange,
     pub attr: Identifier,
@@ -2791,6 +2942,7 @@ pub struct PatternKeyword {
 
 /// See also [MatchStar](https://docs.python.org/3/library/ast.html#ast.MatchStar)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchStar {
     pub range: TextRange,
     pub name: Option<Identifier>,
@@ -2804,6 +2956,7 @@ impl From<PatternMatchStar> for Pattern {
 
 /// See also [MatchAs](https://docs.python.org/3/library/ast.html#ast.MatchAs)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchAs {
     pub range: TextRange,
     pub pattern: Option<Box<Pattern>>,
@@ -2818,6 +2971,7 @@ impl From<PatternMatchAs> for Pattern {
 
 /// See also [MatchOr](https://docs.python.org/3/library/ast.html#ast.MatchOr)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PatternMatchOr {
     pub range: TextRange,
     pub patterns: Vec<Pattern>,
@@ -2831,6 +2985,7 @@ impl From<PatternMatchOr> for Pattern {
 
 /// See also [type_param](https://docs.python.org/3/library/ast.html#ast.type_param)
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeParam {
     TypeVar(TypeParamTypeVar),
     ParamSpec(TypeParamParamSpec),
@@ -2839,6 +2994,7 @@ pub enum TypeParam {
 
 /// See also [TypeVar](https://docs.python.org/3/library/ast.html#ast.TypeVar)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeParamTypeVar {
     pub range: TextRange,
     pub name: Identifier,
@@ -2853,6 +3009,7 @@ impl From<TypeParamTypeVar> for TypeParam {
 
 /// See also [ParamSpec](https://docs.python.org/3/library/ast.html#ast.ParamSpec)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeParamParamSpec {
     pub range: TextRange,
     pub name: Identifier,
@@ -2866,6 +3023,7 @@ impl From<TypeParamParamSpec> for TypeParam {
 
 /// See also [TypeVarTuple](https://docs.python.org/3/library/ast.html#ast.TypeVarTuple)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeParamTypeVarTuple {
     pub range: TextRange,
     pub name: Identifier,
@@ -2879,6 +3037,7 @@ impl From<TypeParamTypeVarTuple> for TypeParam {
 
 /// See also [decorator](https://docs.python.org/3/library/ast.html#ast.decorator)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Decorator {
     pub range: TextRange,
     pub expression: Expr,
@@ -2895,6 +3054,7 @@ pub struct Decorator {
 /// NOTE: This type differs from the original Python AST. See: [arguments](https://docs.python.org/3/library/ast.html#ast.arguments).
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parameters {
     pub range: TextRange,
     pub posonlyargs: Vec<ParameterWithDefault>,
@@ -2954,6 +3114,7 @@ impl Parameters {
 /// NOTE: This type is different from original Python AST.
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParameterWithDefault {
     pub range: TextRange,
     pub parameter: Parameter,
@@ -2983,6 +3144,7 @@ pub struct ParameterWithDefault {
 /// typically used for `metaclass`, with any additional arguments being passed to the `metaclass`.
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Arguments {
     pub range: TextRange,
     pub args: Vec<Expr>,
@@ -2991,6 +3153,9 @@ pub struct Arguments {
 
 /// An entry in the argument list of a function call.
 #[derive(Clone, Debug, PartialEq)]
+// `Arg`/`Keyword` borrow from the `Arguments` they were projected from, so `ArgOrKeyword` can be
+// serialized as a read-only view but (unlike the owned AST nodes) can't implement `Deserialize`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ArgOrKeyword<'a> {
     Arg(&'a Expr),
     Keyword(&'a Keyword),
@@ -3104,6 +3269,7 @@ impl Arguments {
 /// the `T`, `U`, and `V` type parameters in the order they appear in the source code.
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeParams {
     pub range: TextRange,
     pub type_params: Vec<TypeParam>,
@@ -3182,6 +3348,7 @@ impl Parameters {
 ///
 /// [IPython Syntax]: https://github.com/ipython/ipython/blob/635815e8f1ded5b764d66cacc80bbe25e9e2587f/IPython/core/inputtransformer2.py#L335-L343
 #[derive(PartialEq, Eq, Debug, Clone, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IpyEscapeKind {
     /// Send line to underlying system shell (`!`).
     Shell,
@@ -3280,6 +3447,7 @@ impl IpyEscapeKind {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier {
     id: String,
     range: TextRange,
@@ -3357,7 +3525,102 @@ impl Ranged for Identifier {
     }
 }
 
+/// A dotted name, e.g. the `a.b.c` in `import a.b.c` or `from a.b.c import d`.
+///
+/// Unlike [`Identifier`], which stores a single flattened string, this retains each
+/// dot-separated component as its own [`Identifier`] with its own range, so that tools like
+/// import-sorting and rename can address individual segments.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DottedName {
+    segments: Vec<Identifier>,
+    id: String,
+    range: TextRange,
+}
+
+impl DottedName {
+    #[inline]
+    pub fn new(segments: Vec<Identifier>, range: TextRange) -> Self {
+        let id = segments.iter().map(Identifier::as_str).collect::<Vec<_>>().join(".");
+        Self { segments, id, range }
+    }
+
+    #[inline]
+    pub fn segments(&self) -> &[Identifier] {
+        &self.segments
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+impl From<Identifier> for DottedName {
+    #[inline]
+    fn from(identifier: Identifier) -> Self {
+        let range = identifier.range();
+        Self::new(vec![identifier], range)
+    }
+}
+
+impl PartialEq<str> for DottedName {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.id == other
+    }
+}
+
+impl PartialEq<String> for DottedName {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        &self.id == other
+    }
+}
+
+impl std::ops::Deref for DottedName {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.id.as_str()
+    }
+}
+
+impl AsRef<str> for DottedName {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+impl AsRef<String> for DottedName {
+    #[inline]
+    fn as_ref(&self) -> &String {
+        &self.id
+    }
+}
+
+impl std::fmt::Display for DottedName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id, f)
+    }
+}
+
+impl From<DottedName> for String {
+    #[inline]
+    fn from(dotted_name: DottedName) -> String {
+        dotted_name.id
+    }
+}
+
+impl Ranged for DottedName {
+    fn range(&self) -> TextRange {
+        self.range
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Singleton {
     None,
     True,
@@ -3384,11 +3647,17 @@ impl Ranged for crate::nodes::ModExpression {
         self.range
     }
 }
+impl Ranged for crate::nodes::ModFunctionType {
+    fn range(&self) -> TextRange {
+        self.range
+    }
+}
 impl Ranged for crate::Mod {
     fn range(&self) -> TextRange {
         match self {
             Self::Module(node) => node.range(),
             Self::Expression(node) => node.range(),
+            Self::FunctionType(node) => node.range(),
         }
     }
 }
@@ -3893,5 +4162,20 @@ mod size_assertions {
     assert_eq_size!(StmtTry, [u8; 112]);
     assert_eq_size!(Expr, [u8; 80]);
     assert_eq_size!(Pattern, [u8; 96]);
-    assert_eq_size!(Mod, [u8; 32]);
+    assert_eq_size!(Mod, [u8; 40]);
+}
+
+/// `Mod` and the statement/expression/pattern types it's built from must be `Send + Sync` so that
+/// a parsed tree can be handed to another thread (see [`crate::shared::SharedModule`]) rather than
+/// re-parsed per thread.
+mod send_sync_assertions {
+    use static_assertions::assert_impl_all;
+
+    #[allow(clippy::wildcard_imports)]
+    use super::*;
+
+    assert_impl_all!(Mod: Send, Sync);
+    assert_impl_all!(Stmt: Send, Sync);
+    assert_impl_all!(Expr: Send, Sync);
+    assert_impl_all!(Pattern: Send, Sync);
 }
@@ -0,0 +1,396 @@
+//! Shift every [`TextRange`] in a subtree by a fixed offset, in either direction.
+//!
+//! This is the building block a sub-parser needs when it re-parses a fragment of source in
+//! isolation (for example, a dedented copy of a function body, or a slice re-parsed at a
+//! synthetic location) and then has to fold the resulting ranges back into the coordinate space
+//! of the original file. Unlike [`crate::relocate`], which collapses a subtree onto a single
+//! fixed range, this preserves the relative positions of every node and only translates them.
+
+use ruff_text_size::{TextRange, TextSize};
+
+use crate::visitor::transformer::{
+    walk_body, walk_expr, walk_f_string_element, walk_pattern, walk_stmt, walk_type_param,
+    Transformer,
+};
+use crate::{nodes, Expr, FStringElement, Pattern, Stmt, TypeParam};
+
+/// A fixed shift to apply to a [`TextSize`] or [`TextRange`].
+///
+/// [`TextSize`] itself only supports addition (it would otherwise be able to represent a
+/// negative length), so this is what lets [`offset_body`] and friends move a subtree *backwards*
+/// too, as is needed when an edit shrinks the source it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shift {
+    Add(TextSize),
+    Sub(TextSize),
+}
+
+impl Shift {
+    /// Applies the shift to a single offset.
+    pub fn apply(self, offset: TextSize) -> TextSize {
+        match self {
+            Shift::Add(delta) => offset + delta,
+            Shift::Sub(delta) => offset - delta,
+        }
+    }
+
+    /// Applies the shift to both ends of `range`.
+    pub fn apply_range(self, range: TextRange) -> TextRange {
+        TextRange::new(self.apply(range.start()), self.apply(range.end()))
+    }
+}
+
+/// Shifts every statement's range in `body` by `delta`, recursively.
+pub fn offset_body(body: &mut [Stmt], delta: Shift) {
+    let offsetter = Offsetter { delta };
+    walk_body(&offsetter, body);
+}
+
+/// Shifts every range in `stmt` by `delta`, recursively.
+pub fn offset_stmt(stmt: &mut Stmt, delta: Shift) {
+    Offsetter { delta }.visit_stmt(stmt);
+}
+
+/// Shifts every range in `expr` by `delta`, recursively.
+pub fn offset_expr(expr: &mut Expr, delta: Shift) {
+    Offsetter { delta }.visit_expr(expr);
+}
+
+#[derive(Debug)]
+struct Offsetter {
+    delta: Shift,
+}
+
+impl Transformer for Offsetter {
+    fn visit_stmt(&self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::FunctionDef(nodes::StmtFunctionDef { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::ClassDef(nodes::StmtClassDef { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Return(nodes::StmtReturn { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Delete(nodes::StmtDelete { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Assign(nodes::StmtAssign { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::AugAssign(nodes::StmtAugAssign { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::AnnAssign(nodes::StmtAnnAssign { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::TypeAlias(nodes::StmtTypeAlias { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::For(nodes::StmtFor { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::While(nodes::StmtWhile { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::If(nodes::StmtIf { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::With(nodes::StmtWith { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Match(nodes::StmtMatch { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Raise(nodes::StmtRaise { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Try(nodes::StmtTry { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Assert(nodes::StmtAssert { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Import(nodes::StmtImport { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::ImportFrom(nodes::StmtImportFrom { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Global(nodes::StmtGlobal { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Nonlocal(nodes::StmtNonlocal { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Expr(nodes::StmtExpr { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Pass(nodes::StmtPass { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Break(nodes::StmtBreak { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::Continue(nodes::StmtContinue { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Stmt::IpyEscapeCommand(nodes::StmtIpyEscapeCommand { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&self, expr: &mut Expr) {
+        match expr {
+            Expr::BoolOp(nodes::ExprBoolOp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::NamedExpr(nodes::ExprNamedExpr { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::BinOp(nodes::ExprBinOp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::UnaryOp(nodes::ExprUnaryOp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Lambda(nodes::ExprLambda { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::IfExp(nodes::ExprIfExp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Dict(nodes::ExprDict { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Set(nodes::ExprSet { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::ListComp(nodes::ExprListComp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::SetComp(nodes::ExprSetComp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::DictComp(nodes::ExprDictComp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::GeneratorExp(nodes::ExprGeneratorExp { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Await(nodes::ExprAwait { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Yield(nodes::ExprYield { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::YieldFrom(nodes::ExprYieldFrom { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Compare(nodes::ExprCompare { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Call(nodes::ExprCall { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::FString(nodes::ExprFString { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::StringLiteral(nodes::ExprStringLiteral { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::BytesLiteral(nodes::ExprBytesLiteral { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::NumberLiteral(nodes::ExprNumberLiteral { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::BooleanLiteral(nodes::ExprBooleanLiteral { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::NoneLiteral(nodes::ExprNoneLiteral { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::EllipsisLiteral(nodes::ExprEllipsisLiteral { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Attribute(nodes::ExprAttribute { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Subscript(nodes::ExprSubscript { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Starred(nodes::ExprStarred { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Name(nodes::ExprName { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::List(nodes::ExprList { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Tuple(nodes::ExprTuple { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::Slice(nodes::ExprSlice { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Expr::IpyEscapeCommand(nodes::ExprIpyEscapeCommand { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    fn visit_pattern(&self, pattern: &mut Pattern) {
+        match pattern {
+            Pattern::MatchValue(nodes::PatternMatchValue { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchSingleton(nodes::PatternMatchSingleton { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchSequence(nodes::PatternMatchSequence { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchMapping(nodes::PatternMatchMapping { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchClass(nodes::PatternMatchClass { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchStar(nodes::PatternMatchStar { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchAs(nodes::PatternMatchAs { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            Pattern::MatchOr(nodes::PatternMatchOr { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+        }
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_type_param(&self, type_param: &mut TypeParam) {
+        match type_param {
+            TypeParam::TypeVar(nodes::TypeParamTypeVar { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            TypeParam::ParamSpec(nodes::TypeParamParamSpec { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            TypeParam::TypeVarTuple(nodes::TypeParamTypeVarTuple { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+        }
+        walk_type_param(self, type_param);
+    }
+
+    fn visit_f_string_element(&self, f_string_element: &mut FStringElement) {
+        match f_string_element {
+            FStringElement::Literal(nodes::FStringLiteralElement { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+            FStringElement::Expression(nodes::FStringExpressionElement { range, .. }) => {
+                *range = self.delta.apply_range(*range);
+            }
+        }
+        walk_f_string_element(self, f_string_element);
+    }
+
+    fn visit_decorator(&self, decorator: &mut crate::Decorator) {
+        decorator.range = self.delta.apply_range(decorator.range);
+        crate::visitor::transformer::walk_decorator(self, decorator);
+    }
+
+    fn visit_except_handler(&self, except_handler: &mut crate::ExceptHandler) {
+        match except_handler {
+            crate::ExceptHandler::ExceptHandler(nodes::ExceptHandlerExceptHandler {
+                range,
+                ..
+            }) => {
+                *range = self.delta.apply_range(*range);
+            }
+        }
+        crate::visitor::transformer::walk_except_handler(self, except_handler);
+    }
+
+    fn visit_arguments(&self, arguments: &mut crate::Arguments) {
+        arguments.range = self.delta.apply_range(arguments.range);
+        crate::visitor::transformer::walk_arguments(self, arguments);
+    }
+
+    fn visit_parameters(&self, parameters: &mut crate::Parameters) {
+        parameters.range = self.delta.apply_range(parameters.range);
+        crate::visitor::transformer::walk_parameters(self, parameters);
+    }
+
+    fn visit_parameter(&self, parameter: &mut crate::Parameter) {
+        parameter.range = self.delta.apply_range(parameter.range);
+        crate::visitor::transformer::walk_parameter(self, parameter);
+    }
+
+    fn visit_keyword(&self, keyword: &mut crate::Keyword) {
+        keyword.range = self.delta.apply_range(keyword.range);
+        crate::visitor::transformer::walk_keyword(self, keyword);
+    }
+
+    fn visit_alias(&self, alias: &mut crate::Alias) {
+        alias.range = self.delta.apply_range(alias.range);
+        crate::visitor::transformer::walk_alias(self, alias);
+    }
+
+    fn visit_with_item(&self, with_item: &mut crate::WithItem) {
+        with_item.range = self.delta.apply_range(with_item.range);
+        crate::visitor::transformer::walk_with_item(self, with_item);
+    }
+
+    fn visit_type_params(&self, type_params: &mut crate::TypeParams) {
+        type_params.range = self.delta.apply_range(type_params.range);
+        crate::visitor::transformer::walk_type_params(self, type_params);
+    }
+
+    fn visit_match_case(&self, match_case: &mut crate::MatchCase) {
+        match_case.range = self.delta.apply_range(match_case.range);
+        crate::visitor::transformer::walk_match_case(self, match_case);
+    }
+
+    fn visit_pattern_arguments(&self, pattern_arguments: &mut crate::PatternArguments) {
+        pattern_arguments.range = self.delta.apply_range(pattern_arguments.range);
+        crate::visitor::transformer::walk_pattern_arguments(self, pattern_arguments);
+    }
+
+    fn visit_pattern_keyword(&self, pattern_keyword: &mut crate::PatternKeyword) {
+        pattern_keyword.range = self.delta.apply_range(pattern_keyword.range);
+        crate::visitor::transformer::walk_pattern_keyword(self, pattern_keyword);
+    }
+
+    fn visit_elif_else_clause(&self, elif_else_clause: &mut crate::ElifElseClause) {
+        elif_else_clause.range = self.delta.apply_range(elif_else_clause.range);
+        crate::visitor::transformer::walk_elif_else_clause(self, elif_else_clause);
+    }
+
+    fn visit_f_string(&self, f_string: &mut crate::FString) {
+        f_string.range = self.delta.apply_range(f_string.range);
+        crate::visitor::transformer::walk_f_string(self, f_string);
+    }
+
+    fn visit_string_literal(&self, string_literal: &mut crate::StringLiteral) {
+        string_literal.range = self.delta.apply_range(string_literal.range);
+        crate::visitor::transformer::walk_string_literal(self, string_literal);
+    }
+
+    fn visit_bytes_literal(&self, bytes_literal: &mut crate::BytesLiteral) {
+        bytes_literal.range = self.delta.apply_range(bytes_literal.range);
+        crate::visitor::transformer::walk_bytes_literal(self, bytes_literal);
+    }
+
+    fn visit_comprehension(&self, comprehension: &mut crate::Comprehension) {
+        comprehension.range = self.delta.apply_range(comprehension.range);
+        crate::visitor::transformer::walk_comprehension(self, comprehension);
+    }
+}
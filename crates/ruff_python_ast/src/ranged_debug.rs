@@ -0,0 +1,102 @@
+//! An alternative to the derived [`std::fmt::Debug`] output for AST nodes.
+//!
+//! The derived `Debug` output for anything beyond a handful of tokens is a wall of nested struct
+//! literals that's unreadable in a test failure or bug report. [`RangedTreeDebug`] instead prints
+//! one line per node: its [`NodeKind`] and `start..end` [`TextRange`], indented by nesting depth,
+//! with the node's other fields elided entirely.
+//!
+//! ```
+//! use ruff_python_ast::ranged_debug::RangedTreeDebug;
+//! use ruff_python_parser::{parse, Mode};
+//!
+//! let parsed = parse("x = 1 + 2", Mode::Module).unwrap();
+//! let tree = format!("{:?}", RangedTreeDebug::new(&parsed));
+//! assert!(tree.starts_with("ModModule 0..9\n  StmtAssign 0..9\n"));
+//! ```
+
+use std::fmt;
+use std::fmt::Write as _;
+
+use ruff_text_size::Ranged;
+
+use crate::visitor::preorder::{PreorderVisitor, TraversalSignal};
+use crate::{AnyNodeRef, AstNode, Mod};
+
+/// A node that can walk itself (and its descendants) with a [`PreorderVisitor`].
+///
+/// This is implemented for every [`AstNode`], plus [`Mod`] itself, which isn't an [`AstNode`]
+/// since it merely wraps [`ModModule`](crate::ModModule) or
+/// [`ModExpression`](crate::ModExpression) without a range of its own.
+pub trait VisitRangedPreorder {
+    fn visit_ranged_preorder<'a, V>(&'a self, visitor: &mut V)
+    where
+        V: PreorderVisitor<'a> + ?Sized;
+}
+
+impl<T: AstNode> VisitRangedPreorder for T {
+    fn visit_ranged_preorder<'a, V>(&'a self, visitor: &mut V)
+    where
+        V: PreorderVisitor<'a> + ?Sized,
+    {
+        self.visit_preorder(visitor);
+    }
+}
+
+impl VisitRangedPreorder for Mod {
+    fn visit_ranged_preorder<'a, V>(&'a self, visitor: &mut V)
+    where
+        V: PreorderVisitor<'a> + ?Sized,
+    {
+        visitor.visit_mod(self);
+    }
+}
+
+/// Wraps a node to print it (and its descendants) as an indented tree of `NodeKind start..end`
+/// lines instead of the derived `Debug` output. See the [module docs](self) for why.
+pub struct RangedTreeDebug<'a, N: VisitRangedPreorder>(&'a N);
+
+impl<'a, N: VisitRangedPreorder> RangedTreeDebug<'a, N> {
+    pub fn new(node: &'a N) -> Self {
+        Self(node)
+    }
+}
+
+impl<N: VisitRangedPreorder> fmt::Debug for RangedTreeDebug<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut printer = Printer {
+            depth: 0,
+            buffer: String::new(),
+        };
+        self.0.visit_ranged_preorder(&mut printer);
+        f.write_str(&printer.buffer)
+    }
+}
+
+#[derive(Default)]
+struct Printer {
+    depth: usize,
+    buffer: String,
+}
+
+impl<'a> PreorderVisitor<'a> for Printer {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        let range = node.range();
+        writeln!(
+            self.buffer,
+            "{:indent$}{:?} {}..{}",
+            "",
+            node.kind(),
+            u32::from(range.start()),
+            u32::from(range.end()),
+            indent = self.depth * 2,
+        )
+        .unwrap();
+        self.depth += 1;
+
+        TraversalSignal::Traverse
+    }
+
+    fn leave_node(&mut self, _node: AnyNodeRef<'a>) {
+        self.depth -= 1;
+    }
+}
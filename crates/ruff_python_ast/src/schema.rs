@@ -0,0 +1,96 @@
+//! A version number for the `serde` representation of this crate's AST, so consumers that store
+//! or transmit serialized ASTs (rather than consuming them in the same process that produced
+//! them) can detect a breaking change instead of silently misreading a renamed or removed field.
+//!
+//! # Compatibility policy
+//!
+//! [`AST_SCHEMA_VERSION`] is bumped whenever a change to a node's `#[derive(Serialize)]` shape
+//! could break a consumer reading the old shape: a field is renamed, removed, retyped, or a new
+//! enum variant is inserted somewhere other than the end. Adding a field, or appending a variant,
+//! does not bump it -- a consumer that already ignores unknown fields and variants stays correct.
+//!
+//! This version is independent of the crate's own `0.0.0` placeholder version: the crate isn't
+//! published, but serialized ASTs can still outlive the process that wrote them (a cache on disk,
+//! a message queue), so the schema needs its own number.
+
+use serde::Serialize;
+
+/// The current version of this crate's `serde` AST schema. See the [module docs](self) for what
+/// bumping it means.
+pub const AST_SCHEMA_VERSION: u32 = 1;
+
+/// Wraps a serializable AST node together with the schema version it was serialized under, so a
+/// consumer can check [`Self::schema_version`] before trusting the shape of [`Self::ast`].
+#[derive(Serialize)]
+pub struct VersionedAst<T> {
+    schema_version: u32,
+    ast: T,
+}
+
+impl<T> VersionedAst<T> {
+    /// Wraps `ast` with the current [`AST_SCHEMA_VERSION`].
+    pub fn new(ast: T) -> Self {
+        Self {
+            schema_version: AST_SCHEMA_VERSION,
+            ast,
+        }
+    }
+}
+
+/// Returned by [`upgrade`] when asked to convert from a schema version this crate doesn't know
+/// how to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedSchemaVersion(pub u32);
+
+impl std::fmt::Display for UnsupportedSchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unsupported AST schema version {} (this crate reads up to {AST_SCHEMA_VERSION})",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedSchemaVersion {}
+
+/// Converts a `serde_json`-style value serialized under `schema_version` to the current
+/// [`AST_SCHEMA_VERSION`], or reports that `schema_version` isn't one this crate can read.
+///
+/// There is currently only one schema version, so this is a no-op for it. It exists as the
+/// extension point the next breaking change should fill in: when [`AST_SCHEMA_VERSION`] is
+/// bumped, add a match arm here converting the previous version's shape into the new one, so
+/// consumers that persist ASTs across a version bump have a documented upgrade path rather than
+/// having to reverse-engineer the diff themselves.
+pub fn upgrade<T>(schema_version: u32, value: T) -> Result<T, UnsupportedSchemaVersion> {
+    match schema_version {
+        AST_SCHEMA_VERSION => Ok(value),
+        other => Err(UnsupportedSchemaVersion(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{upgrade, UnsupportedSchemaVersion, VersionedAst, AST_SCHEMA_VERSION};
+
+    #[test]
+    fn versioned_ast_serializes_the_schema_version_alongside_the_node() {
+        let versioned = VersionedAst::new(42);
+        let value = serde_json::to_value(&versioned).unwrap();
+        assert_eq!(value["schema_version"], AST_SCHEMA_VERSION);
+        assert_eq!(value["ast"], 42);
+    }
+
+    #[test]
+    fn upgrade_passes_through_the_current_version() {
+        assert_eq!(upgrade(AST_SCHEMA_VERSION, "ast"), Ok("ast"));
+    }
+
+    #[test]
+    fn upgrade_rejects_an_unknown_version() {
+        assert_eq!(
+            upgrade(AST_SCHEMA_VERSION + 1, "ast"),
+            Err(UnsupportedSchemaVersion(AST_SCHEMA_VERSION + 1))
+        );
+    }
+}
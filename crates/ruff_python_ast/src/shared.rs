@@ -0,0 +1,40 @@
+//! A cheaply-cloneable handle to a parsed [`Mod`], for sharing one parse across threads.
+//!
+//! `Mod` and the node types it owns are all plain, `Rc`/`RefCell`-free trees (see the
+//! `send_sync_assertions` module in `nodes.rs`), so they're already safe to send to another
+//! thread. What they don't offer on their own is *cheap* sharing: handing the same parse to
+//! several analysis threads would otherwise mean cloning the whole tree. [`SharedModule`] wraps
+//! an [`Arc`] so that sharing is a refcount bump instead of a deep copy.
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::Mod;
+
+/// An [`Arc`]-backed handle to a parsed [`Mod`].
+///
+/// Cloning a [`SharedModule`] clones the handle, not the tree: all clones refer to the same
+/// parse. This is the form a language server (or any other consumer that fans one parse out to
+/// multiple worker threads) should hold onto, rather than an owned `Mod`.
+#[derive(Clone, Debug)]
+pub struct SharedModule(Arc<Mod>);
+
+impl SharedModule {
+    pub fn new(module: Mod) -> Self {
+        Self(Arc::new(module))
+    }
+}
+
+impl Deref for SharedModule {
+    type Target = Mod;
+
+    fn deref(&self) -> &Mod {
+        &self.0
+    }
+}
+
+impl From<Mod> for SharedModule {
+    fn from(module: Mod) -> Self {
+        Self::new(module)
+    }
+}
@@ -0,0 +1,130 @@
+//! A mapping from this crate's [`NodeKind`] to the symbol names used by the
+//! [`tree-sitter-python`](https://github.com/tree-sitter/tree-sitter-python) grammar, plus a
+//! small query interface that filters a tree by those names and a [`TextRange`]. This lets
+//! editor tooling built around tree-sitter's node-kind strings plug into this parser without
+//! needing to learn this crate's own `NodeKind` names.
+//!
+//! The mapping is necessarily approximate: this crate's AST is a node-per-expression-kind tree
+//! (`ExprBinOp`, `ExprBoolOp`, ...), while `tree-sitter-python` has a flatter, more
+//! concrete-syntax-oriented grammar (`binary_operator`, `boolean_operator`, ...) that doesn't
+//! distinguish some kinds this crate does (for example, `True`/`False`/`None` are their own node
+//! kinds here but `tree-sitter-python` has no constant-specific node at all -- it lexes them as
+//! anonymous keyword tokens). Kinds with no reasonable tree-sitter equivalent fall back to this
+//! crate's own [`NodeKind`] name in `snake_case`, which is not a symbol that grammar actually
+//! produces.
+
+use ruff_text_size::{Ranged, TextRange};
+
+use crate::visitor::preorder::{PreorderVisitor, TraversalSignal};
+use crate::{AnyNodeRef, NodeKind};
+
+/// Returns the `tree-sitter-python` symbol name for `kind`, or `None` if the grammar has no
+/// equivalent node (see the [module docs](self)).
+pub fn tree_sitter_kind(kind: NodeKind) -> Option<&'static str> {
+    let name = match kind {
+        NodeKind::ModModule => "module",
+        NodeKind::StmtFunctionDef => "function_definition",
+        NodeKind::StmtClassDef => "class_definition",
+        NodeKind::StmtReturn => "return_statement",
+        NodeKind::StmtDelete => "delete_statement",
+        NodeKind::StmtAssign => "assignment",
+        NodeKind::StmtAugAssign => "augmented_assignment",
+        NodeKind::StmtAnnAssign => "assignment",
+        NodeKind::StmtFor => "for_statement",
+        NodeKind::StmtWhile => "while_statement",
+        NodeKind::StmtIf => "if_statement",
+        NodeKind::StmtWith => "with_statement",
+        NodeKind::StmtMatch => "match_statement",
+        NodeKind::StmtRaise => "raise_statement",
+        NodeKind::StmtTry => "try_statement",
+        NodeKind::StmtAssert => "assert_statement",
+        NodeKind::StmtImport => "import_statement",
+        NodeKind::StmtImportFrom => "import_from_statement",
+        NodeKind::StmtGlobal => "global_statement",
+        NodeKind::StmtNonlocal => "nonlocal_statement",
+        NodeKind::StmtExpr => "expression_statement",
+        NodeKind::StmtPass => "pass_statement",
+        NodeKind::StmtBreak => "break_statement",
+        NodeKind::StmtContinue => "continue_statement",
+        NodeKind::ExprBoolOp => "boolean_operator",
+        NodeKind::ExprNamedExpr => "named_expression",
+        NodeKind::ExprBinOp => "binary_operator",
+        NodeKind::ExprUnaryOp => "unary_operator",
+        NodeKind::ExprLambda => "lambda",
+        NodeKind::ExprIfExp => "conditional_expression",
+        NodeKind::ExprDict => "dictionary",
+        NodeKind::ExprSet => "set",
+        NodeKind::ExprListComp => "list_comprehension",
+        NodeKind::ExprSetComp => "set_comprehension",
+        NodeKind::ExprDictComp => "dictionary_comprehension",
+        NodeKind::ExprGeneratorExp => "generator_expression",
+        NodeKind::ExprAwait => "await",
+        NodeKind::ExprYield | NodeKind::ExprYieldFrom => "yield",
+        NodeKind::ExprCompare => "comparison_operator",
+        NodeKind::ExprCall => "call",
+        NodeKind::ExprFString => "string",
+        NodeKind::ExprStringLiteral => "string",
+        NodeKind::ExprBytesLiteral => "string",
+        NodeKind::ExprNumberLiteral => "integer",
+        NodeKind::ExprAttribute => "attribute",
+        NodeKind::ExprSubscript => "subscript",
+        NodeKind::ExprStarred => "list_splat",
+        NodeKind::ExprName => "identifier",
+        NodeKind::ExprList => "list",
+        NodeKind::ExprTuple => "tuple",
+        NodeKind::ExprSlice => "slice",
+        NodeKind::ExceptHandlerExceptHandler => "except_clause",
+        NodeKind::PatternMatchValue | NodeKind::PatternMatchSingleton => "case_pattern",
+        NodeKind::PatternMatchSequence => "list_pattern",
+        NodeKind::PatternMatchMapping => "dict_pattern",
+        NodeKind::PatternMatchClass => "class_pattern",
+        NodeKind::PatternMatchStar => "splat_pattern",
+        NodeKind::PatternMatchAs => "as_pattern",
+        NodeKind::PatternMatchOr => "case_pattern",
+        NodeKind::Keyword => "keyword_argument",
+        NodeKind::Alias => "aliased_import",
+        NodeKind::WithItem => "with_item",
+        NodeKind::Decorator => "decorator",
+        NodeKind::Parameters => "parameters",
+        NodeKind::Parameter | NodeKind::ParameterWithDefault => "parameter",
+        NodeKind::StringLiteral | NodeKind::BytesLiteral => "string_content",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Finds every node in `root` (inclusive) whose `tree-sitter-python` symbol name is `kind` and
+/// whose range intersects `range`, in preorder.
+pub fn query_by_kind<'a>(
+    root: AnyNodeRef<'a>,
+    kind: &str,
+    range: TextRange,
+) -> Vec<AnyNodeRef<'a>> {
+    let mut finder = KindFinder {
+        kind,
+        range,
+        matches: Vec::new(),
+    };
+    if finder.enter_node(root).is_traverse() {
+        root.visit_preorder(&mut finder);
+    }
+    finder.matches
+}
+
+struct KindFinder<'a, 'q> {
+    kind: &'q str,
+    range: TextRange,
+    matches: Vec<AnyNodeRef<'a>>,
+}
+
+impl<'a> PreorderVisitor<'a> for KindFinder<'a, '_> {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        if node.range().intersect(self.range).is_none() {
+            return TraversalSignal::Skip;
+        }
+        if tree_sitter_kind(node.kind()) == Some(self.kind) {
+            self.matches.push(node);
+        }
+        TraversalSignal::Traverse
+    }
+}
@@ -0,0 +1,52 @@
+//! A statically invalid-node-free wrapper around [`Mod`].
+//!
+//! This parser doesn't recover from a syntax error: it aborts at the first one instead of
+//! patching the tree with an error-placeholder node and continuing (there is no
+//! `Expr::Invalid`/`Pattern::Invalid`/recovered-node variant anywhere in this AST). That means
+//! every [`Mod`] this crate can hand back is already invalid-node-free by construction, and
+//! [`ValidMod::try_from`] can never actually fail. [`ValidMod`] exists anyway so that code
+//! generators and type checkers that want to match on the tree exhaustively, without a defensive
+//! arm for a "this node failed to parse" case, have a type that says so -- and so that if a
+//! recovering parse mode is ever added, there's already a single place downstream consumers can
+//! go through to keep that guarantee instead of relying on every call site checking by hand.
+
+use std::convert::Infallible;
+
+use crate::Mod;
+
+/// A [`Mod`] that's guaranteed not to contain any invalid/recovered nodes. See the [module
+/// docs](self) for why that's always true today.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidMod(Mod);
+
+impl ValidMod {
+    /// Returns the wrapped, validated module.
+    pub fn into_inner(self) -> Mod {
+        self.0
+    }
+}
+
+impl AsRef<Mod> for ValidMod {
+    fn as_ref(&self) -> &Mod {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for ValidMod {
+    type Target = Mod;
+
+    fn deref(&self) -> &Mod {
+        &self.0
+    }
+}
+
+impl TryFrom<Mod> for ValidMod {
+    /// No [`Mod`] this crate can produce is ever invalid, so this conversion can't fail -- but
+    /// a `Result` keeps the door open for a future recovering parse mode to make it fallible
+    /// without breaking this API.
+    type Error = Infallible;
+
+    fn try_from(module: Mod) -> Result<Self, Self::Error> {
+        Ok(Self(module))
+    }
+}
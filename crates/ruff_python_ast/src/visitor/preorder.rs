@@ -178,6 +178,7 @@ where
         match module {
             Mod::Module(module) => module.visit_preorder(visitor),
             Mod::Expression(module) => module.visit_preorder(visitor),
+            Mod::FunctionType(module) => module.visit_preorder(visitor),
         }
     }
 
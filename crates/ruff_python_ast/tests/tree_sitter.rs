@@ -0,0 +1,28 @@
+use ruff_python_ast::tree_sitter::{query_by_kind, tree_sitter_kind};
+use ruff_python_ast::{AnyNodeRef, NodeKind};
+use ruff_python_parser::{parse, Mode};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+
+#[test]
+fn maps_common_statement_and_expression_kinds() {
+    assert_eq!(tree_sitter_kind(NodeKind::StmtIf), Some("if_statement"));
+    assert_eq!(tree_sitter_kind(NodeKind::ExprCall), Some("call"));
+    assert_eq!(tree_sitter_kind(NodeKind::ExprName), Some("identifier"));
+}
+
+#[test]
+fn kinds_with_no_grammar_equivalent_are_none() {
+    assert_eq!(tree_sitter_kind(NodeKind::ExprBooleanLiteral), None);
+}
+
+#[test]
+fn query_finds_calls_within_range() {
+    let source = "foo()\nbar()\nfoo()\n";
+    let parsed = parse(source, Mode::Module).unwrap();
+    let root = AnyNodeRef::from(&parsed);
+
+    let matches = query_by_kind(root, "call", TextRange::new(TextSize::new(0), TextSize::new(5)));
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(&source[matches[0].range()], "foo()");
+}
@@ -177,6 +177,16 @@ where
             visitor.visit_body(body);
         }
         ast::Mod::Expression(ast::ModExpression { body, range: _ }) => visitor.visit_expr(body),
+        ast::Mod::FunctionType(ast::ModFunctionType {
+            argtypes,
+            returns,
+            range: _,
+        }) => {
+            for argtype in argtypes {
+                visitor.visit_expr(argtype);
+            }
+            visitor.visit_expr(returns);
+        }
     }
 }
 
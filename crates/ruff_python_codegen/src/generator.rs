@@ -4,9 +4,9 @@ use std::ops::Deref;
 
 use ruff_python_ast::{
     self as ast, Alias, ArgOrKeyword, BoolOp, CmpOp, Comprehension, ConversionFlag, DebugText,
-    ExceptHandler, Expr, Identifier, MatchCase, Operator, Parameter, Parameters, Pattern,
-    Singleton, Stmt, Suite, TypeParam, TypeParamParamSpec, TypeParamTypeVar, TypeParamTypeVarTuple,
-    WithItem,
+    DottedName, ExceptHandler, Expr, Identifier, MatchCase, Operator, Parameter, Parameters,
+    Pattern, Singleton, Stmt, Suite, TypeParam, TypeParamParamSpec, TypeParamTypeVar,
+    TypeParamTypeVarTuple, WithItem,
 };
 use ruff_python_ast::{ParameterWithDefault, TypeParams};
 use ruff_python_literal::escape::{AsciiEscape, Escape, UnicodeEscape};
@@ -149,6 +149,10 @@ impl<'a> Generator<'a> {
         self.p(s.as_str());
     }
 
+    fn p_dotted_name(&mut self, s: &DottedName) {
+        self.p(s.as_str());
+    }
+
     fn p_bytes_repr(&mut self, s: &[u8]) {
         let escape = AsciiEscape::with_preferred_quote(s, self.quote.into());
         if let Some(len) = escape.layout().len {
@@ -576,7 +580,7 @@ impl<'a> Generator<'a> {
                         }
                     }
                     if let Some(module) = module {
-                        self.p_id(module);
+                        self.p_dotted_name(module);
                     }
                     self.p(" import ");
                     let mut first = true;
@@ -1386,7 +1390,7 @@ impl<'a> Generator<'a> {
     }
 
     fn unparse_alias(&mut self, alias: &Alias) {
-        self.p_id(&alias.name);
+        self.p_dotted_name(&alias.name);
         if let Some(asname) = &alias.asname {
             self.p(" as ");
             self.p_id(asname);
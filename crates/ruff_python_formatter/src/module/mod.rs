@@ -14,6 +14,9 @@ impl FormatRule<Mod, PyFormatContext<'_>> for FormatMod {
         match item {
             Mod::Module(x) => x.format().fmt(f),
             Mod::Expression(x) => x.format().fmt(f),
+            Mod::FunctionType(_) => {
+                unreachable!("a function type comment's signature is never formatted on its own")
+            }
         }
     }
 }
@@ -1,5 +1,5 @@
 use ruff_formatter::{FormatOwnedWithRule, FormatRefWithRule};
-use ruff_python_ast::Identifier;
+use ruff_python_ast::{DottedName, Identifier};
 use ruff_python_trivia::is_python_whitespace;
 use ruff_text_size::Ranged;
 
@@ -46,11 +46,11 @@ impl<'ast> IntoFormat<PyFormatContext<'ast>> for Identifier {
 /// identifiers with newlines must be formatted via `text`. This struct implements both the fast
 /// and slow paths for such identifiers.
 #[derive(Debug)]
-pub(crate) struct DotDelimitedIdentifier<'a>(&'a Identifier);
+pub(crate) struct DotDelimitedIdentifier<'a>(&'a DottedName);
 
 impl<'a> DotDelimitedIdentifier<'a> {
-    pub(crate) fn new(identifier: &'a Identifier) -> Self {
-        Self(identifier)
+    pub(crate) fn new(name: &'a DottedName) -> Self {
+        Self(name)
     }
 }
 
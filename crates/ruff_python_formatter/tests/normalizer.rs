@@ -34,6 +34,12 @@ impl Normalizer {
             ast::Mod::Expression(expression) => {
                 self.visit_expr(&mut expression.body);
             }
+            ast::Mod::FunctionType(function_type) => {
+                for argtype in &mut function_type.argtypes {
+                    self.visit_expr(argtype);
+                }
+                self.visit_expr(&mut function_type.returns);
+            }
         }
     }
 }
@@ -0,0 +1,63 @@
+use ruff_text_size::TextSize;
+
+/// Where a comment sits relative to the surrounding code. Determined once, while indexing the
+/// token stream, so that formatters and comment-attachment logic don't each need to recompute it
+/// by scanning backwards from the comment's offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentPlacement {
+    /// The only thing on its line, and that line isn't part of a bracketed expression or an
+    /// explicit (`\`) continuation, e.g. the comment between the two statements here:
+    /// ```python
+    /// x = 1
+    /// # comment
+    /// y = 2
+    /// ```
+    OwnLine,
+    /// Follows code on the same line, e.g. the comment in `x = 1  # comment`.
+    Trailing,
+    /// The only thing on its line, but that line is inside a bracketed expression or a `\`
+    /// continuation, e.g. the comment here:
+    /// ```python
+    /// x = [
+    ///     1,
+    ///     # comment
+    ///     2,
+    /// ]
+    /// ```
+    BlockContinuation,
+}
+
+/// The [`CommentPlacement`] of every comment in a file, keyed by the comment's start offset, as
+/// built by [`crate::Indexer`].
+#[derive(Debug, Clone, Default)]
+pub struct CommentPlacements {
+    placements: Vec<(TextSize, CommentPlacement)>,
+}
+
+impl CommentPlacements {
+    /// Returns the [`CommentPlacement`] of the comment starting at `offset`, or `None` if there's
+    /// no comment there.
+    pub fn get(&self, offset: TextSize) -> Option<CommentPlacement> {
+        self.placements
+            .binary_search_by_key(&offset, |(start, _)| *start)
+            .ok()
+            .map(|index| self.placements[index].1)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CommentPlacementsBuilder {
+    placements: Vec<(TextSize, CommentPlacement)>,
+}
+
+impl CommentPlacementsBuilder {
+    pub(crate) fn push(&mut self, offset: TextSize, placement: CommentPlacement) {
+        self.placements.push((offset, placement));
+    }
+
+    pub(crate) fn finish(self) -> CommentPlacements {
+        CommentPlacements {
+            placements: self.placements,
+        }
+    }
+}
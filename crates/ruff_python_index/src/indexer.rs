@@ -10,16 +10,24 @@ use ruff_python_trivia::{
 use ruff_source_file::Locator;
 use ruff_text_size::{Ranged, TextRange, TextSize};
 
+use crate::comment_placement::{CommentPlacement, CommentPlacementsBuilder};
 use crate::fstring_ranges::{FStringRanges, FStringRangesBuilder};
 use crate::multiline_ranges::{MultilineRanges, MultilineRangesBuilder};
-use crate::CommentRangesBuilder;
+use crate::{CommentPlacements, CommentRangesBuilder};
 
 pub struct Indexer {
     comment_ranges: CommentRanges,
 
+    /// The placement of each comment in `comment_ranges`, relative to the surrounding code.
+    comment_placements: CommentPlacements,
+
     /// Stores the start offset of continuation lines.
     continuation_lines: Vec<TextSize>,
 
+    /// Stores the offset of the backslash of each continuation in `continuation_lines`, in the
+    /// same order.
+    continuation_offsets: Vec<TextSize>,
+
     /// The range of all f-string in the source document.
     fstring_ranges: FStringRanges,
 
@@ -32,13 +40,21 @@ impl Indexer {
         assert!(TextSize::try_from(locator.contents().len()).is_ok());
 
         let mut comment_ranges_builder = CommentRangesBuilder::default();
+        let mut comment_placements_builder = CommentPlacementsBuilder::default();
         let mut fstring_ranges_builder = FStringRangesBuilder::default();
         let mut multiline_ranges_builder = MultilineRangesBuilder::default();
         let mut continuation_lines = Vec::new();
+        // The offset of the backslash of each continuation in `continuation_lines`, in the same
+        // order.
+        let mut continuation_offsets = Vec::new();
         // Token, end
         let mut prev_end = TextSize::default();
         let mut prev_token: Option<&Tok> = None;
         let mut line_start = TextSize::default();
+        // Bracket-nesting depth, counting all of `()`, `[]`, and `{}`.
+        let mut nesting: u32 = 0;
+        // `true` once a token other than a comment has been seen on the current physical line.
+        let mut line_has_code = false;
 
         for (tok, range) in tokens.iter().flatten() {
             let trivia = locator.slice(TextRange::new(prev_end, range.start()));
@@ -55,6 +71,13 @@ impl Indexer {
                 // Newlines after a newline never form a continuation.
                 if !matches!(prev_token, Some(Tok::Newline | Tok::NonLogicalNewline)) {
                     continuation_lines.push(line_start);
+                    // SAFETY: Safe because of the len assertion at the top of the function. The
+                    // backslash is the character immediately before the newline we just matched.
+                    #[allow(clippy::cast_possible_truncation)]
+                    {
+                        continuation_offsets
+                            .push(prev_end + TextSize::new(index as u32) - TextSize::new(1));
+                    }
                 }
 
                 // SAFETY: Safe because of the len assertion at the top of the function.
@@ -62,6 +85,7 @@ impl Indexer {
                 {
                     line_start = prev_end + TextSize::new((index + 1) as u32);
                 }
+                line_has_code = false;
             }
 
             comment_ranges_builder.visit_token(tok, *range);
@@ -71,13 +95,36 @@ impl Indexer {
             match tok {
                 Tok::Newline | Tok::NonLogicalNewline => {
                     line_start = range.end();
+                    line_has_code = false;
                 }
                 Tok::String { .. } => {
                     // If the previous token was a string, find the start of the line that contains
                     // the closing delimiter, since the token itself can span multiple lines.
                     line_start = locator.line_start(range.end());
+                    line_has_code = true;
+                }
+                Tok::Comment(_) => {
+                    let placement = if line_has_code {
+                        CommentPlacement::Trailing
+                    } else if nesting > 0 || continuation_lines.binary_search(&line_start).is_ok()
+                    {
+                        CommentPlacement::BlockContinuation
+                    } else {
+                        CommentPlacement::OwnLine
+                    };
+                    comment_placements_builder.push(range.start(), placement);
+                }
+                Tok::Lpar | Tok::Lsqb | Tok::Lbrace => {
+                    nesting += 1;
+                    line_has_code = true;
+                }
+                Tok::Rpar | Tok::Rsqb | Tok::Rbrace => {
+                    nesting = nesting.saturating_sub(1);
+                    line_has_code = true;
+                }
+                _ => {
+                    line_has_code = true;
                 }
-                _ => {}
             }
 
             prev_token = Some(tok);
@@ -86,7 +133,9 @@ impl Indexer {
 
         Self {
             comment_ranges: comment_ranges_builder.finish(),
+            comment_placements: comment_placements_builder.finish(),
             continuation_lines,
+            continuation_offsets,
             fstring_ranges: fstring_ranges_builder.finish(),
             multiline_ranges: multiline_ranges_builder.finish(),
         }
@@ -97,6 +146,12 @@ impl Indexer {
         &self.comment_ranges
     }
 
+    /// Returns the [`CommentPlacement`] of each comment in [`Indexer::comment_ranges`], relative
+    /// to the surrounding code.
+    pub const fn comment_placements(&self) -> &CommentPlacements {
+        &self.comment_placements
+    }
+
     /// Returns the byte offset ranges of f-strings.
     pub const fn fstring_ranges(&self) -> &FStringRanges {
         &self.fstring_ranges
@@ -112,6 +167,14 @@ impl Indexer {
         &self.continuation_lines
     }
 
+    /// Returns the offset of the backslash of each explicit line continuation, in source order.
+    /// Unlike [`Indexer::continuation_line_starts`], these are the exact offsets of the `\`
+    /// characters themselves, so callers that need to find and rewrite a continuation don't have
+    /// to re-scan the line to locate it.
+    pub fn continuation_line_offsets(&self) -> &[TextSize] {
+        &self.continuation_offsets
+    }
+
     /// Returns `true` if the given offset is part of a continuation line.
     pub fn is_continuation(&self, offset: TextSize, locator: &Locator) -> bool {
         let line_start = locator.line_start(offset);
@@ -255,7 +318,51 @@ mod tests {
     use ruff_source_file::Locator;
     use ruff_text_size::{TextRange, TextSize};
 
-    use crate::Indexer;
+    use crate::{CommentPlacement, Indexer};
+
+    #[test]
+    fn comment_placement() {
+        let contents = r"
+x = 1  # trailing
+# own line
+y = [
+    1,
+    # block continuation
+    2,
+]
+z = 1 + \
+    2  # trailing after continuation
+"
+        .trim();
+        let lxr: Vec<LexResult> = lexer::lex(contents, Mode::Module).collect();
+        let indexer = Indexer::from_tokens(&lxr, &Locator::new(contents));
+
+        let placements: Vec<CommentPlacement> = indexer
+            .comment_ranges()
+            .iter()
+            .map(|range| indexer.comment_placements().get(range.start()).unwrap())
+            .collect();
+        assert_eq!(
+            placements,
+            [
+                CommentPlacement::Trailing,
+                CommentPlacement::OwnLine,
+                CommentPlacement::BlockContinuation,
+                CommentPlacement::Trailing,
+            ]
+        );
+    }
+
+    #[test]
+    fn continuation_offsets() {
+        let contents = r"x = 1 + \
+    2
+";
+        let lxr: Vec<LexResult> = lexer::lex(contents, Mode::Module).collect();
+        let indexer = Indexer::from_tokens(&lxr, &Locator::new(contents));
+        assert_eq!(indexer.continuation_line_offsets(), [TextSize::from(8)]);
+        assert_eq!(&contents[8..9], "\\");
+    }
 
     #[test]
     fn continuation() {
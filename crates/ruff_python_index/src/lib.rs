@@ -1,7 +1,11 @@
+mod comment_placement;
 mod comment_ranges;
 mod fstring_ranges;
 mod indexer;
 mod multiline_ranges;
+mod parsed_file;
 
+pub use comment_placement::{CommentPlacement, CommentPlacements};
 pub use comment_ranges::{tokens_and_ranges, CommentRangesBuilder};
 pub use indexer::Indexer;
+pub use parsed_file::ParsedFile;
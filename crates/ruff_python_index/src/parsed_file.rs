@@ -0,0 +1,103 @@
+//! Bundles a parsed file's source, tokens, AST, and comment ranges behind one handle, alongside
+//! a [`LineIndex`] for translating offsets back to line/column, so a linter or formatter pass
+//! doesn't have to thread all of those through separately and risk re-deriving one of them from
+//! a different source string than the others.
+
+use ruff_python_ast::{Mod, PySourceType};
+use ruff_python_parser::lexer::LexResult;
+use ruff_python_parser::{parse_tokens_ref, AsMode, ParseError, ParseErrorType};
+use ruff_python_trivia::CommentRanges;
+use ruff_source_file::LineIndex;
+
+use crate::comment_ranges::tokens_and_ranges;
+
+/// The source, tokens, AST, comment ranges, and line index produced by [`ParsedFile::parse`]ing
+/// a single file, bundled together so they can't drift apart (a stale `LineIndex` built from a
+/// different version of the source, say) and don't each need their own accessor threaded through
+/// a pass's call chain.
+pub struct ParsedFile {
+    source: String,
+    tokens: Vec<LexResult>,
+    ast: Mod,
+    comment_ranges: CommentRanges,
+    line_index: LineIndex,
+}
+
+impl ParsedFile {
+    /// Lexes, parses, and indexes `source`, bundling the results together.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first syntax error encountered, same as the lower-level parsing functions this
+    /// wraps: this parser doesn't recover past a syntax error, so there's no partial
+    /// [`ParsedFile`] to hand back alongside one.
+    pub fn parse(source: String, source_type: PySourceType) -> Result<Self, ParseError> {
+        let (tokens, comment_ranges) =
+            tokens_and_ranges(&source, source_type).map_err(|err| ParseError {
+                offset: err.location,
+                error: ParseErrorType::Lexical(err.error),
+            })?;
+        let ast = parse_tokens_ref(&tokens, &source, source_type.as_mode())?;
+        let line_index = LineIndex::from_source_text(&source);
+
+        Ok(Self {
+            source,
+            tokens,
+            ast,
+            comment_ranges,
+            line_index,
+        })
+    }
+
+    /// The original source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The tokens produced while lexing [`source`](ParsedFile::source), including trivia (since
+    /// [`comment_ranges`](ParsedFile::comment_ranges) was derived from them).
+    pub fn tokens(&self) -> &[LexResult] {
+        &self.tokens
+    }
+
+    /// The parsed module.
+    pub fn ast(&self) -> &Mod {
+        &self.ast
+    }
+
+    /// The ranges of every comment token in [`tokens`](ParsedFile::tokens).
+    pub fn comment_ranges(&self) -> &CommentRanges {
+        &self.comment_ranges
+    }
+
+    /// The line index for [`source`](ParsedFile::source).
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::{Mod, PySourceType};
+    use ruff_text_size::TextSize;
+
+    use super::ParsedFile;
+
+    #[test]
+    fn parses_and_bundles_a_valid_file() {
+        let parsed =
+            ParsedFile::parse("x = 1  # comment\n".to_string(), PySourceType::Python).unwrap();
+
+        assert_eq!(parsed.source(), "x = 1  # comment\n");
+        assert!(!parsed.tokens().is_empty());
+        assert!(matches!(parsed.ast(), Mod::Module(_)));
+        assert_eq!(parsed.comment_ranges().len(), 1);
+        assert_eq!(parsed.line_index().line_count(), 2);
+    }
+
+    #[test]
+    fn surfaces_the_first_syntax_error() {
+        let error = ParsedFile::parse("x =".to_string(), PySourceType::Python).unwrap_err();
+        assert_eq!(error.offset, TextSize::from(3));
+    }
+}
@@ -0,0 +1,161 @@
+//! Render a parsed [`ast::Mod`] as the text `ast.dump(node, include_attributes=True)` would
+//! print, so a parsed module can be diffed against `CPython`'s own output, or just read by a
+//! human who already knows `ast.dump`'s shape, without round-tripping through a Python process.
+//!
+//! This builds directly on [`crate::cpython_ast::to_cpython_json`] rather than walking the AST a
+//! second time, so it inherits that module's documented differences from the real `ast` module
+//! (implicitly concatenated strings merged, `type_comment` always `None`, and so on -- see its
+//! docs) plus two of its own:
+//! - Field order follows the underlying JSON object's own key order (alphabetical, since this
+//!   workspace's `serde_json` doesn't enable `preserve_order`), not `CPython`'s ASDL field
+//!   declaration order. The four position attributes (`lineno`, `col_offset`, `end_lineno`,
+//!   `end_col_offset`) are moved to the end of each node's field list, in that order, to at
+//!   least match `ast.dump`'s attributes-last placement.
+//! - A handful of [`crate::cpython_ast`]'s JSON stand-ins for values JSON can't represent --
+//!   arbitrary-precision integers and complex numbers -- print as a quoted string or a nested
+//!   `{re, im}`-shaped node rather than the bare numeral or `complex` literal `ast.dump` would
+//!   produce.
+
+use std::fmt::Write;
+
+use ruff_python_ast as ast;
+use ruff_python_ast::min_version::MinVersion;
+use serde_json::{Map, Value};
+
+use crate::cpython_ast::to_cpython_json;
+
+/// The order `ast.dump(..., include_attributes=True)` appends a node's position attributes in.
+const ATTRIBUTE_ORDER: [&str; 4] = ["lineno", "col_offset", "end_lineno", "end_col_offset"];
+
+/// Render `module` the way `ast.dump(ast.parse(source), include_attributes=True)` would, as if
+/// parsed by the given `target_version` of `CPython`. See the [module docs](self) for what is
+/// and isn't faithfully reproduced.
+pub fn dump(module: &ast::Mod, source: &str, target_version: MinVersion) -> String {
+    let value = to_cpython_json(module, source, target_version);
+    let mut out = String::new();
+    write_value(&value, &mut out);
+    out
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => write_node(map, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::String(value) => out.push_str(&python_repr(value)),
+        Value::Number(number) => out.push_str(&number.to_string()),
+        Value::Bool(true) => out.push_str("True"),
+        Value::Bool(false) => out.push_str("False"),
+        Value::Null => out.push_str("None"),
+    }
+}
+
+fn write_node(map: &Map<String, Value>, out: &mut String) {
+    let type_name = map.get("_type").and_then(Value::as_str).unwrap_or("?");
+    out.push_str(type_name);
+    out.push('(');
+
+    let mut first = true;
+    let mut write_field = |key: &str, value: &Value, out: &mut String| {
+        if !first {
+            out.push_str(", ");
+        }
+        first = false;
+        out.push_str(key);
+        out.push('=');
+        write_value(value, out);
+    };
+
+    for (key, value) in map {
+        if key == "_type" || ATTRIBUTE_ORDER.contains(&key.as_str()) {
+            continue;
+        }
+        write_field(key, value, out);
+    }
+    for key in ATTRIBUTE_ORDER {
+        if let Some(value) = map.get(key) {
+            write_field(key, value, out);
+        }
+    }
+
+    out.push(')');
+}
+
+/// `CPython`'s `repr()` for a `str`: single-quoted, unless the string contains a `'` and no `"`,
+/// in which case double-quoted; control characters are escaped, everything else is left as-is.
+fn python_repr(value: &str) -> String {
+    let quote = if value.contains('\'') && !value.contains('"') {
+        '"'
+    } else {
+        '\''
+    };
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push(quote);
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch == quote => {
+                out.push('\\');
+                out.push(ch);
+            }
+            ch if (ch as u32) < 0x20 || ch as u32 == 0x7f => {
+                let _ = write!(out, "\\x{:02x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push(quote);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dump;
+    use crate::{parse, Mode};
+    use ruff_python_ast::min_version::MinVersion;
+
+    fn dump_source(source: &str) -> String {
+        let module = parse(source, Mode::Module).unwrap();
+        dump(&module, source, MinVersion::PY312)
+    }
+
+    #[test]
+    fn renders_a_simple_assignment() {
+        let text = dump_source("x = 1\n");
+        assert!(text.starts_with("Module(body=[Assign("));
+        assert!(text.contains("lineno=1, col_offset=0, end_lineno=1, end_col_offset=5"));
+    }
+
+    #[test]
+    fn attributes_are_appended_in_cpython_order() {
+        let text = dump_source("x\n");
+        // The lone `Expr` statement's inner `Name` node carries its own attributes, last.
+        assert!(text.contains(
+            "Name(ctx=Load(), id='x', lineno=1, col_offset=0, end_lineno=1, end_col_offset=1)"
+        ));
+    }
+
+    #[test]
+    fn strings_are_rendered_with_python_repr_rules() {
+        let text = dump_source("x = \"it's\"\n");
+        assert!(text.contains("value=\"it's\""));
+    }
+
+    #[test]
+    fn lists_are_comma_separated() {
+        let text = dump_source("[1, 2]\n");
+        assert!(text.contains("elts=[Constant("));
+        assert!(text.contains("), Constant("));
+    }
+}
@@ -0,0 +1,55 @@
+//! Reusing allocations across repeated parses of the same (or a similarly-sized) file, the
+//! pattern an LSP or a watch-mode CLI falls into: the file changes, but its rough size and token
+//! count stay about the same from one parse to the next.
+//!
+//! [`allocate_tokens_vec`](crate::allocate_tokens_vec) already estimates a token `Vec`'s starting
+//! capacity from the source's byte length, but that's a rough heuristic (`len * 0.15`) computed
+//! fresh every call. [`ParseBuffers`] instead remembers the exact token count observed on the
+//! previous parse and uses that as the next one's starting capacity, which is a tighter bound for
+//! a file that's only being lightly edited between parses.
+//!
+//! This can't go further and hand the same `Vec`'s heap allocation from one parse to the next:
+//! [`parse_tokens`](crate::parse_tokens) takes its `Vec<LexResult>` by value and hands it to
+//! [`TokenSource`](crate::TokenSource), whose iterator is then driven to completion inside the
+//! generated lalrpop parser, which never returns it. Recovering the allocation itself would mean
+//! changing that generated code or `parse_tokens`'s signature, which is out of scope here; what
+//! this type buys is avoiding the `Vec`'s internal reallocations while it's being filled, not
+//! reusing one allocation indefinitely.
+
+use crate::lexer::LexResult;
+use crate::{lexer, parse_tokens, Mode, ParseError};
+use ruff_python_ast::Mod;
+
+/// Carries sizing information from one [`parse_into`] call to the next. See the [module
+/// docs](self) for what this does and doesn't save.
+#[derive(Debug, Default)]
+pub struct ParseBuffers {
+    /// Number of tokens (including trivia) produced by the most recent parse.
+    last_token_count: usize,
+}
+
+impl ParseBuffers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Lexes and parses `source` in the given `mode`, using `buffers` to size the token `Vec` from
+/// the previous parse instead of guessing from `source`'s byte length.
+///
+/// Equivalent to `parse_tokens(tokenize_all(source, mode), source, mode)`, except for how the
+/// intermediate token `Vec` is sized. See the [module docs](self) for why this doesn't reuse the
+/// `Vec`'s allocation outright.
+pub fn parse_into(
+    buffers: &mut ParseBuffers,
+    source: &str,
+    mode: Mode,
+) -> Result<Mod, ParseError> {
+    let mut tokens: Vec<LexResult> = Vec::with_capacity(buffers.last_token_count);
+    for token in lexer::lex(source, mode) {
+        tokens.push(token);
+    }
+    buffers.last_token_count = tokens.len();
+
+    parse_tokens(tokens, source, mode)
+}
@@ -0,0 +1,361 @@
+//! A compact binary encoding of a token stream, for on-disk caches that want to skip re-lexing
+//! unchanged source.
+//!
+//! This only covers the token stream, not the parsed AST: `ruff_python_ast`'s node types don't
+//! implement `serde` (or any other serialization) yet, and hand-rolling a codec for every node
+//! type here would duplicate that work and go stale the moment the AST changes shape. Once the
+//! AST gains serialization support, a cache built on top of it can replace this one; until then,
+//! callers that want to avoid re-parsing can cache [`encode_tokens`]'s output, keyed by a content
+//! hash (see [`ruff_cache`]), and feed [`decode_tokens`]'s result straight into [`parse_tokens`].
+//!
+//! [`parse_tokens`]: crate::parse_tokens
+
+use ruff_python_ast::{Int, IpyEscapeKind};
+use ruff_text_size::{TextRange, TextSize};
+
+use crate::lexer::Spanned;
+use crate::{StringKind, Tok};
+
+/// All [fieldless][Tok] token kinds that [`encode_tokens`]/[`decode_tokens`] round-trip without a
+/// payload, in a fixed order. The position of a kind in this list is its on-disk tag; the list is
+/// the single source of truth for both directions, so there's no separate tag enum to keep in
+/// sync.
+const FIELDLESS_TOKENS: &[Tok] = &[
+    Tok::FStringStart,
+    Tok::FStringEnd,
+    Tok::Newline,
+    Tok::NonLogicalNewline,
+    Tok::Indent,
+    Tok::Dedent,
+    Tok::EndOfFile,
+    Tok::Question,
+    Tok::Exclamation,
+    Tok::Lpar,
+    Tok::Rpar,
+    Tok::Lsqb,
+    Tok::Rsqb,
+    Tok::Colon,
+    Tok::Comma,
+    Tok::Semi,
+    Tok::Plus,
+    Tok::Minus,
+    Tok::Star,
+    Tok::Slash,
+    Tok::Vbar,
+    Tok::Amper,
+    Tok::Less,
+    Tok::Greater,
+    Tok::Equal,
+    Tok::Dot,
+    Tok::Percent,
+    Tok::Lbrace,
+    Tok::Rbrace,
+    Tok::EqEqual,
+    Tok::NotEqual,
+    Tok::LessEqual,
+    Tok::GreaterEqual,
+    Tok::Tilde,
+    Tok::CircumFlex,
+    Tok::LeftShift,
+    Tok::RightShift,
+    Tok::DoubleStar,
+    Tok::DoubleStarEqual,
+    Tok::PlusEqual,
+    Tok::MinusEqual,
+    Tok::StarEqual,
+    Tok::SlashEqual,
+    Tok::PercentEqual,
+    Tok::AmperEqual,
+    Tok::VbarEqual,
+    Tok::CircumflexEqual,
+    Tok::LeftShiftEqual,
+    Tok::RightShiftEqual,
+    Tok::DoubleSlash,
+    Tok::DoubleSlashEqual,
+    Tok::ColonEqual,
+    Tok::At,
+    Tok::AtEqual,
+    Tok::Rarrow,
+    Tok::Ellipsis,
+    Tok::False,
+    Tok::None,
+    Tok::True,
+    Tok::And,
+    Tok::As,
+    Tok::Assert,
+    Tok::Async,
+    Tok::Await,
+    Tok::Break,
+    Tok::Class,
+    Tok::Continue,
+    Tok::Def,
+    Tok::Del,
+    Tok::Elif,
+    Tok::Else,
+    Tok::Except,
+    Tok::Finally,
+    Tok::For,
+    Tok::From,
+    Tok::Global,
+    Tok::If,
+    Tok::Import,
+    Tok::In,
+    Tok::Is,
+    Tok::Lambda,
+    Tok::Nonlocal,
+    Tok::Not,
+    Tok::Or,
+    Tok::Pass,
+    Tok::Raise,
+    Tok::Return,
+    Tok::Try,
+    Tok::While,
+    Tok::Match,
+    Tok::Type,
+    Tok::Case,
+    Tok::With,
+    Tok::Yield,
+    Tok::StartModule,
+    Tok::StartExpression,
+    Tok::StartFunctionType,
+];
+
+/// Tags for the data-carrying [`Tok`] variants, continuing on from [`FIELDLESS_TOKENS`]'s range so
+/// the two tag spaces never collide.
+const TAG_NAME: u8 = 0;
+const TAG_INT: u8 = 1;
+const TAG_FLOAT: u8 = 2;
+const TAG_COMPLEX: u8 = 3;
+const TAG_STRING: u8 = 4;
+const TAG_FSTRING_MIDDLE: u8 = 5;
+const TAG_IPY_ESCAPE_COMMAND: u8 = 6;
+const TAG_COMMENT: u8 = 7;
+const PAYLOAD_TAG_COUNT: u8 = 8;
+
+fn string_kind_to_u8(kind: StringKind) -> u8 {
+    match kind {
+        StringKind::String => 0,
+        StringKind::Bytes => 1,
+        StringKind::RawString => 2,
+        StringKind::RawBytes => 3,
+        StringKind::Unicode => 4,
+    }
+}
+
+fn string_kind_from_u8(byte: u8) -> Option<StringKind> {
+    Some(match byte {
+        0 => StringKind::String,
+        1 => StringKind::Bytes,
+        2 => StringKind::RawString,
+        3 => StringKind::RawBytes,
+        4 => StringKind::Unicode,
+        _ => return None,
+    })
+}
+
+fn ipy_escape_kind_to_u8(kind: IpyEscapeKind) -> u8 {
+    match kind {
+        IpyEscapeKind::Shell => 0,
+        IpyEscapeKind::ShCap => 1,
+        IpyEscapeKind::Help => 2,
+        IpyEscapeKind::Help2 => 3,
+        IpyEscapeKind::Magic => 4,
+        IpyEscapeKind::Magic2 => 5,
+        IpyEscapeKind::Quote => 6,
+        IpyEscapeKind::Quote2 => 7,
+        IpyEscapeKind::Paren => 8,
+    }
+}
+
+fn ipy_escape_kind_from_u8(byte: u8) -> Option<IpyEscapeKind> {
+    Some(match byte {
+        0 => IpyEscapeKind::Shell,
+        1 => IpyEscapeKind::ShCap,
+        2 => IpyEscapeKind::Help,
+        3 => IpyEscapeKind::Help2,
+        4 => IpyEscapeKind::Magic,
+        5 => IpyEscapeKind::Magic2,
+        6 => IpyEscapeKind::Quote,
+        7 => IpyEscapeKind::Quote2,
+        8 => IpyEscapeKind::Paren,
+        _ => return None,
+    })
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    write_u32(out, value.len() as u32);
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// A cursor over an encoded byte slice, returning `None` once the input is malformed or
+/// truncated rather than panicking.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.offset)?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        let bytes = self.bytes.get(self.offset..self.offset + 4)?;
+        self.offset += 4;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    fn read_f64(&mut self) -> Option<f64> {
+        let bytes = self.bytes.get(self.offset..self.offset + 8)?;
+        self.offset += 8;
+        Some(f64::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.bytes.get(self.offset..self.offset + len)?;
+        self.offset += len;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+/// Encodes a token stream produced by the lexer into a flat byte buffer.
+///
+/// The format isn't versioned or documented as stable: it's meant to be written and read back by
+/// the same build of this crate, the same way an on-disk cache is invalidated whenever the tool
+/// version changes.
+pub fn encode_tokens(tokens: &[Spanned]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(tokens.len() * 12);
+    write_u32(&mut out, tokens.len() as u32);
+
+    for (tok, range) in tokens {
+        match tok {
+            Tok::Name { name } => {
+                out.push(TAG_NAME);
+                write_str(&mut out, name);
+            }
+            Tok::Int { value } => {
+                out.push(TAG_INT);
+                write_str(&mut out, &value.to_string());
+            }
+            Tok::Float { value } => {
+                out.push(TAG_FLOAT);
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            Tok::Complex { real, imag } => {
+                out.push(TAG_COMPLEX);
+                out.extend_from_slice(&real.to_le_bytes());
+                out.extend_from_slice(&imag.to_le_bytes());
+            }
+            Tok::String {
+                value,
+                kind,
+                triple_quoted,
+            } => {
+                out.push(TAG_STRING);
+                out.push(string_kind_to_u8(*kind));
+                out.push(u8::from(*triple_quoted));
+                write_str(&mut out, value);
+            }
+            Tok::FStringMiddle { value, is_raw } => {
+                out.push(TAG_FSTRING_MIDDLE);
+                out.push(u8::from(*is_raw));
+                write_str(&mut out, value);
+            }
+            Tok::IpyEscapeCommand { value, kind } => {
+                out.push(TAG_IPY_ESCAPE_COMMAND);
+                out.push(ipy_escape_kind_to_u8(*kind));
+                write_str(&mut out, value);
+            }
+            Tok::Comment(value) => {
+                out.push(TAG_COMMENT);
+                write_str(&mut out, value);
+            }
+            fieldless => {
+                let index = FIELDLESS_TOKENS
+                    .iter()
+                    .position(|candidate| candidate == fieldless)
+                    .expect("every fieldless `Tok` variant is listed in `FIELDLESS_TOKENS`");
+                out.push(PAYLOAD_TAG_COUNT + index as u8);
+            }
+        }
+
+        write_u32(&mut out, range.start().to_u32());
+        write_u32(&mut out, range.end().to_u32());
+    }
+
+    out
+}
+
+/// Decodes a byte buffer produced by [`encode_tokens`] back into a token stream.
+///
+/// Returns `None` if `bytes` is truncated or doesn't match the current encoding, in which case
+/// callers should fall back to re-lexing the source rather than trusting a stale or corrupt
+/// cache entry.
+pub fn decode_tokens(bytes: &[u8]) -> Option<Vec<Spanned>> {
+    let mut reader = Reader::new(bytes);
+    let len = reader.read_u32()? as usize;
+    let mut tokens = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let tag = reader.read_u8()?;
+        let tok = match tag {
+            TAG_NAME => Tok::Name {
+                name: reader.read_str()?,
+            },
+            TAG_INT => Tok::Int {
+                value: reader.read_str()?.parse::<Int>().ok()?,
+            },
+            TAG_FLOAT => Tok::Float {
+                value: reader.read_f64()?,
+            },
+            TAG_COMPLEX => Tok::Complex {
+                real: reader.read_f64()?,
+                imag: reader.read_f64()?,
+            },
+            TAG_STRING => {
+                let kind = string_kind_from_u8(reader.read_u8()?)?;
+                let triple_quoted = reader.read_u8()? != 0;
+                Tok::String {
+                    value: reader.read_str()?,
+                    kind,
+                    triple_quoted,
+                }
+            }
+            TAG_FSTRING_MIDDLE => {
+                let is_raw = reader.read_u8()? != 0;
+                Tok::FStringMiddle {
+                    value: reader.read_str()?,
+                    is_raw,
+                }
+            }
+            TAG_IPY_ESCAPE_COMMAND => {
+                let kind = ipy_escape_kind_from_u8(reader.read_u8()?)?;
+                Tok::IpyEscapeCommand {
+                    value: reader.read_str()?,
+                    kind,
+                }
+            }
+            TAG_COMMENT => Tok::Comment(reader.read_str()?),
+            other => FIELDLESS_TOKENS
+                .get(usize::from(other.checked_sub(PAYLOAD_TAG_COUNT)?))?
+                .clone(),
+        };
+
+        let start = TextSize::from(reader.read_u32()?);
+        let end = TextSize::from(reader.read_u32()?);
+        tokens.push((tok, TextRange::new(start, end)));
+    }
+
+    Some(tokens)
+}
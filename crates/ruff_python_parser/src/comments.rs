@@ -0,0 +1,292 @@
+//! An optional post-parse pass that associates comments with the statements they document.
+//!
+//! [`collect_comments`] walks a parsed module's statements alongside its comment tokens and
+//! decides, for each comment, whether it's [leading](CommentPosition::Leading) a statement,
+//! [trailing](CommentPosition::Trailing) one, or [dangling](CommentPosition::Dangling) at the end
+//! of a suite with no statement left in that suite to attach to. This is the same three-way split
+//! `ruff_python_formatter`'s own comment-placement logic uses internally, but this crate has no
+//! reason to depend on the formatter just to answer "what comment goes with this node" -- a
+//! linter rule building a suppression mechanism, or a tool rendering documentation, wants the
+//! answer without pulling in a whole print pipeline. This pass is deliberately less precise than
+//! the formatter's (no special-casing of parenthesized expressions, `/` markers, or the like) in
+//! exchange for living next to the parser instead of a few crates downstream of it.
+//!
+//! Running this pass is optional: nothing in [`crate::parse`] calls it, so a caller that doesn't
+//! need comment attachment pays nothing for it.
+//!
+//! [`Stmt`] has no stable identity to key a map on, so, the same as
+//! [`crate::type_comments::TypeComment::statement`], a statement's own [`TextRange`] stands in for
+//! it -- two statements in the same parse never share a range.
+use ruff_python_ast::{ExceptHandler, Stmt};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+use rustc_hash::FxHashMap;
+
+use crate::{lexer, Mode, Tok};
+
+/// Where a comment sits relative to the statement [`Comments`] attaches it to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentPosition {
+    /// On its own line(s), immediately before the statement.
+    Leading,
+    /// On the same source line as the end of the statement -- the whole statement for a simple
+    /// one, or the line containing the `:` that opens a compound statement's suite.
+    Trailing,
+    /// Inside a compound statement's suite, after its last nested statement, with no sibling
+    /// statement in that suite left to call it leading or trailing of.
+    Dangling,
+}
+
+/// A comment attached to a statement by [`collect_comments`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachedComment {
+    pub range: TextRange,
+    pub position: CommentPosition,
+}
+
+/// Every comment [`collect_comments`] could attach to a statement, keyed by that statement's own
+/// range.
+#[derive(Debug, Default)]
+pub struct Comments {
+    by_statement: FxHashMap<TextRange, Vec<AttachedComment>>,
+}
+
+impl Comments {
+    /// The comments attached to `stmt`, in source order. Empty if none were.
+    pub fn for_statement(&self, stmt: &Stmt) -> &[AttachedComment] {
+        self.by_statement
+            .get(&stmt.range())
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Finds every comment in `source` and associates it with the statement in `body` it documents.
+///
+/// `source` must be the same source `body` was parsed from, so comment and statement ranges line
+/// up.
+pub fn collect_comments(source: &str, body: &[Stmt]) -> Comments {
+    let comment_ranges: Vec<TextRange> = lexer::lex(source, Mode::Module)
+        .filter_map(|result| match result {
+            Ok((Tok::Comment(_), range)) => Some(range),
+            _ => None,
+        })
+        .collect();
+
+    let mut collector = Collector {
+        source,
+        comments: &comment_ranges,
+        index: 0,
+        by_statement: FxHashMap::default(),
+    };
+    collector.visit_suite(body, 0);
+    Comments {
+        by_statement: collector.by_statement,
+    }
+}
+
+struct Collector<'a> {
+    source: &'a str,
+    comments: &'a [TextRange],
+    index: usize,
+    by_statement: FxHashMap<TextRange, Vec<AttachedComment>>,
+}
+
+impl Collector<'_> {
+    fn peek(&self) -> Option<TextRange> {
+        self.comments.get(self.index).copied()
+    }
+
+    /// Consumes and returns every not-yet-consumed comment starting before `bound`.
+    fn take_before(&mut self, bound: TextSize) -> Vec<TextRange> {
+        let mut taken = Vec::new();
+        while let Some(comment) = self.peek() {
+            if comment.start() >= bound {
+                break;
+            }
+            taken.push(comment);
+            self.index += 1;
+        }
+        taken
+    }
+
+    /// Whether `a` and `b` appear on the same source line.
+    fn same_line(&self, a: TextSize, b: TextSize) -> bool {
+        let range = TextRange::new(a.min(b), a.max(b));
+        !self.source[range].contains('\n')
+    }
+
+    /// The zero-indexed column `offset` starts at, counting in `char`s.
+    fn column(&self, offset: TextSize) -> usize {
+        let line_start = self.source[..offset.to_usize()]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        self.source[line_start..offset.to_usize()].chars().count()
+    }
+
+    fn attach(&mut self, stmt: TextRange, range: TextRange, position: CommentPosition) {
+        self.by_statement
+            .entry(stmt)
+            .or_default()
+            .push(AttachedComment { range, position });
+    }
+
+    /// Attaches every comment within `stmts`' own span, then any comments following `stmts`'
+    /// last entry that are still indented to at least `suite_column` as [`CommentPosition::Dangling`]
+    /// on that last statement -- no sibling statement remains in this suite to call them leading or
+    /// trailing of instead.
+    fn visit_suite(&mut self, stmts: &[Stmt], suite_column: usize) {
+        for (index, stmt) in stmts.iter().enumerate() {
+            let leading = self.take_before(stmt.start());
+            for (position, comment) in leading.iter().enumerate() {
+                if index > 0
+                    && position == 0
+                    && self.same_line(stmts[index - 1].end(), comment.start())
+                {
+                    self.attach(
+                        stmts[index - 1].range(),
+                        *comment,
+                        CommentPosition::Trailing,
+                    );
+                } else {
+                    self.attach(stmt.range(), *comment, CommentPosition::Leading);
+                }
+            }
+
+            for nested in nested_suites(stmt) {
+                if let Some(first) = nested.first() {
+                    self.visit_suite(nested, self.column(first.start()));
+                }
+            }
+        }
+
+        let Some(last) = stmts.last() else {
+            return;
+        };
+        if let Some(comment) = self.peek() {
+            if self.same_line(last.end(), comment.start()) {
+                self.attach(last.range(), comment, CommentPosition::Trailing);
+                self.index += 1;
+            } else {
+                while let Some(comment) = self.peek() {
+                    if self.column(comment.start()) < suite_column {
+                        break;
+                    }
+                    self.attach(last.range(), comment, CommentPosition::Dangling);
+                    self.index += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Every nested statement suite directly owned by `stmt`, in source order -- a function or class
+/// body, a loop's `else`, a `try`'s `except`/`else`/`finally`, a `match`'s cases, and so on.
+fn nested_suites(stmt: &Stmt) -> Vec<&[Stmt]> {
+    match stmt {
+        Stmt::FunctionDef(node) => vec![node.body.as_slice()],
+        Stmt::ClassDef(node) => vec![node.body.as_slice()],
+        Stmt::For(node) => {
+            let mut suites = vec![node.body.as_slice()];
+            if !node.orelse.is_empty() {
+                suites.push(&node.orelse);
+            }
+            suites
+        }
+        Stmt::While(node) => {
+            let mut suites = vec![node.body.as_slice()];
+            if !node.orelse.is_empty() {
+                suites.push(&node.orelse);
+            }
+            suites
+        }
+        Stmt::If(node) => {
+            let mut suites = vec![node.body.as_slice()];
+            suites.extend(
+                node.elif_else_clauses
+                    .iter()
+                    .map(|clause| clause.body.as_slice()),
+            );
+            suites
+        }
+        Stmt::With(node) => vec![node.body.as_slice()],
+        Stmt::Match(node) => node.cases.iter().map(|case| case.body.as_slice()).collect(),
+        Stmt::Try(node) => {
+            let mut suites = vec![node.body.as_slice()];
+            suites.extend(node.handlers.iter().map(|handler| {
+                let ExceptHandler::ExceptHandler(handler) = handler;
+                handler.body.as_slice()
+            }));
+            if !node.orelse.is_empty() {
+                suites.push(&node.orelse);
+            }
+            if !node.finalbody.is_empty() {
+                suites.push(&node.finalbody);
+            }
+            suites
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::{Mod, Stmt};
+
+    use super::{collect_comments, CommentPosition};
+    use crate::{parse, Mode};
+
+    fn module_body(source: &str) -> Vec<ruff_python_ast::Stmt> {
+        match parse(source, Mode::Module).unwrap() {
+            Mod::Module(module) => module.body,
+            Mod::Expression(_) | Mod::FunctionType(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn attaches_a_leading_comment() {
+        let source = "# leading\nx = 1\n";
+        let body = module_body(source);
+        let comments = collect_comments(source, &body);
+        let attached = comments.for_statement(&body[0]);
+        assert_eq!(attached.len(), 1);
+        assert_eq!(attached[0].position, CommentPosition::Leading);
+    }
+
+    #[test]
+    fn attaches_a_trailing_comment() {
+        let source = "x = 1  # trailing\ny = 2\n";
+        let body = module_body(source);
+        let comments = collect_comments(source, &body);
+        let attached = comments.for_statement(&body[0]);
+        assert_eq!(attached.len(), 1);
+        assert_eq!(attached[0].position, CommentPosition::Trailing);
+    }
+
+    #[test]
+    fn attaches_a_dangling_comment_inside_a_suite() {
+        let source = "if True:\n    pass\n    # dangling\nx = 1\n";
+        let body = module_body(source);
+        let if_stmt = &body[0];
+        let comments = collect_comments(source, &body);
+        let attached = comments.for_statement(if_stmt);
+        assert!(attached.is_empty());
+
+        let Stmt::If(node) = if_stmt else {
+            panic!("expected an if statement");
+        };
+        let inner = comments.for_statement(&node.body[0]);
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].position, CommentPosition::Dangling);
+    }
+
+    #[test]
+    fn a_comment_after_a_suite_leads_the_next_top_level_statement() {
+        let source = "if True:\n    pass\n# leads y, not dangling in the if\ny = 1\n";
+        let body = module_body(source);
+        let comments = collect_comments(source, &body);
+        assert_eq!(comments.for_statement(&body[1]).len(), 1);
+        assert_eq!(
+            comments.for_statement(&body[1])[0].position,
+            CommentPosition::Leading
+        );
+    }
+}
@@ -1,6 +1,18 @@
 use ruff_python_ast::{self as ast, Expr, ExprContext};
 
-pub(crate) fn set_context(expr: Expr, ctx: ExprContext) -> Expr {
+/// Rewrites `expr` so that it (and, recursively, any sub-expressions that can appear on the left
+/// hand side of an assignment) carries `ctx` instead of its current [`ExprContext`].
+///
+/// The parser uses this internally to turn an expression parsed as a value (`ExprContext::Load`)
+/// into an assignment target (`ExprContext::Store`) or deletion target (`ExprContext::Del`)
+/// after the fact, rather than duplicating the expression grammar for each context. It's exposed
+/// publicly so that fixers that synthesize an assignment or `del` statement out of an existing
+/// expression can rewrite its context the same way, instead of reimplementing this recursion.
+///
+/// Only `Name`, `Tuple`, `List`, `Attribute`, `Subscript`, and `Starred` are rewritten; every
+/// other expression is returned unchanged, since those are the only expressions a context can
+/// apply to in the grammar.
+pub fn set_context(expr: Expr, ctx: ExprContext) -> Expr {
     match expr {
         Expr::Name(ast::ExprName { id, range, .. }) => ast::ExprName { range, id, ctx }.into(),
         Expr::Tuple(ast::ExprTuple { elts, range, .. }) => ast::ExprTuple {
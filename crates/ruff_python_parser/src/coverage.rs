@@ -0,0 +1,54 @@
+//! Lightweight instrumentation for tracking which hand-written corners of the parser a run
+//! exercised, gated behind the `coverage` feature.
+//!
+//! Most of this crate's grammar lives in the `lalrpop`-generated `python.rs` table, which isn't
+//! something worth hand-instrumenting: any probe inserted there would be invalidated by the next
+//! grammar change, and `lalrpop` offers no hook to do it for us. What *is* worth instrumenting is
+//! the hand-written code around that table that makes the judgment calls behind "recovery" and
+//! "edge case" bug reports -- soft-keyword disambiguation (is this `match` a keyword or an
+//! identifier?) and f-string format-spec nesting (`{a:{b:{c}}}`) chief among them. This module
+//! gives those call sites a place to record a hit, and a corpus run (see `cargo dev conformance`)
+//! a place to read the tally back.
+//!
+//! Probes accumulate into a process-global table, so a report reflects every parse performed by
+//! the process so far; call [`reset`] before a run that should be measured in isolation.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+fn probes() -> &'static Mutex<BTreeMap<&'static str, u64>> {
+    static PROBES: OnceLock<Mutex<BTreeMap<&'static str, u64>>> = OnceLock::new();
+    PROBES.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+/// Records one hit against `probe`, for example `"soft_keyword:match_as_keyword"`.
+pub fn record(probe: &'static str) {
+    *probes().lock().unwrap().entry(probe).or_insert(0) += 1;
+}
+
+/// Returns the hit count recorded for each probe so far.
+pub fn report() -> BTreeMap<&'static str, u64> {
+    probes().lock().unwrap().clone()
+}
+
+/// Clears every recorded hit, so a subsequent [`report`] reflects only what happens next.
+pub fn reset() {
+    probes().lock().unwrap().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record, report, reset};
+
+    #[test]
+    fn records_and_reports_hits_per_probe() {
+        reset();
+        record("a");
+        record("a");
+        record("b");
+        let report = report();
+        assert_eq!(report.get("a"), Some(&2));
+        assert_eq!(report.get("b"), Some(&1));
+        reset();
+    }
+}
@@ -0,0 +1,1211 @@
+//! Render a parsed [`ast::Mod`] as JSON using `CPython`'s `ast` module's node names, field names,
+//! and `lineno`/`col_offset`/`end_lineno`/`end_col_offset` conventions, so the result can be
+//! compared against `json.dumps(ast_to_dict(ast.parse(source)))`-style output from `CPython`, or
+//! consumed directly by Python tooling that already expects that shape.
+//!
+//! Each node is rendered as a JSON object with a `_type` key holding the `CPython` class name
+//! (the convention used by the popular `ast2json` package, since `ast.dump` itself doesn't
+//! produce JSON), plus one key per `CPython` field. `lineno`/`col_offset` are 1-indexed line /
+//! 0-indexed UTF-8 byte column, matching [the `ast` module's documented convention]; nodes that
+//! `CPython`'s ASDL grammar gives no `attributes` (operators, `expr_context`, ...) don't get them.
+//!
+//! A handful of differences between this crate's unified AST and `CPython`'s are bridged here
+//! rather than pushed onto callers:
+//! - Implicitly concatenated string/f-string/bytes literals are merged into a single `Constant`
+//!   or `JoinedStr`, since `CPython`'s parser performs that merge before producing the AST.
+//! - [`Parameters`] carries each parameter's default inline; `CPython`'s `arguments` node instead
+//!   has parallel `defaults`/`kw_defaults` lists, which are reconstructed here.
+//! - [`PatternKeyword`] pairs (attr, pattern) where `CPython`'s `MatchClass` has parallel
+//!   `kwd_attrs`/`kwd_patterns` lists, which are reconstructed here too.
+//!
+//! `type_comment` fields hold the raw text after `# type:` on a statement's header line -- the
+//! same string `CPython` reports, not a parsed expression -- using [`crate::type_comments`] to
+//! find it; a statement with no trailing `# type:` comment gets `null`, same as `CPython` does
+//! when `type_comments=False`. `type_params` is only emitted on
+//! `FunctionDef`/`ClassDef` for [`MinVersion::PY312`] and above, since `CPython` didn't add that
+//! field until 3.12. Values that JSON (and `CPython`'s own `json.dumps`) can't represent natively
+//! -- arbitrary-precision integers, complex numbers, `Ellipsis` -- are given a documented
+//! stand-in rather than silently truncated; see [`number_to_json`].
+//!
+//! There's no IPython-escape-command node in `CPython`'s `ast` module, since it's a Jupyter
+//! extension this crate supports (see [`crate::ipython`]); those nodes are rendered under a
+//! synthetic `IpyEscapeCommand` type instead of being dropped.
+//!
+//! [the `ast` module's documented convention]: https://docs.python.org/3/library/ast.html#ast.AST.col_offset
+
+use ruff_python_ast::min_version::MinVersion;
+use ruff_python_ast::{self as ast, Number, Singleton};
+use ruff_source_file::LineIndex;
+use ruff_text_size::{Ranged, TextRange};
+use rustc_hash::FxHashMap;
+use serde_json::{json, Map, Value};
+
+use crate::type_comments::parse_type_comments;
+
+/// Render `module` as a `CPython`-`ast`-shaped JSON value, as if it had been parsed and dumped by
+/// the given `target_version` of `CPython`. See the [module docs](self) for what is and isn't
+/// faithfully reproduced.
+pub fn to_cpython_json(module: &ast::Mod, source: &str, target_version: MinVersion) -> Value {
+    let body = match module {
+        ast::Mod::Module(module) => module.body.as_slice(),
+        ast::Mod::Expression(_) | ast::Mod::FunctionType(_) => &[],
+    };
+    let type_comments = parse_type_comments(source, body)
+        .into_iter()
+        .map(|comment| (comment.statement, comment.comment))
+        .collect();
+    let dumper = Dumper {
+        source,
+        line_index: LineIndex::from_source_text(source),
+        target_version,
+        type_comments,
+    };
+    match module {
+        ast::Mod::Module(module) => dumper.node(
+            "Module",
+            module.range,
+            [
+                ("body", dumper.stmts(&module.body)),
+                ("type_ignores", json!([])),
+            ],
+        ),
+        ast::Mod::Expression(expression) => dumper.node(
+            "Expression",
+            expression.range,
+            [("body", dumper.expr(&expression.body))],
+        ),
+        ast::Mod::FunctionType(function_type) => dumper.node(
+            "FunctionType",
+            function_type.range,
+            [
+                ("argtypes", dumper.exprs(&function_type.argtypes)),
+                ("returns", dumper.expr(&function_type.returns)),
+            ],
+        ),
+    }
+}
+
+struct Dumper<'a> {
+    source: &'a str,
+    line_index: LineIndex,
+    target_version: MinVersion,
+    /// Every statement range with a trailing `# type:` comment, mapped to that comment's own
+    /// range -- see [`Self::type_comment`].
+    type_comments: FxHashMap<TextRange, TextRange>,
+}
+
+impl Dumper<'_> {
+    /// A located AST node: `_type`, `lineno`/`col_offset`/`end_lineno`/`end_col_offset`, plus the
+    /// given fields, in `CPython`'s `ast.dump` field order.
+    fn node<const N: usize>(
+        &self,
+        type_name: &'static str,
+        range: TextRange,
+        fields: [(&'static str, Value); N],
+    ) -> Value {
+        let mut map = Map::with_capacity(N + 5);
+        map.insert("_type".to_string(), Value::String(type_name.to_string()));
+        for (name, value) in fields {
+            map.insert(name.to_string(), value);
+        }
+        let (lineno, col_offset) = self.position(range.start());
+        let (end_lineno, end_col_offset) = self.position(range.end());
+        map.insert("lineno".to_string(), json!(lineno));
+        map.insert("col_offset".to_string(), json!(col_offset));
+        map.insert("end_lineno".to_string(), json!(end_lineno));
+        map.insert("end_col_offset".to_string(), json!(end_col_offset));
+        Value::Object(map)
+    }
+
+    /// A position-less helper node, for the ASDL sum types (`operator`, `expr_context`, ...)
+    /// that `CPython` gives no `attributes`.
+    fn bare_node<const N: usize>(
+        type_name: &'static str,
+        fields: [(&'static str, Value); N],
+    ) -> Value {
+        let mut map = Map::with_capacity(N + 1);
+        map.insert("_type".to_string(), Value::String(type_name.to_string()));
+        for (name, value) in fields {
+            map.insert(name.to_string(), value);
+        }
+        Value::Object(map)
+    }
+
+    /// `(lineno, col_offset)`: 1-indexed line, 0-indexed UTF-8 byte column, matching `CPython`.
+    fn position(&self, offset: ruff_text_size::TextSize) -> (usize, usize) {
+        let line = self.line_index.line_index(offset);
+        let line_start = self.line_index.line_start(line, self.source);
+        (line.get(), usize::from(offset) - usize::from(line_start))
+    }
+
+    fn stmts(&self, stmts: &[ast::Stmt]) -> Value {
+        Value::Array(stmts.iter().map(|stmt| self.stmt(stmt)).collect())
+    }
+
+    fn exprs(&self, exprs: &[ast::Expr]) -> Value {
+        Value::Array(exprs.iter().map(|expr| self.expr(expr)).collect())
+    }
+
+    fn identifier(identifier: &ast::Identifier) -> Value {
+        Value::String(identifier.as_str().to_string())
+    }
+
+    fn identifiers(identifiers: &[ast::Identifier]) -> Value {
+        Value::Array(identifiers.iter().map(Self::identifier).collect())
+    }
+
+    fn opt_identifier(identifier: Option<&ast::Identifier>) -> Value {
+        identifier.map_or(Value::Null, Self::identifier)
+    }
+
+    fn dotted_name(name: &ast::DottedName) -> Value {
+        Value::String(name.as_str().to_string())
+    }
+
+    fn opt_dotted_name(name: Option<&ast::DottedName>) -> Value {
+        name.map_or(Value::Null, Self::dotted_name)
+    }
+
+    fn opt_expr(&self, expr: Option<&ast::Expr>) -> Value {
+        expr.map_or(Value::Null, |expr| self.expr(expr))
+    }
+
+    /// The raw text after `# type:` trailing `stmt`'s header line, or `null` if it has none.
+    fn type_comment(&self, stmt: TextRange) -> Value {
+        self.type_comments
+            .get(&stmt)
+            .map_or(Value::Null, |comment| {
+                let text = self.source[*comment]
+                    .trim_start_matches('#')
+                    .trim_start()
+                    .trim_start_matches("type:")
+                    .trim()
+                    .to_string();
+                Value::String(text)
+            })
+    }
+
+    fn stmt(&self, stmt: &ast::Stmt) -> Value {
+        let range = stmt.range();
+        match stmt {
+            ast::Stmt::FunctionDef(node) => {
+                let type_name = if node.is_async {
+                    "AsyncFunctionDef"
+                } else {
+                    "FunctionDef"
+                };
+                let mut fields = vec![
+                    ("name", Self::identifier(&node.name)),
+                    ("args", self.parameters(Some(&node.parameters))),
+                    ("body", self.stmts(&node.body)),
+                    ("decorator_list", self.decorators(&node.decorator_list)),
+                    ("returns", self.opt_expr(node.returns.as_deref())),
+                    ("type_comment", self.type_comment(range)),
+                ];
+                if self.target_version >= MinVersion::PY312 {
+                    fields.push((
+                        "type_params",
+                        self.type_params(node.type_params.as_ref()),
+                    ));
+                }
+                self.node_vec(type_name, range, fields)
+            }
+            ast::Stmt::ClassDef(node) => {
+                let mut fields = vec![
+                    ("name", Self::identifier(&node.name)),
+                    ("bases", self.exprs(node.bases())),
+                    ("keywords", self.keywords(node.keywords())),
+                    ("body", self.stmts(&node.body)),
+                    ("decorator_list", self.decorators(&node.decorator_list)),
+                ];
+                if self.target_version >= MinVersion::PY312 {
+                    fields.push((
+                        "type_params",
+                        self.type_params(node.type_params.as_deref()),
+                    ));
+                }
+                self.node_vec("ClassDef", range, fields)
+            }
+            ast::Stmt::Return(node) => self.node(
+                "Return",
+                range,
+                [("value", self.opt_expr(node.value.as_deref()))],
+            ),
+            ast::Stmt::Delete(node) => {
+                self.node("Delete", range, [("targets", self.exprs(&node.targets))])
+            }
+            ast::Stmt::Assign(node) => self.node(
+                "Assign",
+                range,
+                [
+                    ("targets", self.exprs(&node.targets)),
+                    ("value", self.expr(&node.value)),
+                    ("type_comment", self.type_comment(range)),
+                ],
+            ),
+            ast::Stmt::AugAssign(node) => self.node(
+                "AugAssign",
+                range,
+                [
+                    ("target", self.expr(&node.target)),
+                    ("op", Self::operator(node.op)),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Stmt::AnnAssign(node) => self.node(
+                "AnnAssign",
+                range,
+                [
+                    ("target", self.expr(&node.target)),
+                    ("annotation", self.expr(&node.annotation)),
+                    ("value", self.opt_expr(node.value.as_deref())),
+                    ("simple", json!(i32::from(node.simple))),
+                ],
+            ),
+            ast::Stmt::TypeAlias(node) => self.node(
+                "TypeAlias",
+                range,
+                [
+                    ("name", self.expr(&node.name)),
+                    ("type_params", self.type_params(node.type_params.as_ref())),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Stmt::For(node) => {
+                let type_name = if node.is_async { "AsyncFor" } else { "For" };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("target", self.expr(&node.target)),
+                        ("iter", self.expr(&node.iter)),
+                        ("body", self.stmts(&node.body)),
+                        ("orelse", self.stmts(&node.orelse)),
+                        ("type_comment", self.type_comment(range)),
+                    ],
+                )
+            }
+            ast::Stmt::While(node) => self.node(
+                "While",
+                range,
+                [
+                    ("test", self.expr(&node.test)),
+                    ("body", self.stmts(&node.body)),
+                    ("orelse", self.stmts(&node.orelse)),
+                ],
+            ),
+            ast::Stmt::If(node) => self.if_stmt(node),
+            ast::Stmt::With(node) => {
+                let type_name = if node.is_async { "AsyncWith" } else { "With" };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("items", self.with_items(&node.items)),
+                        ("body", self.stmts(&node.body)),
+                        ("type_comment", self.type_comment(range)),
+                    ],
+                )
+            }
+            ast::Stmt::Match(node) => self.node(
+                "Match",
+                range,
+                [
+                    ("subject", self.expr(&node.subject)),
+                    ("cases", self.match_cases(&node.cases)),
+                ],
+            ),
+            ast::Stmt::Raise(node) => self.node(
+                "Raise",
+                range,
+                [
+                    ("exc", self.opt_expr(node.exc.as_deref())),
+                    ("cause", self.opt_expr(node.cause.as_deref())),
+                ],
+            ),
+            ast::Stmt::Try(node) => {
+                let type_name = if node.is_star { "TryStar" } else { "Try" };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("body", self.stmts(&node.body)),
+                        ("handlers", self.except_handlers(&node.handlers)),
+                        ("orelse", self.stmts(&node.orelse)),
+                        ("finalbody", self.stmts(&node.finalbody)),
+                    ],
+                )
+            }
+            ast::Stmt::Assert(node) => self.node(
+                "Assert",
+                range,
+                [
+                    ("test", self.expr(&node.test)),
+                    ("msg", self.opt_expr(node.msg.as_deref())),
+                ],
+            ),
+            ast::Stmt::Import(node) => {
+                self.node("Import", range, [("names", self.aliases(&node.names))])
+            }
+            ast::Stmt::ImportFrom(node) => self.node(
+                "ImportFrom",
+                range,
+                [
+                    ("module", Self::opt_dotted_name(node.module.as_ref())),
+                    ("names", self.aliases(&node.names)),
+                    ("level", json!(node.level.unwrap_or(0))),
+                ],
+            ),
+            ast::Stmt::Global(node) => {
+                self.node("Global", range, [("names", Self::identifiers(&node.names))])
+            }
+            ast::Stmt::Nonlocal(node) => self.node(
+                "Nonlocal",
+                range,
+                [("names", Self::identifiers(&node.names))],
+            ),
+            ast::Stmt::Expr(node) => {
+                self.node("Expr", range, [("value", self.expr(&node.value))])
+            }
+            ast::Stmt::Pass(_) => self.node("Pass", range, []),
+            ast::Stmt::Break(_) => self.node("Break", range, []),
+            ast::Stmt::Continue(_) => self.node("Continue", range, []),
+            ast::Stmt::IpyEscapeCommand(node) => self.node(
+                "IpyEscapeCommand",
+                range,
+                [
+                    ("kind", json!(format!("{:?}", node.kind))),
+                    ("value", json!(node.value)),
+                ],
+            ),
+        }
+    }
+
+    /// Like [`Self::node`], but for the handful of statements whose field count varies with
+    /// `target_version` and so can't go through the fixed-size array overload.
+    fn node_vec(
+        &self,
+        type_name: &'static str,
+        range: TextRange,
+        fields: Vec<(&'static str, Value)>,
+    ) -> Value {
+        let mut map = Map::with_capacity(fields.len() + 5);
+        map.insert("_type".to_string(), Value::String(type_name.to_string()));
+        for (name, value) in fields {
+            map.insert(name.to_string(), value);
+        }
+        let (lineno, col_offset) = self.position(range.start());
+        let (end_lineno, end_col_offset) = self.position(range.end());
+        map.insert("lineno".to_string(), json!(lineno));
+        map.insert("col_offset".to_string(), json!(col_offset));
+        map.insert("end_lineno".to_string(), json!(end_lineno));
+        map.insert("end_col_offset".to_string(), json!(end_col_offset));
+        Value::Object(map)
+    }
+
+    fn if_stmt(&self, node: &ast::StmtIf) -> Value {
+        self.if_stmt_inner(node.range, &node.test, &node.body, &node.elif_else_clauses)
+    }
+
+    /// `elif`/`else` clauses are flattened in this crate's AST ([`ast::ElifElseClause`]) but
+    /// nested in `CPython`'s, where each `elif` is an `If` node in the parent's single-element
+    /// `orelse` list.
+    fn if_stmt_inner(
+        &self,
+        range: TextRange,
+        test: &ast::Expr,
+        body: &[ast::Stmt],
+        clauses: &[ast::ElifElseClause],
+    ) -> Value {
+        let orelse = match clauses.split_first() {
+            None => json!([]),
+            Some((clause, rest)) => match &clause.test {
+                // `elif`: nest another `If` covering the rest of the chain.
+                Some(test) => Value::Array(vec![
+                    self.if_stmt_inner(clause.range, test, &clause.body, rest),
+                ]),
+                // `else`: its body is the `orelse` list directly.
+                None => self.stmts(&clause.body),
+            },
+        };
+        self.node(
+            "If",
+            range,
+            [
+                ("test", self.expr(test)),
+                ("body", self.stmts(body)),
+                ("orelse", orelse),
+            ],
+        )
+    }
+
+    fn with_items(&self, items: &[ast::WithItem]) -> Value {
+        Value::Array(
+            items
+                .iter()
+                .map(|item| {
+                    Self::bare_node(
+                        "withitem",
+                        [
+                            ("context_expr", self.expr(&item.context_expr)),
+                            (
+                                "optional_vars",
+                                self.opt_expr(item.optional_vars.as_deref()),
+                            ),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn match_cases(&self, cases: &[ast::MatchCase]) -> Value {
+        Value::Array(
+            cases
+                .iter()
+                .map(|case| {
+                    Self::bare_node(
+                        "match_case",
+                        [
+                            ("pattern", self.pattern(&case.pattern)),
+                            ("guard", self.opt_expr(case.guard.as_deref())),
+                            ("body", self.stmts(&case.body)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn except_handlers(&self, handlers: &[ast::ExceptHandler]) -> Value {
+        Value::Array(
+            handlers
+                .iter()
+                .map(|handler| {
+                    let ast::ExceptHandler::ExceptHandler(node) = handler;
+                    self.node(
+                        "ExceptHandler",
+                        node.range,
+                        [
+                            ("type", self.opt_expr(node.type_.as_deref())),
+                            ("name", Self::opt_identifier(node.name.as_ref())),
+                            ("body", self.stmts(&node.body)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn aliases(&self, aliases: &[ast::Alias]) -> Value {
+        Value::Array(
+            aliases
+                .iter()
+                .map(|alias| {
+                    self.node(
+                        "alias",
+                        alias.range,
+                        [
+                            ("name", Self::dotted_name(&alias.name)),
+                            ("asname", Self::opt_identifier(alias.asname.as_ref())),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn decorators(&self, decorators: &[ast::Decorator]) -> Value {
+        self.exprs_from(decorators.iter().map(|decorator| &decorator.expression))
+    }
+
+    fn exprs_from<'b>(&self, exprs: impl Iterator<Item = &'b ast::Expr>) -> Value {
+        Value::Array(exprs.map(|expr| self.expr(expr)).collect())
+    }
+
+    fn keywords(&self, keywords: &[ast::Keyword]) -> Value {
+        Value::Array(
+            keywords
+                .iter()
+                .map(|keyword| {
+                    self.node(
+                        "keyword",
+                        keyword.range,
+                        [
+                            ("arg", Self::opt_identifier(keyword.arg.as_ref())),
+                            ("value", self.expr(&keyword.value)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// `CPython`'s `arguments` node splits each parameter's default out into parallel
+    /// `defaults`/`kw_defaults` lists; this crate attaches the default directly to the
+    /// parameter instead, so it's reassembled here. A missing `Parameters` (a bare `lambda:`,
+    /// or a `def f():`) still produces an `arguments` node with all-empty fields, matching
+    /// `CPython`.
+    fn parameters(&self, parameters: Option<&ast::Parameters>) -> Value {
+        let empty = Vec::new();
+        let (posonlyargs, args, vararg, kwonlyargs, kwarg) = match parameters {
+            Some(parameters) => (
+                &parameters.posonlyargs,
+                &parameters.args,
+                parameters.vararg.as_deref(),
+                &parameters.kwonlyargs,
+                parameters.kwarg.as_deref(),
+            ),
+            None => (&empty, &empty, None, &empty, None),
+        };
+
+        let defaults = posonlyargs
+            .iter()
+            .chain(args)
+            .filter_map(|parameter| parameter.default.as_deref())
+            .map(|default| self.expr(default))
+            .collect();
+        let kw_defaults = kwonlyargs
+            .iter()
+            .map(|parameter| self.opt_expr(parameter.default.as_deref()))
+            .collect();
+
+        Self::bare_node(
+            "arguments",
+            [
+                (
+                    "posonlyargs",
+                    self.arg_list(posonlyargs.iter().map(|p| &p.parameter)),
+                ),
+                ("args", self.arg_list(args.iter().map(|p| &p.parameter))),
+                ("vararg", self.opt_arg(vararg)),
+                (
+                    "kwonlyargs",
+                    self.arg_list(kwonlyargs.iter().map(|p| &p.parameter)),
+                ),
+                ("kw_defaults", Value::Array(kw_defaults)),
+                ("kwarg", self.opt_arg(kwarg)),
+                ("defaults", Value::Array(defaults)),
+            ],
+        )
+    }
+
+    fn arg_list<'b>(&self, parameters: impl Iterator<Item = &'b ast::Parameter>) -> Value {
+        Value::Array(parameters.map(|parameter| self.arg(parameter)).collect())
+    }
+
+    fn opt_arg(&self, parameter: Option<&ast::Parameter>) -> Value {
+        parameter.map_or(Value::Null, |parameter| self.arg(parameter))
+    }
+
+    fn arg(&self, parameter: &ast::Parameter) -> Value {
+        self.node(
+            "arg",
+            parameter.range,
+            [
+                ("arg", Self::identifier(&parameter.name)),
+                ("annotation", self.opt_expr(parameter.annotation.as_deref())),
+                ("type_comment", Value::Null),
+            ],
+        )
+    }
+
+    fn type_params(&self, type_params: Option<&ast::TypeParams>) -> Value {
+        let empty: &[ast::TypeParam] = &[];
+        let type_params = type_params.map_or(empty, |params| &params.type_params);
+        Value::Array(
+            type_params
+                .iter()
+                .map(|type_param| self.type_param(type_param))
+                .collect(),
+        )
+    }
+
+    fn type_param(&self, type_param: &ast::TypeParam) -> Value {
+        match type_param {
+            ast::TypeParam::TypeVar(node) => self.node(
+                "TypeVar",
+                node.range,
+                [
+                    ("name", Self::identifier(&node.name)),
+                    ("bound", self.opt_expr(node.bound.as_deref())),
+                ],
+            ),
+            ast::TypeParam::ParamSpec(node) => {
+                self.node("ParamSpec", node.range, [("name", Self::identifier(&node.name))])
+            }
+            ast::TypeParam::TypeVarTuple(node) => self.node(
+                "TypeVarTuple",
+                node.range,
+                [("name", Self::identifier(&node.name))],
+            ),
+        }
+    }
+
+    fn pattern(&self, pattern: &ast::Pattern) -> Value {
+        let range = pattern.range();
+        match pattern {
+            ast::Pattern::MatchValue(node) => {
+                self.node("MatchValue", range, [("value", self.expr(&node.value))])
+            }
+            ast::Pattern::MatchSingleton(node) => self.node(
+                "MatchSingleton",
+                range,
+                [("value", Self::singleton(&node.value))],
+            ),
+            ast::Pattern::MatchSequence(node) => self.node(
+                "MatchSequence",
+                range,
+                [("patterns", self.patterns(&node.patterns))],
+            ),
+            ast::Pattern::MatchMapping(node) => self.node(
+                "MatchMapping",
+                range,
+                [
+                    ("keys", self.exprs(&node.keys)),
+                    ("patterns", self.patterns(&node.patterns)),
+                    ("rest", Self::opt_identifier(node.rest.as_ref())),
+                ],
+            ),
+            ast::Pattern::MatchClass(node) => self.node(
+                "MatchClass",
+                range,
+                [
+                    ("cls", self.expr(&node.cls)),
+                    ("patterns", self.patterns(&node.arguments.patterns)),
+                    (
+                        "kwd_attrs",
+                        Value::Array(
+                            node.arguments
+                                .keywords
+                                .iter()
+                                .map(|keyword| Self::identifier(&keyword.attr))
+                                .collect(),
+                        ),
+                    ),
+                    (
+                        "kwd_patterns",
+                        Value::Array(
+                            node.arguments
+                                .keywords
+                                .iter()
+                                .map(|keyword| self.pattern(&keyword.pattern))
+                                .collect(),
+                        ),
+                    ),
+                ],
+            ),
+            ast::Pattern::MatchStar(node) => self.node(
+                "MatchStar",
+                range,
+                [("name", Self::opt_identifier(node.name.as_ref()))],
+            ),
+            ast::Pattern::MatchAs(node) => self.node(
+                "MatchAs",
+                range,
+                [
+                    ("pattern", node.pattern.as_deref().map_or(Value::Null, |p| self.pattern(p))),
+                    ("name", Self::opt_identifier(node.name.as_ref())),
+                ],
+            ),
+            ast::Pattern::MatchOr(node) => self.node(
+                "MatchOr",
+                range,
+                [("patterns", self.patterns(&node.patterns))],
+            ),
+        }
+    }
+
+    fn patterns(&self, patterns: &[ast::Pattern]) -> Value {
+        Value::Array(patterns.iter().map(|pattern| self.pattern(pattern)).collect())
+    }
+
+    /// `MatchSingleton.value` is the raw constant (`None`/`True`/`False`) in `CPython`'s `ast`
+    /// module, not wrapped in a `Constant` node, so this matches that directly.
+    fn singleton(singleton: &Singleton) -> Value {
+        match singleton {
+            Singleton::None => Value::Null,
+            Singleton::True => Value::Bool(true),
+            Singleton::False => Value::Bool(false),
+        }
+    }
+
+    fn operator(op: ast::Operator) -> Value {
+        let name = match op {
+            ast::Operator::Add => "Add",
+            ast::Operator::Sub => "Sub",
+            ast::Operator::Mult => "Mult",
+            ast::Operator::MatMult => "MatMult",
+            ast::Operator::Div => "Div",
+            ast::Operator::Mod => "Mod",
+            ast::Operator::Pow => "Pow",
+            ast::Operator::LShift => "LShift",
+            ast::Operator::RShift => "RShift",
+            ast::Operator::BitOr => "BitOr",
+            ast::Operator::BitXor => "BitXor",
+            ast::Operator::BitAnd => "BitAnd",
+            ast::Operator::FloorDiv => "FloorDiv",
+        };
+        Self::bare_node(name, [])
+    }
+
+    fn unary_op(op: ast::UnaryOp) -> Value {
+        let name = match op {
+            ast::UnaryOp::Invert => "Invert",
+            ast::UnaryOp::Not => "Not",
+            ast::UnaryOp::UAdd => "UAdd",
+            ast::UnaryOp::USub => "USub",
+        };
+        Self::bare_node(name, [])
+    }
+
+    fn bool_op(op: ast::BoolOp) -> Value {
+        let name = match op {
+            ast::BoolOp::And => "And",
+            ast::BoolOp::Or => "Or",
+        };
+        Self::bare_node(name, [])
+    }
+
+    fn cmp_op(op: ast::CmpOp) -> Value {
+        let name = match op {
+            ast::CmpOp::Eq => "Eq",
+            ast::CmpOp::NotEq => "NotEq",
+            ast::CmpOp::Lt => "Lt",
+            ast::CmpOp::LtE => "LtE",
+            ast::CmpOp::Gt => "Gt",
+            ast::CmpOp::GtE => "GtE",
+            ast::CmpOp::Is => "Is",
+            ast::CmpOp::IsNot => "IsNot",
+            ast::CmpOp::In => "In",
+            ast::CmpOp::NotIn => "NotIn",
+        };
+        Self::bare_node(name, [])
+    }
+
+    fn expr_context(ctx: ast::ExprContext) -> Value {
+        let name = match ctx {
+            ast::ExprContext::Load => "Load",
+            ast::ExprContext::Store => "Store",
+            ast::ExprContext::Del => "Del",
+        };
+        Self::bare_node(name, [])
+    }
+
+    fn comprehensions(&self, comprehensions: &[ast::Comprehension]) -> Value {
+        Value::Array(
+            comprehensions
+                .iter()
+                .map(|comprehension| {
+                    Self::bare_node(
+                        "comprehension",
+                        [
+                            ("target", self.expr(&comprehension.target)),
+                            ("iter", self.expr(&comprehension.iter)),
+                            ("ifs", self.exprs(&comprehension.ifs)),
+                            ("is_async", json!(i32::from(comprehension.is_async))),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Flattens the implicit string-literal/f-string concatenation this crate's [`ast::Expr`]
+    /// keeps explicit into the single merged literal `CPython`'s parser would have produced.
+    fn expr(&self, expr: &ast::Expr) -> Value {
+        let range = expr.range();
+        match expr {
+            ast::Expr::BoolOp(node) => self.node(
+                "BoolOp",
+                range,
+                [
+                    ("op", Self::bool_op(node.op)),
+                    ("values", self.exprs(&node.values)),
+                ],
+            ),
+            ast::Expr::NamedExpr(node) => self.node(
+                "NamedExpr",
+                range,
+                [
+                    ("target", self.expr(&node.target)),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Expr::BinOp(node) => self.node(
+                "BinOp",
+                range,
+                [
+                    ("left", self.expr(&node.left)),
+                    ("op", Self::operator(node.op)),
+                    ("right", self.expr(&node.right)),
+                ],
+            ),
+            ast::Expr::UnaryOp(node) => self.node(
+                "UnaryOp",
+                range,
+                [
+                    ("op", Self::unary_op(node.op)),
+                    ("operand", self.expr(&node.operand)),
+                ],
+            ),
+            ast::Expr::Lambda(node) => self.node(
+                "Lambda",
+                range,
+                [
+                    ("args", self.parameters(node.parameters.as_deref())),
+                    ("body", self.expr(&node.body)),
+                ],
+            ),
+            ast::Expr::IfExp(node) => self.node(
+                "IfExp",
+                range,
+                [
+                    ("test", self.expr(&node.test)),
+                    ("body", self.expr(&node.body)),
+                    ("orelse", self.expr(&node.orelse)),
+                ],
+            ),
+            ast::Expr::Dict(node) => self.node(
+                "Dict",
+                range,
+                [
+                    (
+                        "keys",
+                        Value::Array(node.keys.iter().map(|k| self.opt_expr(k.as_ref())).collect()),
+                    ),
+                    ("values", self.exprs(&node.values)),
+                ],
+            ),
+            ast::Expr::Set(node) => self.node("Set", range, [("elts", self.exprs(&node.elts))]),
+            ast::Expr::ListComp(node) => self.node(
+                "ListComp",
+                range,
+                [
+                    ("elt", self.expr(&node.elt)),
+                    ("generators", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::SetComp(node) => self.node(
+                "SetComp",
+                range,
+                [
+                    ("elt", self.expr(&node.elt)),
+                    ("generators", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::DictComp(node) => self.node(
+                "DictComp",
+                range,
+                [
+                    ("key", self.expr(&node.key)),
+                    ("value", self.expr(&node.value)),
+                    ("generators", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::GeneratorExp(node) => self.node(
+                "GeneratorExp",
+                range,
+                [
+                    ("elt", self.expr(&node.elt)),
+                    ("generators", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::Await(node) => {
+                self.node("Await", range, [("value", self.expr(&node.value))])
+            }
+            ast::Expr::Yield(node) => self.node(
+                "Yield",
+                range,
+                [("value", self.opt_expr(node.value.as_deref()))],
+            ),
+            ast::Expr::YieldFrom(node) => {
+                self.node("YieldFrom", range, [("value", self.expr(&node.value))])
+            }
+            ast::Expr::Compare(node) => self.node(
+                "Compare",
+                range,
+                [
+                    ("left", self.expr(&node.left)),
+                    (
+                        "ops",
+                        Value::Array(node.ops.iter().map(|op| Self::cmp_op(*op)).collect()),
+                    ),
+                    ("comparators", self.exprs(&node.comparators)),
+                ],
+            ),
+            ast::Expr::Call(node) => self.node(
+                "Call",
+                range,
+                [
+                    ("func", self.expr(&node.func)),
+                    ("args", self.exprs(&node.arguments.args)),
+                    ("keywords", self.keywords(&node.arguments.keywords)),
+                ],
+            ),
+            ast::Expr::FString(node) => self.f_string_value(range, &node.value),
+            ast::Expr::StringLiteral(node) => self.node(
+                "Constant",
+                range,
+                [
+                    ("value", json!(node.value.to_str())),
+                    (
+                        "kind",
+                        if node.value.is_unicode() {
+                            json!("u")
+                        } else {
+                            Value::Null
+                        },
+                    ),
+                ],
+            ),
+            ast::Expr::BytesLiteral(node) => self.node(
+                "Constant",
+                range,
+                [
+                    (
+                        "value",
+                        Value::Array(
+                            node.value
+                                .as_slice()
+                                .iter()
+                                .flat_map(|part| part.value.iter().copied())
+                                .map(|byte| json!(byte))
+                                .collect(),
+                        ),
+                    ),
+                    ("kind", Value::Null),
+                ],
+            ),
+            ast::Expr::NumberLiteral(node) => self.node(
+                "Constant",
+                range,
+                [("value", Self::number(&node.value)), ("kind", Value::Null)],
+            ),
+            ast::Expr::BooleanLiteral(node) => self.node(
+                "Constant",
+                range,
+                [("value", json!(node.value)), ("kind", Value::Null)],
+            ),
+            ast::Expr::NoneLiteral(_) => {
+                self.node("Constant", range, [("value", Value::Null), ("kind", Value::Null)])
+            }
+            ast::Expr::EllipsisLiteral(_) => self.node(
+                "Constant",
+                range,
+                // `json.dumps` can't represent CPython's `Ellipsis` singleton either; this
+                // marker mirrors how e.g. `ast2json` stands in for it.
+                [("value", json!("Ellipsis")), ("kind", Value::Null)],
+            ),
+            ast::Expr::Attribute(node) => self.node(
+                "Attribute",
+                range,
+                [
+                    ("value", self.expr(&node.value)),
+                    ("attr", Self::identifier(&node.attr)),
+                    ("ctx", Self::expr_context(node.ctx)),
+                ],
+            ),
+            ast::Expr::Subscript(node) => self.node(
+                "Subscript",
+                range,
+                [
+                    ("value", self.expr(&node.value)),
+                    ("slice", self.expr(&node.slice)),
+                    ("ctx", Self::expr_context(node.ctx)),
+                ],
+            ),
+            ast::Expr::Starred(node) => self.node(
+                "Starred",
+                range,
+                [
+                    ("value", self.expr(&node.value)),
+                    ("ctx", Self::expr_context(node.ctx)),
+                ],
+            ),
+            ast::Expr::Name(node) => self.node(
+                "Name",
+                range,
+                [
+                    ("id", json!(node.id)),
+                    ("ctx", Self::expr_context(node.ctx)),
+                ],
+            ),
+            ast::Expr::List(node) => self.node(
+                "List",
+                range,
+                [
+                    ("elts", self.exprs(&node.elts)),
+                    ("ctx", Self::expr_context(node.ctx)),
+                ],
+            ),
+            ast::Expr::Tuple(node) => self.node(
+                "Tuple",
+                range,
+                [
+                    ("elts", self.exprs(&node.elts)),
+                    ("ctx", Self::expr_context(node.ctx)),
+                ],
+            ),
+            ast::Expr::Slice(node) => self.node(
+                "Slice",
+                range,
+                [
+                    ("lower", self.opt_expr(node.lower.as_deref())),
+                    ("upper", self.opt_expr(node.upper.as_deref())),
+                    ("step", self.opt_expr(node.step.as_deref())),
+                ],
+            ),
+            ast::Expr::IpyEscapeCommand(node) => self.node(
+                "IpyEscapeCommand",
+                range,
+                [
+                    ("kind", json!(format!("{:?}", node.kind))),
+                    ("value", json!(node.value)),
+                ],
+            ),
+        }
+    }
+
+    fn number(number: &Number) -> Value {
+        match number {
+            Number::Int(int) => match int.as_i64() {
+                Some(value) => json!(value),
+                // Bigger than an `i64`: `serde_json`'s default `Value::Number` can't hold it
+                // exactly, so it's stringified rather than silently losing precision. A real
+                // `ast.dump`-backed JSON (via CPython's own `json` module) would instead emit a
+                // bare, arbitrary-precision numeric token here.
+                None => Value::String(int.to_string()),
+            },
+            Number::Float(value) => json!(value),
+            // CPython's own `json.dumps` can't serialize a `complex` value either (it raises
+            // `TypeError`); this object is a documented stand-in, not an attempt at parity.
+            Number::Complex { real, imag } => json!({ "re": real, "im": imag }),
+        }
+    }
+
+    /// Merges an (possibly implicitly concatenated) f-string's parts into one `JoinedStr`,
+    /// matching `CPython`'s parser, which performs that concatenation before building the AST.
+    fn f_string_value(&self, range: TextRange, value: &ast::FStringValue) -> Value {
+        let mut values = Vec::new();
+        for part in value.as_slice() {
+            match part {
+                ast::FStringPart::Literal(literal) => {
+                    values.push(self.node(
+                        "Constant",
+                        literal.range,
+                        [("value", json!(literal.value)), ("kind", Value::Null)],
+                    ));
+                }
+                ast::FStringPart::FString(f_string) => {
+                    for element in &f_string.elements {
+                        values.push(self.f_string_element(element));
+                    }
+                }
+            }
+        }
+        self.node("JoinedStr", range, [("values", Value::Array(values))])
+    }
+
+    fn f_string_element(&self, element: &ast::FStringElement) -> Value {
+        match element {
+            ast::FStringElement::Literal(literal) => self.node(
+                "Constant",
+                literal.range,
+                [("value", json!(literal.value)), ("kind", Value::Null)],
+            ),
+            ast::FStringElement::Expression(expression) => self.node(
+                "FormattedValue",
+                expression.range,
+                [
+                    ("value", self.expr(&expression.expression)),
+                    ("conversion", json!(expression.conversion as i64)),
+                    (
+                        "format_spec",
+                        expression.format_spec.as_deref().map_or(Value::Null, |spec| {
+                            let values = spec
+                                .elements
+                                .iter()
+                                .map(|element| self.f_string_element(element))
+                                .collect();
+                            self.node("JoinedStr", spec.range, [("values", Value::Array(values))])
+                        }),
+                    ),
+                ],
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::to_cpython_json;
+    use crate::{parse, Mode};
+    use ruff_python_ast::min_version::MinVersion;
+
+    fn dump(source: &str) -> serde_json::Value {
+        let module = parse(source, Mode::Module).unwrap();
+        to_cpython_json(&module, source, MinVersion::PY311)
+    }
+
+    #[test]
+    fn simple_call() {
+        let value = dump("print(1)\n");
+        let call = &value["body"][0]["value"];
+        assert_eq!(call["_type"], json!("Call"));
+        assert_eq!(call["func"]["id"], json!("print"));
+        assert_eq!(call["args"][0]["value"], json!(1));
+        assert_eq!(call["lineno"], json!(1));
+        assert_eq!(call["col_offset"], json!(0));
+    }
+
+    #[test]
+    fn implicit_string_concatenation_merges_into_one_constant() {
+        let value = dump("'a' 'b'\n");
+        let constant = &value["body"][0]["value"];
+        assert_eq!(constant["_type"], json!("Constant"));
+        assert_eq!(constant["value"], json!("ab"));
+    }
+
+    #[test]
+    fn elif_nests_as_an_if_in_orelse() {
+        let value = dump("if a:\n    pass\nelif b:\n    pass\nelse:\n    pass\n");
+        let orelse = &value["body"][0]["orelse"][0];
+        assert_eq!(orelse["_type"], json!("If"));
+        assert_eq!(orelse["orelse"][0]["_type"], json!("Pass"));
+    }
+
+    #[test]
+    fn parameters_split_into_defaults_and_kw_defaults() {
+        let value = dump("def f(a, b=1, *, c, d=2): pass\n");
+        let args = &value["body"][0]["args"];
+        let defaults = args["defaults"].as_array().unwrap();
+        assert_eq!(defaults.len(), 1);
+        assert_eq!(defaults[0]["value"], json!(1));
+        let kw_defaults = args["kw_defaults"].as_array().unwrap();
+        assert_eq!(kw_defaults[0], serde_json::Value::Null);
+        assert_eq!(kw_defaults[1]["value"], json!(2));
+    }
+
+    #[test]
+    fn operators_and_singletons_use_cpython_names() {
+        let value = dump("match x:\n    case None:\n        pass\n");
+        let pattern = &value["body"][0]["cases"][0]["pattern"];
+        assert_eq!(pattern["_type"], json!("MatchSingleton"));
+        assert_eq!(pattern["value"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn type_comment_reports_the_raw_text() {
+        let value = dump("x = []  # type: List[int]\n");
+        assert_eq!(value["body"][0]["type_comment"], json!("List[int]"));
+    }
+
+    #[test]
+    fn type_comment_is_null_without_a_trailing_comment() {
+        let value = dump("x = 1\n");
+        assert_eq!(value["body"][0]["type_comment"], serde_json::Value::Null);
+    }
+}
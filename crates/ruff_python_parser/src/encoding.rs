@@ -0,0 +1,330 @@
+//! Detecting a [PEP 263] encoding declaration and decoding legacy single-byte encodings to UTF-8.
+//!
+//! Every other module in this crate works on an already-decoded `&str`, so this module's job
+//! stops at turning raw file bytes into one: [`decode_source`] looks at a leading BOM and the
+//! first two lines for a `# -*- coding: ... -*-` (or `# coding: ...`) comment, decodes the bytes
+//! using whichever of the two (or UTF-8, absent either) applies, and hands back the resulting
+//! [`DecodedSource`], which can map an offset in the decoded text back to the byte offset it came
+//! from in the original file.
+//!
+//! Wiring this into the `parse_*` family so they can accept `&[u8]` directly is a larger, separate
+//! change — those functions, and everything downstream of them (diagnostics, `Locator`, the
+//! formatter), assume their input is already a `&str` slice that ranges index into directly, and
+//! making that hold for decoded-from-Latin-1 source too means threading a byte-offset mapping
+//! through all of them, not just lexing. This module is the piece that doesn't depend on that:
+//! given the file's bytes, produce the `String` those functions already know how to consume.
+//!
+//! [PEP 263]: https://peps.python.org/pep-0263/
+
+use std::fmt;
+
+/// A single-byte legacy encoding this module knows how to decode to UTF-8.
+///
+/// Deliberately small: these are the two encodings [PEP 263] itself gives as examples of what a
+/// coding declaration is for, and both are simple enough to decode without pulling in a general
+/// purpose codec crate. Anything else named in a coding declaration is reported as
+/// [`DecodeError::UnsupportedEncoding`] rather than silently mis-decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegacyEncoding {
+    /// `latin-1` / `iso-8859-1`: byte `b` maps directly to the code point `U+00{b:02X}`.
+    Latin1,
+    /// `cp1252` / `windows-1252`: same as Latin-1 except for the `0x80..=0x9F` range, which
+    /// Windows-1252 assigns to printable characters (smart quotes, the euro sign, and so on)
+    /// instead of the C1 control codes Latin-1 puts there.
+    Cp1252,
+}
+
+impl LegacyEncoding {
+    /// Recognizes the handful of spellings Python itself accepts for these two codecs. See
+    /// <https://docs.python.org/3/library/codecs.html#standard-encodings>.
+    fn from_label(label: &str) -> Option<LegacyEncoding> {
+        match label.to_ascii_lowercase().replace(['_', ' '], "-").as_str() {
+            "latin-1" | "latin1" | "iso-8859-1" | "iso8859-1" | "8859" | "l1" => {
+                Some(LegacyEncoding::Latin1)
+            }
+            "cp1252" | "windows-1252" => Some(LegacyEncoding::Cp1252),
+            _ => None,
+        }
+    }
+
+    fn decode_byte(self, byte: u8) -> char {
+        match self {
+            LegacyEncoding::Latin1 => char::from(byte),
+            LegacyEncoding::Cp1252 => match byte {
+                0x80 => '\u{20AC}',
+                0x82 => '\u{201A}',
+                0x83 => '\u{0192}',
+                0x84 => '\u{201E}',
+                0x85 => '\u{2026}',
+                0x86 => '\u{2020}',
+                0x87 => '\u{2021}',
+                0x88 => '\u{02C6}',
+                0x89 => '\u{2030}',
+                0x8A => '\u{0160}',
+                0x8B => '\u{2039}',
+                0x8C => '\u{0152}',
+                0x8E => '\u{017D}',
+                0x91 => '\u{2018}',
+                0x92 => '\u{2019}',
+                0x93 => '\u{201C}',
+                0x94 => '\u{201D}',
+                0x95 => '\u{2022}',
+                0x96 => '\u{2013}',
+                0x97 => '\u{2014}',
+                0x98 => '\u{02DC}',
+                0x99 => '\u{2122}',
+                0x9A => '\u{0161}',
+                0x9B => '\u{203A}',
+                0x9C => '\u{0153}',
+                0x9E => '\u{017E}',
+                0x9F => '\u{0178}',
+                // The remaining 0x80..=0x9F bytes are unassigned in Windows-1252; fall back to
+                // Latin-1's interpretation rather than erroring, matching how Python's `cp1252`
+                // codec maps them to the corresponding C1 control codes.
+                _ => char::from(byte),
+            },
+        }
+    }
+}
+
+impl fmt::Display for LegacyEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LegacyEncoding::Latin1 => "latin-1",
+            LegacyEncoding::Cp1252 => "cp1252",
+        })
+    }
+}
+
+/// Why [`decode_source`] couldn't produce a [`DecodedSource`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The coding declaration named an encoding this module doesn't implement a decoder for.
+    UnsupportedEncoding(String),
+    /// The coding declaration named an encoding other than UTF-8, but the file also starts with a
+    /// UTF-8 BOM, which PEP 263 calls out as a hard error rather than picking one.
+    ConflictingBom { declared: String },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnsupportedEncoding(name) => {
+                write!(f, "unsupported encoding declaration: {name}")
+            }
+            DecodeError::ConflictingBom { declared } => write!(
+                f,
+                "encoding declaration `{declared}` conflicts with a UTF-8 byte order mark"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The result of decoding a source file's raw bytes to UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSource {
+    /// The decoded, UTF-8 source text, with any BOM already stripped.
+    pub text: String,
+    /// Whether a UTF-8 BOM was present at the start of the file.
+    pub had_bom: bool,
+    /// The encoding that was used to decode `text`, or `None` if the input was already UTF-8
+    /// (whether or not a coding declaration redundantly said so).
+    pub encoding: Option<LegacyEncoding>,
+    /// `byte_offsets[i]` is the offset, in the original file's bytes (after the BOM, if any, was
+    /// stripped), of the byte that decoded to `text`'s `i`-th byte.
+    byte_offsets: Vec<u32>,
+}
+
+impl DecodedSource {
+    /// Maps a byte offset into `self.text` back to the corresponding byte offset in the original,
+    /// possibly-BOM-prefixed file.
+    ///
+    /// Panics if `offset` is out of bounds for `self.text`, mirroring how `ruff_text_size` ranges
+    /// are expected to always be in bounds for the text they index into.
+    #[must_use]
+    pub fn map_to_original(&self, offset: u32) -> u32 {
+        let mapped = self.byte_offsets[offset as usize];
+        mapped + u32::from(self.had_bom) * BOM_UTF8.len() as u32
+    }
+}
+
+const BOM_UTF8: &str = "\u{feff}";
+
+/// Scans `bytes` for a UTF-8 BOM and a PEP 263 coding declaration, and decodes accordingly.
+///
+/// Returns `Err` if the declared encoding isn't one this module supports, or if it conflicts with
+/// a BOM that's also present. A missing or UTF-8 coding declaration always succeeds, since `text`
+/// is already expected to be valid UTF-8 in that case (this function doesn't itself validate that
+/// — a non-UTF-8 file with no coding declaration will surface as invalid UTF-8 wherever the
+/// caller next tries to use the result as a `&str`, the same as it does today).
+pub fn decode_source(bytes: &[u8]) -> Result<DecodedSource, DecodeError> {
+    let had_bom = bytes.starts_with(BOM_UTF8.as_bytes());
+    let rest = if had_bom {
+        &bytes[BOM_UTF8.len()..]
+    } else {
+        bytes
+    };
+
+    let declared = find_coding_declaration(rest);
+
+    match declared {
+        None | Some("utf-8" | "utf8" | "u8") => {
+            let text = String::from_utf8_lossy(rest).into_owned();
+            let byte_offsets = (0..=u32::try_from(text.len()).unwrap_or(u32::MAX)).collect();
+            Ok(DecodedSource {
+                text,
+                had_bom,
+                encoding: None,
+                byte_offsets,
+            })
+        }
+        Some(label) => {
+            if had_bom {
+                return Err(DecodeError::ConflictingBom {
+                    declared: label.to_string(),
+                });
+            }
+            let Some(encoding) = LegacyEncoding::from_label(label) else {
+                return Err(DecodeError::UnsupportedEncoding(label.to_string()));
+            };
+
+            let mut text = String::with_capacity(rest.len());
+            let mut byte_offsets = Vec::with_capacity(rest.len() + 1);
+            for (original_offset, &byte) in rest.iter().enumerate() {
+                let decoded_offset_before = u32::try_from(text.len()).unwrap_or(u32::MAX);
+                let decoded_char = encoding.decode_byte(byte);
+                for _ in 0..decoded_char.len_utf8() {
+                    byte_offsets.push(u32::try_from(original_offset).unwrap_or(u32::MAX));
+                }
+                text.push(decoded_char);
+                debug_assert!(decoded_offset_before as usize <= text.len());
+            }
+            byte_offsets.push(u32::try_from(rest.len()).unwrap_or(u32::MAX));
+
+            Ok(DecodedSource {
+                text,
+                had_bom,
+                encoding: Some(encoding),
+                byte_offsets,
+            })
+        }
+    }
+}
+
+/// Looks for a `# -*- coding: <name> -*-` (or bare `# coding: <name>`/`# coding=<name>`)
+/// declaration on the first or second line of `bytes`, per [PEP 263]'s grammar. Returns the
+/// matched encoding label, unvalidated.
+///
+/// [PEP 263]: https://peps.python.org/pep-0263/
+fn find_coding_declaration(bytes: &[u8]) -> Option<&str> {
+    for line in bytes.split(|&b| b == b'\n').take(2) {
+        let line = std::str::from_utf8(line).ok()?;
+        let Some(hash) = line.find('#') else {
+            continue;
+        };
+        let comment = &line[hash + 1..];
+        let Some(coding_at) = comment.find("coding") else {
+            continue;
+        };
+        let after_coding = comment[coding_at + "coding".len()..].trim_start();
+        let Some(after_sep) = after_coding
+            .strip_prefix(':')
+            .or_else(|| after_coding.strip_prefix('='))
+        else {
+            continue;
+        };
+        let label = after_sep.trim_start();
+        let end = label
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .unwrap_or(label.len());
+        if end == 0 {
+            continue;
+        }
+        return Some(&label[..end]);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_utf8_has_no_encoding() {
+        let decoded = decode_source("print('hi')".as_bytes()).unwrap();
+        assert_eq!(decoded.text, "print('hi')");
+        assert!(!decoded.had_bom);
+        assert_eq!(decoded.encoding, None);
+    }
+
+    #[test]
+    fn bom_is_stripped_and_recorded() {
+        let mut bytes = BOM_UTF8.as_bytes().to_vec();
+        bytes.extend_from_slice(b"x = 1\n");
+        let decoded = decode_source(&bytes).unwrap();
+        assert_eq!(decoded.text, "x = 1\n");
+        assert!(decoded.had_bom);
+    }
+
+    #[test]
+    fn latin1_coding_declaration_decodes_high_bytes() {
+        // `Ã©` spelled out in Latin-1 is the single byte 0xE9, which is `é` (U+00E9).
+        let mut bytes = b"# -*- coding: latin-1 -*-\nx = '".to_vec();
+        bytes.push(0xE9);
+        bytes.extend_from_slice(b"'\n");
+        let decoded = decode_source(&bytes).unwrap();
+        assert_eq!(decoded.encoding, Some(LegacyEncoding::Latin1));
+        assert!(decoded.text.contains('\u{E9}'));
+    }
+
+    #[test]
+    fn cp1252_smart_quotes_differ_from_latin1() {
+        let mut bytes = b"# coding=cp1252\nx = '".to_vec();
+        bytes.push(0x93); // left double quotation mark in cp1252, a control code in Latin-1.
+        bytes.extend_from_slice(b"'\n");
+        let decoded = decode_source(&bytes).unwrap();
+        assert_eq!(decoded.encoding, Some(LegacyEncoding::Cp1252));
+        assert!(decoded.text.contains('\u{201C}'));
+    }
+
+    #[test]
+    fn unsupported_encoding_is_reported() {
+        let err = decode_source(b"# coding: shift-jis\nx = 1\n").unwrap_err();
+        assert_eq!(err, DecodeError::UnsupportedEncoding("shift-jis".to_string()));
+    }
+
+    #[test]
+    fn bom_conflicting_with_declaration_is_an_error() {
+        let mut bytes = BOM_UTF8.as_bytes().to_vec();
+        bytes.extend_from_slice(b"# coding: latin-1\nx = 1\n");
+        let err = decode_source(&bytes).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::ConflictingBom {
+                declared: "latin-1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn declaration_past_first_two_lines_is_ignored() {
+        let bytes = b"x = 1\ny = 2\n# coding: latin-1\n";
+        let decoded = decode_source(bytes).unwrap();
+        assert_eq!(decoded.encoding, None);
+    }
+
+    #[test]
+    fn offsets_map_back_through_multibyte_expansion() {
+        let mut bytes = b"# coding: latin-1\n".to_vec();
+        bytes.push(0xE9);
+        let decoded = decode_source(&bytes).unwrap();
+        // The decoded `é` is 2 bytes in UTF-8 but came from 1 byte in the original file.
+        let last_char_start = u32::try_from(decoded.text.len() - 2).unwrap();
+        assert_eq!(
+            decoded.map_to_original(last_char_start),
+            u32::try_from(bytes.len() - 1).unwrap()
+        );
+    }
+}
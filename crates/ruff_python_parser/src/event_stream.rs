@@ -0,0 +1,180 @@
+//! An alternative view of a parse as a flat [`Event`] stream (`StartNode`/`Token`/`FinishNode`,
+//! plus `Error`), in the spirit of `rust-analyzer`'s parser events.
+//!
+//! This crate's grammar builds a typed [`Mod`] directly rather than an intermediate untyped tree,
+//! so there's no hook inside the grammar itself to emit these events as parsing happens -- doing
+//! that would mean instrumenting every `lalrpop`-generated reduce action (see the module docs on
+//! [`crate::parser`] for why that isn't a maintainable place to hang instrumentation).
+//! [`event_stream`] reconstructs the same information after the fact instead: it walks the
+//! already-built AST in [preorder](ruff_python_ast::visitor::preorder) for `StartNode`/
+//! `FinishNode`, and merges in the separately-lexed token stream for the leaf-level `Token`
+//! events, using each node's range to decide which pending tokens belong to it. A consumer can
+//! fold the result into a green tree, a flat array, or any other representation it likes without
+//! forking the grammar to get at this information.
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use ruff_python_ast::visitor::preorder::{PreorderVisitor, TraversalSignal};
+use ruff_python_ast::{AnyNodeRef, Mod, NodeKind};
+use ruff_text_size::{Ranged, TextRange};
+
+use crate::{parse, tokenize_all, Mode, Tok, TokenKind};
+
+/// One step of a parse, in source order. `StartNode`/`FinishNode` pairs nest the same way the AST
+/// does; `Token` events appear between a node's `StartNode` and `FinishNode` once attached to it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A node started at `range`, matched by a later [`Event::FinishNode`].
+    StartNode { kind: NodeKind, range: TextRange },
+    /// A single token, attached to the innermost node open at its position.
+    Token { kind: TokenKind, range: TextRange },
+    /// The innermost currently open node ended.
+    FinishNode,
+    /// The parser reported a syntax error at `range`. This crate's parser reports the first
+    /// syntax error it hits rather than recovering past it, so there is at most one of these, and
+    /// it's always the last event in the stream.
+    Error { message: String, range: TextRange },
+}
+
+/// Parses `source` in the given `mode` and renders it as an [`Event`] stream.
+///
+/// On success, the stream covers every token and node the grammar produced. On failure, the
+/// stream is just the single [`Event::Error`] -- there's no partial tree to report events for,
+/// since this crate's parser doesn't recover past a syntax error (see [`crate::stats`] for the
+/// same point made about counters).
+pub fn event_stream(source: &str, mode: Mode) -> Vec<Event> {
+    match parse(source, mode) {
+        Ok(module) => build(&module, source, mode),
+        Err(error) => vec![Event::Error {
+            message: error.error.to_string(),
+            range: TextRange::empty(error.offset),
+        }],
+    }
+}
+
+fn build(module: &Mod, source: &str, mode: Mode) -> Vec<Event> {
+    let tokens = tokenize_all(source, mode)
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+
+    let mut builder = EventBuilder {
+        tokens,
+        events: Vec::new(),
+    };
+    builder.visit_mod(module);
+    builder.drain_remaining_tokens();
+    builder.events
+}
+
+struct EventBuilder {
+    tokens: Peekable<IntoIter<(Tok, TextRange)>>,
+    events: Vec<Event>,
+}
+
+impl EventBuilder {
+    /// Emits a [`Event::Token`] for every pending token that starts before `offset`, in order.
+    fn drain_tokens_before(&mut self, offset: ruff_text_size::TextSize) {
+        while self
+            .tokens
+            .peek()
+            .is_some_and(|(_, range)| range.start() < offset)
+        {
+            self.emit_next_token();
+        }
+    }
+
+    /// Emits an [`Event::Token`] for every token left once the tree walk is done, such as a
+    /// trailing newline or the end-of-file marker that falls outside the module's own range.
+    fn drain_remaining_tokens(&mut self) {
+        while self.tokens.peek().is_some() {
+            self.emit_next_token();
+        }
+    }
+
+    fn emit_next_token(&mut self) {
+        let (tok, range) = self.tokens.next().expect("just peeked Some");
+        self.events.push(Event::Token {
+            kind: TokenKind::from(&tok),
+            range,
+        });
+    }
+}
+
+impl<'a> PreorderVisitor<'a> for EventBuilder {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        self.drain_tokens_before(node.range().start());
+        self.events.push(Event::StartNode {
+            kind: node.kind(),
+            range: node.range(),
+        });
+        TraversalSignal::Traverse
+    }
+
+    fn leave_node(&mut self, node: AnyNodeRef<'a>) {
+        self.drain_tokens_before(node.range().end());
+        self.events.push(Event::FinishNode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::NodeKind;
+
+    use super::{event_stream, Event};
+    use crate::Mode;
+
+    #[test]
+    fn a_successful_parse_brackets_every_node_and_reports_no_error() {
+        let events = event_stream("x = 1\n", Mode::Module);
+        assert!(events
+            .iter()
+            .all(|event| !matches!(event, Event::Error { .. })));
+        let starts = events
+            .iter()
+            .filter(|event| matches!(event, Event::StartNode { .. }))
+            .count();
+        let finishes = events
+            .iter()
+            .filter(|event| matches!(event, Event::FinishNode))
+            .count();
+        assert_eq!(starts, finishes);
+        assert!(starts > 0);
+    }
+
+    #[test]
+    fn every_token_is_attached_inside_a_start_finish_pair() {
+        let events = event_stream("x = 1\n", Mode::Module);
+        let mut depth = 0;
+        for event in &events {
+            match event {
+                Event::StartNode { .. } => depth += 1,
+                Event::FinishNode => depth -= 1,
+                Event::Token { .. } => assert!(depth > 0, "token emitted outside any node"),
+                Event::Error { .. } => unreachable!("this source parses successfully"),
+            }
+        }
+        assert_eq!(depth, 0);
+    }
+
+    #[test]
+    fn a_syntax_error_produces_a_single_error_event() {
+        let events = event_stream("x =", Mode::Module);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Event::Error { .. }));
+    }
+
+    #[test]
+    fn the_module_itself_is_the_outermost_node() {
+        let events = event_stream("x = 1\n", Mode::Module);
+        assert!(matches!(
+            events.first(),
+            Some(Event::StartNode {
+                kind: NodeKind::ModModule,
+                ..
+            })
+        ));
+    }
+}
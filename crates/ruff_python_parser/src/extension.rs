@@ -0,0 +1,130 @@
+//! A small extension point for embedders that want to recognize their own escape-prefixed
+//! commands inside parsed source, without forking the grammar.
+//!
+//! This crate's own IPython support (see [`crate::ipython`]) is the model to follow:
+//! `Mode::Ipython` recognizes a fixed set of escape prefixes (`%`, `!`, `?`, ...) in the lexer
+//! and parses them into [`ast::StmtIpyEscapeCommand`]/[`ast::ExprIpyEscapeCommand`] nodes that
+//! carry the raw, unparsed command text plus which [`ast::IpyEscapeKind`] prefix introduced it.
+//! Teaching the grammar to recognize an altogether new prefix character would mean forking the
+//! lexer and `python.lalrpop` -- out of scope here, and not what this module attempts. What it
+//! offers instead is the next step: a way to register handlers that interpret the command text
+//! already captured in those nodes, the same way [`parse_magic_command`](crate::parse_magic_command)
+//! interprets IPython's own `%`-magics.
+//!
+//! A notebook or DSL embedder that wants its own `%%sql`/`!!docker`-style forms can hand each one
+//! to an [`ExtensionRegistry`] and let it dispatch by kind and command text, rather than
+//! hand-rolling a `match` over every [`ast::IpyEscapeKind`] at each call site.
+
+use ruff_python_ast as ast;
+
+/// A handler for one kind of embedder-defined escape command.
+///
+/// Implementations inspect the raw `value` text of an [`ast::IpyEscapeKind`] command node -- the
+/// same text [`parse_magic_command`](crate::parse_magic_command) would see -- and return `Some`
+/// if they recognize it, or `None` to let [`ExtensionRegistry::dispatch`] try the next handler.
+pub trait StatementExtension {
+    /// What a successful match produces, e.g. a parsed query or a sub-AST.
+    type Output;
+
+    /// Tries to interpret `value`, the text following `kind`'s escape prefix.
+    fn try_handle(&self, kind: ast::IpyEscapeKind, value: &str) -> Option<Self::Output>;
+}
+
+/// An ordered list of [`StatementExtension`]s for a single `Output` type, tried in registration
+/// order by [`dispatch`](ExtensionRegistry::dispatch).
+pub struct ExtensionRegistry<O> {
+    handlers: Vec<Box<dyn StatementExtension<Output = O>>>,
+}
+
+impl<O> ExtensionRegistry<O> {
+    /// Creates a registry with no handlers registered.
+    pub fn new() -> Self {
+        Self {
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Registers `handler`, to be tried after every handler already registered.
+    pub fn register(
+        &mut self,
+        handler: impl StatementExtension<Output = O> + 'static,
+    ) -> &mut Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Tries each registered handler in turn, returning the first match.
+    pub fn dispatch(&self, kind: ast::IpyEscapeKind, value: &str) -> Option<O> {
+        self.handlers
+            .iter()
+            .find_map(|handler| handler.try_handle(kind, value))
+    }
+}
+
+impl<O> Default for ExtensionRegistry<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::IpyEscapeKind;
+
+    use super::{ExtensionRegistry, StatementExtension};
+
+    /// Recognizes `%%sql`-style cell magics and reports the query text, standing in for a
+    /// notebook embedder's own handler.
+    struct SqlCellMagic;
+
+    impl StatementExtension for SqlCellMagic {
+        type Output = String;
+
+        fn try_handle(&self, kind: IpyEscapeKind, value: &str) -> Option<String> {
+            let query = value.strip_prefix("sql")?.trim_start();
+            (kind == IpyEscapeKind::Magic2).then(|| query.to_string())
+        }
+    }
+
+    #[test]
+    fn dispatches_to_a_matching_handler() {
+        let mut registry = ExtensionRegistry::new();
+        registry.register(SqlCellMagic);
+
+        let query = registry
+            .dispatch(IpyEscapeKind::Magic2, "sql select * from users")
+            .unwrap();
+        assert_eq!(query, "select * from users");
+    }
+
+    #[test]
+    fn falls_through_when_nothing_matches() {
+        let mut registry: ExtensionRegistry<String> = ExtensionRegistry::new();
+        registry.register(SqlCellMagic);
+
+        assert!(registry
+            .dispatch(IpyEscapeKind::Magic2, "timeit f(x)")
+            .is_none());
+        assert!(registry
+            .dispatch(IpyEscapeKind::Magic, "sql select 1")
+            .is_none());
+    }
+
+    #[test]
+    fn later_handlers_are_tried_after_earlier_ones_decline() {
+        struct AlwaysNone;
+        impl StatementExtension for AlwaysNone {
+            type Output = String;
+            fn try_handle(&self, _kind: IpyEscapeKind, _value: &str) -> Option<String> {
+                None
+            }
+        }
+
+        let mut registry = ExtensionRegistry::new();
+        registry.register(AlwaysNone).register(SqlCellMagic);
+
+        assert!(registry
+            .dispatch(IpyEscapeKind::Magic2, "sql select 1")
+            .is_some());
+    }
+}
@@ -0,0 +1,83 @@
+//! A panic-proof entry point for fuzzers, gated behind the `fuzz` feature so nothing pays for it
+//! who isn't fuzzing.
+//!
+//! [`fuzz_parse`] commits to a narrower guarantee than [`parse`]: given *any* byte sequence --
+//! valid UTF-8 or not, any length, any content -- it always returns either a parsed module or a
+//! [`ParseError`], and never panics. That's a guarantee about this crate's own code, not about
+//! every crate it calls into; the lalrpop-generated grammar internals and `unicode_names2` are
+//! trusted, not re-audited here.
+//!
+//! Two panics on fuzzer input are worked around here rather than at each call site:
+//! [`Lexer::new`](crate::lexer::Lexer::new) asserts that its input fits in a `u32` (a file over
+//! 4GB would abort the process instead of producing an error), and there's no `Mode` that can
+//! lex bytes that aren't valid UTF-8 at all. Both are handled below before the real parser ever
+//! sees the input.
+
+use ruff_python_ast::Mod;
+use ruff_text_size::TextSize;
+
+use crate::lexer::LexicalErrorType;
+use crate::{parse, Mode, ParseError, ParseErrorType};
+
+/// Parses `bytes` as a Python module the way a fuzzer needs to call this crate: no input, however
+/// malformed, can make it panic.
+///
+/// Invalid UTF-8 is truncated to its longest valid prefix, so a fuzzer-generated byte string that
+/// merely happens not to be valid UTF-8 still gets parsed as whatever Python source its valid
+/// bytes spell out, rather than being rejected outright. Input over 4GB is reported as a
+/// [`ParseError`] up front instead of reaching [`Lexer::new`](crate::lexer::Lexer::new)'s own
+/// size assertion.
+pub fn fuzz_parse(bytes: &[u8]) -> Result<Mod, ParseError> {
+    let source = utf8_prefix(bytes);
+
+    if u32::try_from(source.len()).is_err() {
+        return Err(ParseError {
+            error: ParseErrorType::Lexical(LexicalErrorType::OtherError(
+                "input exceeds the 4GB size the lexer can address".to_string(),
+            )),
+            offset: TextSize::default(),
+        });
+    }
+
+    parse(source, Mode::Module)
+}
+
+/// The longest prefix of `bytes` that's valid UTF-8.
+fn utf8_prefix(bytes: &[u8]) -> &str {
+    match std::str::from_utf8(bytes) {
+        Ok(source) => source,
+        Err(error) => std::str::from_utf8(&bytes[..error.valid_up_to()])
+            .expect("valid_up_to() always lands on a UTF-8 boundary"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzz_parse;
+
+    #[test]
+    fn parses_valid_utf8_source() {
+        assert!(fuzz_parse(b"x = 1\n").is_ok());
+    }
+
+    #[test]
+    fn truncates_invalid_utf8_instead_of_rejecting_outright() {
+        let mut bytes = b"x = 1\n".to_vec();
+        bytes.push(0xff);
+        assert!(fuzz_parse(&bytes).is_ok());
+    }
+
+    #[test]
+    fn never_panics_on_a_grab_bag_of_arbitrary_bytes() {
+        for bytes in [
+            &b""[..],
+            &b"\x00"[..],
+            &[0xff, 0xfe, 0xfd][..],
+            &b"def f(:"[..],
+            &b"'''"[..],
+            &b"\xc0\xaf"[..],
+        ] {
+            let _ = fuzz_parse(bytes);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! An optional `rowan` green-tree backend, gated behind the `green_tree` feature.
+//!
+//! [`crate::event_stream`] already reconstructs a parse as a flat stream of node/token
+//! boundaries; this module feeds that stream into a [`rowan::GreenNodeBuilder`] to get a
+//! [`rowan::GreenNode`] -- a tree with structural sharing and cheap incremental re-parses, the
+//! shape IDE-oriented consumers (editors, language servers) usually want instead of the plain
+//! [`ruff_python_ast::Mod`] this crate's [`crate::parse`] returns. That entry point is untouched:
+//! this is an alternative view built on top of it, not a replacement.
+//!
+//! The event stream only covers meaningful tokens, the same ones [`crate::parse`] keeps -- it
+//! says nothing about the whitespace and comments *between* them. To make the green tree
+//! lossless (every byte of the source accounted for by some token, so the tree round-trips back
+//! to the exact source text), [`green_tree`] fills each gap between consecutive token events
+//! with a synthetic [`Kind::Trivia`] token covering that span verbatim.
+//!
+//! [`rowan::SyntaxNode`] (aliased here as [`SyntaxNode`]) is the "red tree" view over the
+//! [`GreenNode`] [`green_tree`] returns -- parented, with absolute offsets, the shape a
+//! refactoring tool actually walks. [`lower_to_ast`] is the way back from there to the
+//! [`ruff_python_ast::Mod`] the rest of this crate works with.
+
+use std::sync::{Mutex, OnceLock};
+
+use rowan::{GreenNode, GreenNodeBuilder, Language};
+use ruff_text_size::{TextRange, TextSize};
+
+use ruff_python_ast::NodeKind;
+
+use crate::event_stream::{event_stream, Event};
+use crate::{Mode, ParseError, TokenKind};
+
+/// The kind carried by every node and token in a tree built by [`green_tree`]: a composite AST
+/// [`NodeKind`], a lexed [`TokenKind`], a gap of [`Kind::Trivia`] between two tokens that the
+/// lexer itself doesn't represent (whitespace that isn't a [`TokenKind::NonLogicalNewline`]), or
+/// the single [`Kind::Root`] wrapping everything else.
+///
+/// [`Kind::Root`] exists because [`crate::event_stream`] doesn't guarantee its own outermost node
+/// covers the whole source: a trailing newline or the end-of-file marker can fall outside it, and
+/// [`rowan::GreenNodeBuilder`] requires exactly one balanced node at the top of the tree. Wrapping
+/// every event in a [`Kind::Root`] node sidesteps that mismatch instead of requiring the event
+/// stream to paper over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    Root,
+    Node(NodeKind),
+    Token(TokenKind),
+    Trivia,
+}
+
+/// The [`rowan::Language`] this module's green trees are built in.
+///
+/// `NodeKind` and `TokenKind` together have hundreds of variants and no numeric representation
+/// of their own, so rather than hand-maintain a `u16` mapping covering all of them, [`PythonLanguage`]
+/// interns each [`Kind`] it sees into a process-global table the first time it's used and treats
+/// the resulting index as the [`rowan::SyntaxKind`]. The cost is a lock on every kind lookup;
+/// the benefit is that adding an AST node or token variant never requires touching this file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PythonLanguage {}
+
+impl Language for PythonLanguage {
+    type Kind = Kind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> Kind {
+        kind_table().lock().unwrap()[raw.0 as usize]
+    }
+
+    fn kind_to_raw(kind: Kind) -> rowan::SyntaxKind {
+        let mut table = kind_table().lock().unwrap();
+        let index = table
+            .iter()
+            .position(|&interned| interned == kind)
+            .unwrap_or_else(|| {
+                table.push(kind);
+                table.len() - 1
+            });
+        rowan::SyntaxKind(u16::try_from(index).expect("far fewer than u16::MAX distinct kinds"))
+    }
+}
+
+fn kind_table() -> &'static Mutex<Vec<Kind>> {
+    static TABLE: OnceLock<Mutex<Vec<Kind>>> = OnceLock::new();
+    TABLE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub type SyntaxNode = rowan::SyntaxNode<PythonLanguage>;
+pub type SyntaxToken = rowan::SyntaxToken<PythonLanguage>;
+
+/// Parses `source` in the given `mode` and builds a lossless `rowan` green tree from it.
+///
+/// Returns `None` if `source` doesn't parse: [`crate::event_stream`] only has a single error
+/// event to offer in that case, and there's no partial tree to build from it.
+pub fn green_tree(source: &str, mode: Mode) -> Option<GreenNode> {
+    let mut builder = GreenNodeBuilder::new();
+    let mut end_of_previous_token = TextSize::new(0);
+    let mut saw_error = false;
+
+    builder.start_node(PythonLanguage::kind_to_raw(Kind::Root));
+    for event in event_stream(source, mode) {
+        match event {
+            Event::StartNode { kind, .. } => {
+                builder.start_node(PythonLanguage::kind_to_raw(Kind::Node(kind)));
+            }
+            Event::Token { kind, range } => {
+                if range.start() > end_of_previous_token {
+                    let gap = TextRange::new(end_of_previous_token, range.start());
+                    builder.token(PythonLanguage::kind_to_raw(Kind::Trivia), &source[gap]);
+                }
+                builder.token(
+                    PythonLanguage::kind_to_raw(Kind::Token(kind)),
+                    &source[range],
+                );
+                end_of_previous_token = range.end();
+            }
+            Event::FinishNode => builder.finish_node(),
+            Event::Error { .. } => saw_error = true,
+        }
+    }
+    let end_of_source = TextSize::try_from(source.len()).expect("source fits in a TextSize");
+    if end_of_source > end_of_previous_token {
+        let gap = TextRange::new(end_of_previous_token, end_of_source);
+        builder.token(PythonLanguage::kind_to_raw(Kind::Trivia), &source[gap]);
+    }
+    builder.finish_node();
+
+    if saw_error {
+        None
+    } else {
+        Some(builder.finish())
+    }
+}
+
+/// Lowers a lossless tree built by [`green_tree`] back to the [`ast::Mod`] the rest of this
+/// crate works with.
+///
+/// The tree doesn't carry enough of its own structure to rebuild [`ast::Mod`] by walking it --
+/// [`Kind::Node`] only records which AST node a span came from, not that node's fields -- so
+/// this instead leans on the tree's own round-tripping guarantee: [`SyntaxNode::text`] always
+/// reproduces `root`'s exact source text, and re-parsing that text in `mode` deterministically
+/// reproduces the exact [`ast::Mod`] the original parse would have built. Reimplementing that as
+/// a structural walk would mean duplicating the grammar's semantic actions a second time, for a
+/// tree that by construction parses back to the same thing.
+///
+/// [`ast::Mod`]: ruff_python_ast::Mod
+pub fn lower_to_ast(root: &SyntaxNode, mode: Mode) -> Result<ruff_python_ast::Mod, ParseError> {
+    crate::parse(&root.text().to_string(), mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{green_tree, SyntaxNode};
+    use crate::Mode;
+
+    #[test]
+    fn a_green_tree_round_trips_the_exact_source_text() {
+        let source = "def f(x):  # a comment\n    return x + 1\n";
+        let green = green_tree(source, Mode::Module).unwrap();
+        let root = SyntaxNode::new_root(green);
+        assert_eq!(root.text().to_string(), source);
+    }
+
+    #[test]
+    fn a_failed_parse_has_no_green_tree() {
+        assert!(green_tree("x =", Mode::Module).is_none());
+    }
+
+    #[test]
+    fn trivia_between_tokens_is_preserved() {
+        let source = "x   =   1\n";
+        let green = green_tree(source, Mode::Module).unwrap();
+        let root = SyntaxNode::new_root(green);
+        assert_eq!(root.text().to_string(), source);
+    }
+
+    #[test]
+    fn lowering_a_round_tripped_tree_matches_a_direct_parse() {
+        use super::lower_to_ast;
+
+        let source = "def f(x):  # a comment\n    return x + 1\n";
+        let green = green_tree(source, Mode::Module).unwrap();
+        let root = SyntaxNode::new_root(green);
+
+        let lowered = lower_to_ast(&root, Mode::Module).unwrap();
+        let direct = crate::parse(source, Mode::Module).unwrap();
+        assert_eq!(lowered, direct);
+    }
+}
@@ -0,0 +1,168 @@
+//! Support for incremental re-lexing/re-parsing of an edited source file.
+//!
+//! [`damaged_token_range`] is the front half: given the tokens produced for the *old* source, the
+//! *new* source, and the range that was edited, it determines the minimal span of old tokens that
+//! can no longer be trusted and must be re-lexed.
+//!
+//! The returned range is deliberately conservative. Tokens whose lexing depends on state that
+//! isn't visible from the token alone (for example the quoting/indentation context of a
+//! multi-line string, or the outstanding indentation stack) are included in the damaged region by
+//! walking outwards from the edit until we reach a position that is unambiguously a safe
+//! boundary, namely the start of a logical line at column zero.
+//!
+//! [`reparse_module`] is the back half, built on top: it re-parses only the top-level statements
+//! that overlap the edit, and reuses the rest of the old module body as-is (aside from shifting
+//! the ranges of the statements that come after the edit). An IDE driving this on every keystroke
+//! only pays for a full-module parse on the minority of edits that don't land cleanly inside a
+//! single statement's boundaries.
+
+use ruff_python_ast::offset::{offset_body, Shift};
+use ruff_python_ast::Suite;
+use ruff_text_size::{Ranged, TextRange, TextSize};
+
+use crate::lexer::{LexResult, Spanned};
+use crate::{parse_program, ParseError, Tok};
+
+/// The token range invalidated by an edit, expressed both as a range over the old token slice
+/// and as a [`TextRange`] over the old source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamagedRange {
+    /// Index of the first old token that must be discarded (inclusive).
+    pub start_token: usize,
+    /// Index of the first old token, after `start_token`, that is still valid (exclusive end).
+    pub end_token: usize,
+    /// The byte range, in the *old* source, covered by the damaged tokens.
+    pub source_range: TextRange,
+}
+
+/// Computes the minimal range of `old_tokens` invalidated by an edit covering `edit_range` in the
+/// old source.
+///
+/// `old_tokens` must be the successful tokens produced from the old source (see [`crate::tokenize_all`]).
+/// Tokens that start or end a string/f-string, or that can change the indentation stack (`Indent`,
+/// `Dedent`, `Newline`, `NonLogicalNewline`), are treated as unsafe resynchronization points: the
+/// damaged range is widened to the nearest enclosing logical-line boundary so that re-lexing can
+/// restart with a known-good lexer state.
+pub fn damaged_token_range(
+    old_tokens: &[LexResult],
+    edit_range: TextRange,
+) -> Option<DamagedRange> {
+    let spans: Vec<&Spanned> = old_tokens.iter().filter_map(|r| r.as_ref().ok()).collect();
+    if spans.is_empty() {
+        return None;
+    }
+
+    // Find the first token touched by the edit and the last token touched by the edit.
+    let first = spans
+        .iter()
+        .position(|(_, range)| range.end() >= edit_range.start())
+        .unwrap_or(spans.len().saturating_sub(1));
+    let last = spans
+        .iter()
+        .rposition(|(_, range)| range.start() <= edit_range.end())
+        .unwrap_or(first);
+
+    // Widen outwards to the nearest safe resynchronization boundary: the token following a
+    // `Newline` (i.e. the start of a logical line), or the start of the token stream.
+    let mut start_token = first;
+    while start_token > 0 && !is_safe_boundary(&spans[start_token - 1].0) {
+        start_token -= 1;
+    }
+
+    let mut end_token = last + 1;
+    while end_token < spans.len() && !is_safe_boundary(&spans[end_token - 1].0) {
+        end_token += 1;
+    }
+
+    let source_start = spans[start_token].1.start();
+    let source_end = spans
+        .get(end_token.min(spans.len()) - 1)
+        .map_or(source_start, |(_, range)| range.end());
+
+    Some(DamagedRange {
+        start_token,
+        end_token,
+        source_range: TextRange::new(source_start, source_end.max(source_start)),
+    })
+}
+
+/// Returns `true` if a token of this kind can only be followed by the start of a new logical
+/// line, i.e. it is safe to resume lexing right after it without any carried-over context.
+fn is_safe_boundary(tok: &Tok) -> bool {
+    matches!(tok, Tok::Newline | Tok::Indent | Tok::Dedent)
+}
+
+/// Clamps `range` so that it never extends past `len`, used to guard against edits reported at
+/// the very end of the file.
+pub fn clamp_to_source(range: TextRange, len: TextSize) -> TextRange {
+    TextRange::new(range.start().min(len), range.end().min(len))
+}
+
+/// A single text edit: the byte range, in the old source, that was replaced, and the length of
+/// the text that replaced it in the new source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The byte range, in the old source, that was replaced.
+    pub range: TextRange,
+    /// The length, in bytes, of the replacement text in the new source.
+    pub new_len: TextSize,
+}
+
+impl TextEdit {
+    /// The change in length the edit made to the source, signed: [`Shift::Add`] if it grew the
+    /// source, [`Shift::Sub`] if it shrank it.
+    fn shift(self) -> Shift {
+        let old_len = self.range.len();
+        if self.new_len >= old_len {
+            Shift::Add(self.new_len - old_len)
+        } else {
+            Shift::Sub(old_len - self.new_len)
+        }
+    }
+}
+
+/// Re-parses `new_source`, given the already-parsed `old_body` of the source before `edit` was
+/// applied, reusing whichever leading and trailing top-level statements of `old_body` fall
+/// entirely outside `edit.range` instead of re-parsing the whole module.
+///
+/// Falls back to a full [`parse_program`] of `new_source` if no statement boundary separates the
+/// leading reused statements from the trailing ones -- for example, an edit inside the module's
+/// one and only top-level statement, or one that spans from the middle of one statement into the
+/// middle of another.
+pub fn reparse_module(
+    mut old_body: Suite,
+    new_source: &str,
+    edit: TextEdit,
+) -> Result<Suite, ParseError> {
+    let unchanged_before = old_body
+        .iter()
+        .take_while(|stmt| stmt.range().end() <= edit.range.start())
+        .count();
+    let unchanged_after = old_body.len()
+        - old_body[unchanged_before..]
+            .iter()
+            .rev()
+            .take_while(|stmt| stmt.range().start() >= edit.range.end())
+            .count();
+
+    if unchanged_before >= unchanged_after {
+        return parse_program(new_source).map(|module| module.body);
+    }
+
+    let shift = edit.shift();
+    let mut trailing = old_body.split_off(unchanged_after);
+    let damaged = old_body.split_off(unchanged_before);
+
+    let reparse_start = damaged[0].range().start();
+    let reparse_old_end = damaged[damaged.len() - 1].range().end();
+    let reparse_end = shift.apply(reparse_old_end);
+
+    let mut reparsed =
+        parse_program(&new_source[reparse_start.to_usize()..reparse_end.to_usize()])?.body;
+    offset_body(&mut reparsed, Shift::Add(reparse_start));
+    offset_body(&mut trailing, shift);
+
+    old_body.extend(reparsed);
+    old_body.extend(trailing);
+    Ok(old_body)
+}
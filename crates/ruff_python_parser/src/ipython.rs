@@ -0,0 +1,293 @@
+//! Helpers for working with IPython escape commands (`obj?`, `%magic`, `!shell`, ...) outside of
+//! the `python.lalrpop` grammar actions that produce them.
+
+use std::fmt::Write as _;
+
+use ruff_python_ast as ast;
+use ruff_text_size::{Ranged, TextRange, TextSize};
+
+use crate::lexer::{self, LexicalError, LexicalErrorType};
+use crate::{parse_expression_starts_at, parse_tokens, Mode, ParseError, Tok};
+
+/// The structured form of a [`Magic`](ast::IpyEscapeKind::Magic) or
+/// [`Magic2`](ast::IpyEscapeKind::Magic2) escape command's value, as produced by
+/// [`parse_magic_command`].
+#[derive(Debug, PartialEq)]
+pub struct IpyMagicCommand {
+    /// The magic's name, e.g. `timeit` for `%timeit -n 10 f(x)`.
+    pub name: String,
+    /// The option flags that appeared between the name and the payload, e.g. `["-n", "10"]`
+    /// for `%timeit -n 10 f(x)`.
+    pub flags: Vec<String>,
+    /// The trailing Python payload, e.g. `f(x)` for `%timeit -n 10 f(x)`, parsed as an
+    /// expression with ranges mapped back into the original source. `None` if there's no
+    /// payload, or if the payload isn't valid Python (as is the case for many magics, whose
+    /// arguments aren't meant to be parsed as code at all).
+    pub code: Option<ast::Expr>,
+}
+
+/// Splits a magic escape command's `value` (as stored on [`StmtIpyEscapeCommand`] or
+/// [`ExprIpyEscapeCommand`]) into its magic name, option flags, and trailing Python payload, and
+/// parses the payload as an expression.
+///
+/// `value_start` is the offset of `value` within the original source, i.e. the command's range
+/// start plus [`kind.prefix_len()`](ast::IpyEscapeKind::prefix_len); it's used to map the parsed
+/// payload's ranges back into the original source.
+///
+/// Returns `None` if `kind` isn't [`Magic`](ast::IpyEscapeKind::Magic) or
+/// [`Magic2`](ast::IpyEscapeKind::Magic2), or if `value` has no magic name (e.g. it's empty).
+///
+/// [`StmtIpyEscapeCommand`]: ast::StmtIpyEscapeCommand
+/// [`ExprIpyEscapeCommand`]: ast::ExprIpyEscapeCommand
+pub fn parse_magic_command(
+    kind: ast::IpyEscapeKind,
+    value: &str,
+    value_start: TextSize,
+) -> Option<IpyMagicCommand> {
+    if !kind.is_magic() {
+        return None;
+    }
+
+    let (name, mut rest) = next_token(value)?;
+    let mut offset = value.len() - rest.len();
+
+    let mut flags = Vec::new();
+    while let Some((flag, remainder)) = next_token(rest) {
+        if !flag.starts_with('-') {
+            break;
+        }
+        flags.push(flag.to_string());
+        offset = value.len() - remainder.len();
+        rest = remainder;
+
+        // Many magics take argparse-style numeric option values (e.g. `timeit`'s `-n 10`);
+        // treat a bare number immediately following a flag as that flag's value rather than
+        // the start of the payload.
+        if let Some((flag_value, remainder)) = next_token(rest) {
+            if !flag_value.is_empty() && flag_value.bytes().all(|b| b.is_ascii_digit()) {
+                flags.push(flag_value.to_string());
+                offset = value.len() - remainder.len();
+                rest = remainder;
+            }
+        }
+    }
+
+    let code_offset = offset + (rest.len() - rest.trim_start().len());
+    let code = rest.trim_start();
+    let code = if code.is_empty() {
+        None
+    } else {
+        parse_expression_starts_at(code, value_start + TextSize::try_from(code_offset).unwrap())
+            .ok()
+    };
+
+    Some(IpyMagicCommand {
+        name: name.to_string(),
+        flags,
+        code,
+    })
+}
+
+/// Returns the next whitespace-delimited token in `s` after skipping leading whitespace, along
+/// with the remainder of `s` following that token. Returns `None` if `s` is empty once leading
+/// whitespace is skipped.
+fn next_token(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    if s.is_empty() {
+        return None;
+    }
+    match s.find(char::is_whitespace) {
+        Some(end) => Some((&s[..end], &s[end..])),
+        None => Some((s, "")),
+    }
+}
+
+/// How [`parse_with_ipy_escape_handling`] should treat an escape command found while parsing in
+/// a mode other than [`Mode::Ipython`].
+///
+/// Outside `Mode::Ipython`, the lexer never produces an escape-command token in the first place
+/// (it only recognizes `%`/`!`/`?` specially when `self.mode == Mode::Ipython`), so there's no
+/// per-token flag to flip here the way there is for, say, nesting depth: the only way to get an
+/// escape command recognized at all is to lex under `Mode::Ipython`, whose grammar already
+/// accepts a free mix of ordinary statements and escape commands in the same source. This type
+/// just gives a name to the two useful ways of layering that onto a `mode` other than `Ipython`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IpyEscapeHandling {
+    /// Parse exactly as `mode` dictates: an escape command is a syntax error outside
+    /// `Mode::Ipython`, same as calling [`crate::parse`] directly. The default.
+    #[default]
+    Error,
+    /// Recognize escape commands but drop them: each one is removed from the token stream
+    /// before the grammar sees it (so it can't produce the `Mode::Ipython`-only error), and its
+    /// range is reported back separately instead of appearing in the tree at all.
+    Skip,
+    /// Recognize escape commands and parse them into real `ast::Stmt::IpyEscapeCommand`/
+    /// `ast::Expr::IpyEscapeCommand` nodes, the same as parsing the whole source with
+    /// `Mode::Ipython` would.
+    Parse,
+}
+
+/// Parses `source` as `mode`, applying `handling` to any IPython escape command (`%magic`,
+/// `!shell`, `obj?`) found along the way -- useful for a tool that wants to scan a file that
+/// might be a notebook-exported script without bailing out at the first `!pip install foo` line.
+///
+/// Returns the parsed module alongside the range of every escape command [`IpyEscapeHandling::Skip`]
+/// removed from it. Always empty for [`IpyEscapeHandling::Error`] and [`IpyEscapeHandling::Parse`],
+/// since neither of those drops anything from the tree.
+pub fn parse_with_ipy_escape_handling(
+    source: &str,
+    mode: Mode,
+    handling: IpyEscapeHandling,
+) -> Result<(ast::Mod, Vec<TextRange>), ParseError> {
+    if handling == IpyEscapeHandling::Error || mode == Mode::Ipython {
+        return crate::parse(source, mode).map(|module| (module, Vec::new()));
+    }
+
+    if handling == IpyEscapeHandling::Parse {
+        return crate::parse(source, Mode::Ipython).map(|module| (module, Vec::new()));
+    }
+
+    let mut skipped = Vec::new();
+    let mut tokens = Vec::new();
+    let mut lexed = lexer::lex(source, Mode::Ipython).peekable();
+    while let Some(result) = lexed.next() {
+        let Ok((Tok::IpyEscapeCommand { .. }, mut range)) = result else {
+            tokens.push(result);
+            continue;
+        };
+        while let Some(Ok((tok, next_range))) = lexed.peek() {
+            let is_newline = matches!(tok, Tok::Newline);
+            range = TextRange::new(range.start(), next_range.end());
+            lexed.next();
+            if is_newline {
+                break;
+            }
+        }
+        skipped.push(range);
+    }
+
+    parse_tokens(tokens, source, mode).map(|module| (module, skipped))
+}
+
+/// Renders the target of a help-end escape command (everything before the trailing `?`/`??`)
+/// back into the source text IPython expects, e.g. `foo.bar[0]`.
+///
+/// # Errors
+///
+/// Returns an error if `expr` contains anything other than a name, attribute access, or
+/// subscript by an integer literal, since those are the only targets IPython's help-end syntax
+/// supports.
+pub(crate) fn unparse_help_end_target(expr: &ast::Expr, buffer: &mut String) -> Result<(), LexicalError> {
+    match expr {
+        ast::Expr::Name(ast::ExprName { id, .. }) => {
+            buffer.push_str(id.as_str());
+        }
+        ast::Expr::Subscript(ast::ExprSubscript { value, slice, range, .. }) => {
+            let ast::Expr::NumberLiteral(ast::ExprNumberLiteral { value: ast::Number::Int(integer), .. }) = slice.as_ref() else {
+                return Err(LexicalError {
+                    error: LexicalErrorType::OtherError("only integer literals are allowed in Subscript expressions in help end escape command".to_string()),
+                    location: range.start(),
+                });
+            };
+            unparse_help_end_target(value, buffer)?;
+            let _ = write!(buffer, "[{integer}]");
+        }
+        ast::Expr::Attribute(ast::ExprAttribute { value, attr, .. }) => {
+            unparse_help_end_target(value, buffer)?;
+            buffer.push('.');
+            buffer.push_str(attr.as_str());
+        }
+        _ => {
+            return Err(LexicalError {
+                error: LexicalErrorType::OtherError("only Name, Subscript and Attribute expressions are allowed in help end escape command".to_string()),
+                location: expr.start(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::{IpyEscapeKind, Mod};
+    use ruff_text_size::{Ranged, TextRange, TextSize};
+
+    use super::{parse_magic_command, parse_with_ipy_escape_handling, IpyEscapeHandling, IpyMagicCommand};
+    use crate::Mode;
+
+    fn parse(value: &str) -> Option<IpyMagicCommand> {
+        // As lexed, `value` starts right after the `%`/`%%` prefix.
+        parse_magic_command(IpyEscapeKind::Magic, value, TextSize::from(1))
+    }
+
+    #[test]
+    fn name_flags_and_code() {
+        let command = parse("timeit -n 10 f(x)").unwrap();
+        assert_eq!(command.name, "timeit");
+        assert_eq!(command.flags, vec!["-n".to_string(), "10".to_string()]);
+        assert_eq!(
+            command.code.unwrap().range(),
+            TextRange::new(TextSize::from(14), TextSize::from(18))
+        );
+    }
+
+    #[test]
+    fn name_only() {
+        let command = parse("autoreload").unwrap();
+        assert_eq!(command.name, "autoreload");
+        assert!(command.flags.is_empty());
+        assert!(command.code.is_none());
+    }
+
+    #[test]
+    fn code_that_is_not_valid_python() {
+        // `alias`'s arguments (an alias name followed by a shell command) aren't Python code.
+        let command = parse("alias ll ls -la").unwrap();
+        assert_eq!(command.name, "alias");
+        assert!(command.flags.is_empty());
+        assert!(command.code.is_none());
+    }
+
+    #[test]
+    fn not_a_magic_kind() {
+        assert!(parse_magic_command(IpyEscapeKind::Shell, "ls -la", TextSize::from(1)).is_none());
+    }
+
+    #[test]
+    fn empty_value() {
+        assert!(parse("").is_none());
+    }
+
+    #[test]
+    fn error_handling_rejects_an_escape_command_exactly_like_plain_parse() {
+        let result =
+            parse_with_ipy_escape_handling("!pip install foo\n", Mode::Module, IpyEscapeHandling::Error);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn skip_handling_drops_the_escape_command_and_reports_its_range() {
+        let source = "x = 1\n!pip install foo\ny = 2\n";
+        let (module, skipped) =
+            parse_with_ipy_escape_handling(source, Mode::Module, IpyEscapeHandling::Skip).unwrap();
+        let Mod::Module(module) = module else {
+            panic!("expected a module");
+        };
+        assert_eq!(module.body.len(), 2);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(&source[skipped[0]], "!pip install foo\n");
+    }
+
+    #[test]
+    fn parse_handling_builds_a_real_escape_command_node() {
+        let source = "x = 1\n!pip install foo\n";
+        let (module, skipped) =
+            parse_with_ipy_escape_handling(source, Mode::Module, IpyEscapeHandling::Parse).unwrap();
+        let Mod::Module(module) = module else {
+            panic!("expected a module");
+        };
+        assert_eq!(module.body.len(), 2);
+        assert!(skipped.is_empty());
+        assert!(module.body[1].is_ipy_escape_command_stmt());
+    }
+}
@@ -34,12 +34,14 @@ use std::{char, cmp::Ordering, str::FromStr};
 use unicode_ident::{is_xid_continue, is_xid_start};
 
 use ruff_python_ast::{Int, IpyEscapeKind};
+use ruff_source_file::LineEnding;
 use ruff_text_size::{TextLen, TextRange, TextSize};
 
 use crate::lexer::cursor::{Cursor, EOF_CHAR};
 use crate::lexer::fstring::{FStringContext, FStringContextFlags, FStrings};
 use crate::lexer::indentation::{Indentation, Indentations};
 use crate::{
+    preview::PreviewFeatures,
     soft_keywords::SoftKeywordTransformer,
     string::FStringErrorType,
     token::{StringKind, Tok},
@@ -59,15 +61,60 @@ pub struct Lexer<'source> {
     state: State,
     // Amount of parenthesis.
     nesting: u32,
+    // The deepest `nesting` is allowed to go before `next_token` starts reporting
+    // `TooDeeplyNested` instead of lexing further open brackets.
+    max_nesting_depth: u32,
+    // The deepest `self.fstrings` is allowed to grow (i.e. how many f-strings may be nested
+    // inside one another's replacement fields, as PEP 701 permits) before
+    // `lex_fstring_start` starts reporting `TooDeeplyNestedFString` instead of opening another.
+    max_fstring_nesting_depth: u32,
     // Indentation levels.
     indentations: Indentations,
     pending_indentation: Option<Indentation>,
+    // Whether `input` started with a UTF-8 BOM, which was stripped before lexing began.
+    had_bom: bool,
+    // The line ending style of the first physical newline lexed, used to detect a file that
+    // mixes styles. `None` until the first newline is seen.
+    line_ending_seen: Option<LineEnding>,
+    // Whether a physical newline using a different style than `line_ending_seen` has been lexed.
+    has_mixed_line_endings: bool,
+    // If `true`, lexing a line ending that differs from `line_ending_seen` is a `LexicalError`
+    // instead of just setting `has_mixed_line_endings`.
+    reject_mixed_line_endings: bool,
     // Lexer mode.
     mode: Mode,
+    // Not-yet-released syntax this lexer should accept in addition to whatever `mode` and the
+    // target version already allow. See [`crate::preview`].
+    preview_features: PreviewFeatures,
     // F-string contexts.
     fstrings: FStrings,
+    // Set right after lexing a `!` that opens an f-string conversion (`!s`, `!r`, `!a`); tells
+    // the next call to `next_token` to lex the flag itself with `lex_fstring_conversion_flag`
+    // instead of the generic identifier/keyword path, so long as no whitespace intervenes.
+    fstring_conversion_flag_pending: bool,
+    // Set right after lexing the `{` that opens an f-string replacement field. A `!` can only
+    // mean "start a conversion flag" once a value has been lexed for the field, so this guards
+    // against misreading a bare `!` right after `{` (e.g. an IPython shell escape embedded in an
+    // f-string, `f"{!pwd}"`) as a conversion flag.
+    fstring_expression_just_opened: bool,
 }
 
+/// The default limit on how deeply parentheses, brackets, and braces may be nested, chosen to be
+/// generous enough for any realistic program while still failing long before the host process
+/// would overflow its stack walking or dropping the resulting AST. Override it with
+/// [`Lexer::with_max_nesting_depth`] if a particular embedder needs a tighter bound.
+pub const DEFAULT_MAX_NESTING_DEPTH: u32 = 200;
+
+/// The default limit on how many f-strings may be nested inside one another's replacement
+/// fields (`f"{f"{f"{...}"}"}"`, as PEP 701 permits), chosen the same way as
+/// [`DEFAULT_MAX_NESTING_DEPTH`]: generous enough for any realistic program, but low enough to
+/// fail long before the host process would overflow its stack walking the result. CPython
+/// enforces its own compiler limit on the same construct; this doesn't try to match that exactly,
+/// since doing so would couple this lexer to an implementation detail of a specific CPython
+/// version. Override it with [`Lexer::with_max_fstring_nesting_depth`] if a particular embedder
+/// needs a tighter bound.
+pub const DEFAULT_MAX_FSTRING_NESTING_DEPTH: u32 = 200;
+
 /// Contains a Token along with its `range`.
 pub type Spanned = (Tok, TextRange);
 /// The result of lexing a token.
@@ -140,6 +187,45 @@ pub fn lex_starts_at(
     }
 }
 
+/// A snapshot of a [`Lexer`]'s internal logical-line state, bracket-nesting depth, f-string
+/// stack, and indentation stack, captured by [`Lexer::checkpoint`]. Passing one to
+/// [`lex_starts_at_with_checkpoint`] resumes lexing a bounded sub-range of a larger source as if
+/// the lexer had run continuously from the start of that source, without re-lexing everything
+/// before `start_offset`.
+///
+/// This is a building block for incremental parsing and for tools, such as a language server,
+/// that only need the tokens near a cursor: re-lex the unchanged prefix once, keep the checkpoint
+/// from just before the edited region, and reuse it every time that region changes.
+///
+/// The sub-range must still begin at a position the lexer could plausibly resume from on its own,
+/// i.e. the start of a logical line at the nesting depth recorded in the checkpoint; it does not
+/// encode enough to resume from an arbitrary byte offset such as the middle of a token.
+#[derive(Debug, Clone)]
+pub struct LexerCheckpoint {
+    state: State,
+    nesting: u32,
+    fstrings: FStrings,
+    indentations: Indentations,
+}
+
+/// Create a new lexer over a bounded sub-range of a larger source, resuming from a
+/// [`LexerCheckpoint`] captured earlier in that source instead of starting fresh. See
+/// [`LexerCheckpoint`] for the constraints this places on `source` and `start_offset`.
+pub fn lex_starts_at_with_checkpoint(
+    source: &str,
+    mode: Mode,
+    start_offset: TextSize,
+    checkpoint: LexerCheckpoint,
+) -> LexStartsAtIterator<SoftKeywordTransformer<Lexer>> {
+    LexStartsAtIterator {
+        start_offset,
+        inner: SoftKeywordTransformer::new(
+            Lexer::new(source, mode).with_checkpoint(checkpoint),
+            mode,
+        ),
+    }
+}
+
 impl<'source> Lexer<'source> {
     /// Create a new lexer from T and a starting location. You probably want to use
     /// [`lex`] instead.
@@ -152,33 +238,180 @@ impl<'source> Lexer<'source> {
         let mut lxr = Lexer {
             state: State::AfterNewline,
             nesting: 0,
+            max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+            max_fstring_nesting_depth: DEFAULT_MAX_FSTRING_NESTING_DEPTH,
             indentations: Indentations::default(),
             pending_indentation: None,
+            had_bom: false,
+            line_ending_seen: None,
+            has_mixed_line_endings: false,
+            reject_mixed_line_endings: false,
 
             source: input,
             cursor: Cursor::new(input),
             mode,
+            preview_features: PreviewFeatures::empty(),
             fstrings: FStrings::default(),
+            fstring_conversion_flag_pending: false,
+            fstring_expression_just_opened: false,
         };
-        // TODO: Handle possible mismatch between BOM and explicit encoding declaration.
         // spell-checker:ignore feff
-        lxr.cursor.eat_char('\u{feff}');
+        lxr.had_bom = lxr.cursor.eat_char('\u{feff}');
 
         lxr
     }
 
+    /// Returns `true` if `input` started with a UTF-8 byte order mark, which was stripped before
+    /// lexing began. Every token's range is already relative to the position right after the BOM
+    /// (the first token starts there, not at offset zero), so this is purely informational — it
+    /// exists for callers, such as a formatter, that want to reproduce the BOM on output.
+    ///
+    /// Mismatches between a BOM and an explicit `# -*- coding: ... -*-` declaration are reported
+    /// by [`crate::encoding::decode_source`], which runs before lexing and is where that
+    /// conflict is actually detected.
+    pub fn had_bom(&self) -> bool {
+        self.had_bom
+    }
+
+    /// If set, lexing a line ending that differs from the file's first one is reported as
+    /// [`LexicalErrorType::MixedLineEndings`] instead of silently accepted. Defaults to `false`:
+    /// CPython itself accepts mixed line endings, so by default so does this lexer.
+    #[must_use]
+    pub fn with_reject_mixed_line_endings(mut self, reject: bool) -> Self {
+        self.reject_mixed_line_endings = reject;
+        self
+    }
+
+    /// Returns the line ending style of the first physical newline lexed so far, or `None` if no
+    /// newline has been lexed yet (including a file with no newlines at all).
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        self.line_ending_seen
+    }
+
+    /// Returns `true` if at least two different line ending styles have been lexed.
+    pub fn has_mixed_line_endings(&self) -> bool {
+        self.has_mixed_line_endings
+    }
+
+    /// Records a physical newline's style, setting [`Self::line_ending`] the first time this is
+    /// called and flagging (or, if configured, erroring on) every subsequent call whose `ending`
+    /// differs from that first one.
+    fn record_line_ending(&mut self, ending: LineEnding) -> Result<(), LexicalError> {
+        match self.line_ending_seen {
+            None => self.line_ending_seen = Some(ending),
+            Some(seen) if seen != ending => {
+                self.has_mixed_line_endings = true;
+                if self.reject_mixed_line_endings {
+                    return Err(LexicalError {
+                        error: LexicalErrorType::MixedLineEndings,
+                        location: self.token_start(),
+                    });
+                }
+            }
+            Some(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Overrides the limit on how deeply parentheses, brackets, and braces may be nested.
+    /// Defaults to [`DEFAULT_MAX_NESTING_DEPTH`].
+    #[must_use]
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: u32) -> Self {
+        self.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Overrides the limit on how many f-strings may be nested inside one another's replacement
+    /// fields. Defaults to [`DEFAULT_MAX_FSTRING_NESTING_DEPTH`].
+    #[must_use]
+    pub fn with_max_fstring_nesting_depth(mut self, max_fstring_nesting_depth: u32) -> Self {
+        self.max_fstring_nesting_depth = max_fstring_nesting_depth;
+        self
+    }
+
+    /// Sets which not-yet-released syntax this lexer should accept, on top of whatever `mode`
+    /// and the target version already allow. Defaults to [`PreviewFeatures::empty`].
+    #[must_use]
+    pub fn with_preview_features(mut self, preview_features: PreviewFeatures) -> Self {
+        self.preview_features = preview_features;
+        self
+    }
+
+    /// Returns the set of not-yet-released syntax this lexer was configured to accept.
+    pub fn preview_features(&self) -> PreviewFeatures {
+        self.preview_features
+    }
+
+    /// Captures the lexer's current bracket-nesting depth, f-string stack, and indentation stack
+    /// as a [`LexerCheckpoint`], for resuming lexing later at this point with
+    /// [`Self::with_checkpoint`] (typically via [`lex_starts_at_with_checkpoint`]).
+    pub fn checkpoint(&self) -> LexerCheckpoint {
+        LexerCheckpoint {
+            state: self.state,
+            nesting: self.nesting,
+            fstrings: self.fstrings.clone(),
+            indentations: self.indentations.clone(),
+        }
+    }
+
+    /// Restores a [`LexerCheckpoint`] captured earlier in the same source by [`Self::checkpoint`],
+    /// so lexing can resume mid-file without replaying every token that came before it. See
+    /// [`LexerCheckpoint`] for the constraints this places on where lexing may resume.
+    #[must_use]
+    pub fn with_checkpoint(mut self, checkpoint: LexerCheckpoint) -> Self {
+        self.state = checkpoint.state;
+        self.nesting = checkpoint.nesting;
+        self.fstrings = checkpoint.fstrings;
+        self.indentations = checkpoint.indentations;
+        self
+    }
+
+    /// Increments the bracket-nesting counter, or reports [`LexicalErrorType::TooDeeplyNested`]
+    /// if doing so would exceed `self.max_nesting_depth`.
+    fn enter_nesting(&mut self) -> Result<(), LexicalError> {
+        self.nesting += 1;
+        if self.nesting > self.max_nesting_depth {
+            return Err(LexicalError {
+                error: LexicalErrorType::TooDeeplyNested {
+                    limit: self.max_nesting_depth,
+                },
+                location: self.token_start(),
+            });
+        }
+        Ok(())
+    }
+
+    /// After lexing a NUL byte or other forbidden control character, consumes the rest of the run
+    /// of such characters, up to the next newline outside of brackets or the end of the file,
+    /// instead of leaving them for `next_token` to report one at a time. A truncated or
+    /// non-UTF-8-text file tends to produce long runs of these, and reporting each byte as its
+    /// own [`LexicalError`] just buries the first, useful one in noise.
+    fn resync_after_invalid_run(&mut self, error: LexicalErrorType) -> LexicalError {
+        let location = self.token_start();
+        while !self.cursor.is_eof() {
+            match self.cursor.first() {
+                '\n' | '\r' if self.nesting == 0 => break,
+                c if c.is_ascii_control() && !matches!(c, '\n' | '\r') => {
+                    self.cursor.bump();
+                }
+                _ => break,
+            }
+        }
+        LexicalError { error, location }
+    }
+
     /// Lex an identifier. Also used for keywords and string/bytes literals with a prefix.
     fn lex_identifier(&mut self, first: char) -> Result<Tok, LexicalError> {
         // Detect potential string like rb'' b'' f'' u'' r''
         match (first, self.cursor.first()) {
             ('f' | 'F', quote @ ('\'' | '"')) => {
                 self.cursor.bump();
-                return Ok(self.lex_fstring_start(quote, false));
+                return self.lex_fstring_start(quote, false);
             }
             ('r' | 'R', 'f' | 'F') | ('f' | 'F', 'r' | 'R') if is_quote(self.cursor.second()) => {
                 self.cursor.bump();
                 let quote = self.cursor.bump().unwrap();
-                return Ok(self.lex_fstring_start(quote, true));
+                return self.lex_fstring_start(quote, true);
             }
             (_, quote @ ('\'' | '"')) => {
                 if let Ok(string_kind) = StringKind::try_from(first) {
@@ -249,6 +482,33 @@ impl<'source> Lexer<'source> {
         Ok(keyword)
     }
 
+    /// Lexes the flag right after a `!` that opens an f-string conversion (`f"{x!s}"`). Unlike
+    /// [`Lexer::lex_identifier`], this never consults the keyword table, so a flag that happens
+    /// to collide with a keyword (`f"{x!if}"`) still reports
+    /// [`FStringErrorType::InvalidConversionFlag`] instead of a generic "unexpected token" error
+    /// from the parser. The grammar (`FStringConversion`) still expects a `name` token here, so
+    /// a valid flag is returned as one.
+    fn lex_fstring_conversion_flag(&mut self) -> LexResult {
+        self.cursor.start_token();
+        self.cursor.bump();
+        self.cursor.eat_while(is_identifier_continuation);
+        self.state = State::Other;
+
+        let text = self.token_text();
+        match text {
+            "s" | "r" | "a" => Ok((
+                Tok::Name {
+                    name: text.to_string(),
+                },
+                self.token_range(),
+            )),
+            _ => Err(LexicalError {
+                error: LexicalErrorType::FStringError(FStringErrorType::InvalidConversionFlag),
+                location: self.token_start(),
+            }),
+        }
+    }
+
     /// Numeric lexing. The feast can start!
     fn lex_number(&mut self, first: char) -> Result<Tok, LexicalError> {
         if first == '0' {
@@ -276,7 +536,16 @@ impl<'source> Lexer<'source> {
 
         // Lex the portion of the token after the base prefix (e.g., `9D5` in `0x9D5`).
         let mut number = LexedText::new(self.offset(), self.source);
-        self.radix_run(&mut number, radix);
+        self.radix_run(&mut number, radix)?;
+
+        // A decimal digit immediately following the digit run (`0b10` followed by `2`) is almost
+        // certainly a typo'd digit rather than the start of a new token.
+        if let Some(digit) = self.cursor.eat_if(|c| c.is_ascii_digit()) {
+            return Err(LexicalError {
+                error: LexicalErrorType::InvalidDigitForRadix { radix, digit },
+                location: self.token_start(),
+            });
+        }
 
         // Extract the entire number, including the base prefix (e.g., `0x9D5`).
         let token = &self.source[self.token_range()];
@@ -302,7 +571,7 @@ impl<'source> Lexer<'source> {
         let mut number = LexedText::new(self.token_start(), self.source);
         if first_digit_or_dot != '.' {
             number.push(first_digit_or_dot);
-            self.radix_run(&mut number, Radix::Decimal);
+            self.radix_run(&mut number, Radix::Decimal)?;
         };
 
         let is_float = if first_digit_or_dot == '.' || self.cursor.eat_char('.') {
@@ -310,12 +579,14 @@ impl<'source> Lexer<'source> {
 
             if self.cursor.eat_char('_') {
                 return Err(LexicalError {
-                    error: LexicalErrorType::OtherError("Invalid Syntax".to_owned()),
+                    error: LexicalErrorType::InvalidNumericLiteralUnderscore {
+                        radix: Radix::Decimal,
+                    },
                     location: self.offset() - TextSize::new(1),
                 });
             }
 
-            self.radix_run(&mut number, Radix::Decimal);
+            self.radix_run(&mut number, Radix::Decimal)?;
             true
         } else {
             // Normal number:
@@ -331,10 +602,19 @@ impl<'source> Lexer<'source> {
                     number.push(sign);
                 }
 
-                self.radix_run(&mut number, Radix::Decimal);
+                self.radix_run(&mut number, Radix::Decimal)?;
 
                 true
             }
+            // An `e`/`E` immediately after the digits commits to an exponent; without at least
+            // one digit (optionally signed) following it, it's a malformed exponent rather than
+            // the start of a new token (`1e`, `1e+`, `1e_5`).
+            [b'e' | b'E', ..] => {
+                return Err(LexicalError {
+                    error: LexicalErrorType::MissingExponentDigits,
+                    location: self.offset(),
+                });
+            }
             _ => is_float,
         };
 
@@ -363,9 +643,8 @@ impl<'source> Lexer<'source> {
                 let value = match Int::from_str(number.as_str()) {
                     Ok(value) => {
                         if start_is_zero && value.as_u8() != Some(0) {
-                            // Leading zeros in decimal integer literals are not permitted.
                             return Err(LexicalError {
-                                error: LexicalErrorType::OtherError("Invalid Token".to_owned()),
+                                error: LexicalErrorType::LeadingZeroInDecimalInteger,
                                 location: self.token_range().start(),
                             });
                         }
@@ -386,20 +665,28 @@ impl<'source> Lexer<'source> {
     /// Consume a sequence of numbers with the given radix,
     /// the digits can be decorated with underscores
     /// like this: '`1_2_3_4`' == '1234'
-    fn radix_run(&mut self, number: &mut LexedText, radix: Radix) {
+    fn radix_run(&mut self, number: &mut LexedText, radix: Radix) -> Result<(), LexicalError> {
         loop {
             if let Some(c) = self.cursor.eat_if(|c| radix.is_digit(c)) {
                 number.push(c);
-            }
-            // Number that contains `_` separators. Remove them from the parsed text.
-            else if self.cursor.first() == '_' && radix.is_digit(self.cursor.second()) {
-                // Skip over `_`
-                self.cursor.bump();
-                number.skip_char();
+            } else if self.cursor.first() == '_' {
+                if radix.is_digit(self.cursor.second()) {
+                    // Number that contains `_` separators. Remove them from the parsed text.
+                    self.cursor.bump();
+                    number.skip_char();
+                } else {
+                    // A trailing or doubled underscore (`1_`, `0b1__0`) isn't a valid digit
+                    // separator.
+                    return Err(LexicalError {
+                        error: LexicalErrorType::InvalidNumericLiteralUnderscore { radix },
+                        location: self.offset(),
+                    });
+                }
             } else {
                 break;
             }
         }
+        Ok(())
     }
 
     /// Lex a single comment.
@@ -524,11 +811,21 @@ impl<'source> Lexer<'source> {
         }
     }
 
-    /// Lex a f-string start token.
-    fn lex_fstring_start(&mut self, quote: char, is_raw_string: bool) -> Tok {
+    /// Lex a f-string start token, or report [`LexicalErrorType::TooDeeplyNestedFString`] if
+    /// doing so would exceed `self.max_fstring_nesting_depth`.
+    fn lex_fstring_start(&mut self, quote: char, is_raw_string: bool) -> Result<Tok, LexicalError> {
         #[cfg(debug_assertions)]
         debug_assert_eq!(self.cursor.previous(), quote);
 
+        if self.fstrings.depth() >= self.max_fstring_nesting_depth {
+            return Err(LexicalError {
+                error: LexicalErrorType::TooDeeplyNestedFString {
+                    limit: self.max_fstring_nesting_depth,
+                },
+                location: self.token_start(),
+            });
+        }
+
         let mut flags = FStringContextFlags::empty();
         if quote == '"' {
             flags |= FStringContextFlags::DOUBLE;
@@ -541,7 +838,7 @@ impl<'source> Lexer<'source> {
         }
 
         self.fstrings.push(FStringContext::new(flags, self.nesting));
-        Tok::FStringStart
+        Ok(Tok::FStringStart)
     }
 
     /// Lex a f-string middle or end token.
@@ -690,6 +987,21 @@ impl<'source> Lexer<'source> {
         let value_start = self.offset();
 
         let value_end = loop {
+            // Fast-forward over a run of plain content bytes using SIMD-accelerated scanning,
+            // rather than pulling them through `Cursor::bump` one `char` at a time. Every byte
+            // that the per-character match below treats specially (the closing quote, a
+            // backslash, or a line terminator) is ASCII, so skipping up to the first occurrence
+            // of one of them can never land in the middle of a multi-byte character.
+            let rest = self.cursor.rest().as_bytes();
+            let skip_to_special = memchr::memchr3(quote as u8, b'\\', b'\n', rest)
+                .into_iter()
+                .chain(memchr::memchr(b'\r', rest))
+                .min()
+                .unwrap_or(rest.len());
+            if skip_to_special > 0 {
+                self.cursor.skip_bytes(skip_to_special);
+            }
+
             match self.cursor.bump() {
                 Some('\\') => {
                     if self.cursor.eat_char('\r') {
@@ -769,6 +1081,20 @@ impl<'source> Lexer<'source> {
     // This is the main entry point. Call this function to retrieve the next token.
     // This function is used by the iterator implementation.
     pub fn next_token(&mut self) -> LexResult {
+        let result = self.next_token_impl();
+        self.fstring_expression_just_opened = matches!(result, Ok((Tok::Lbrace, _)));
+        result
+    }
+
+    fn next_token_impl(&mut self) -> LexResult {
+        if self.fstring_conversion_flag_pending {
+            self.fstring_conversion_flag_pending = false;
+            // No whitespace is allowed to intervene for this to count as the flag (`f"{x!s}"`);
+            // `f"{x ! s}"`, while unusual, is lexed as two ordinary tokens, same as before.
+            if is_ascii_identifier_start(self.cursor.first()) {
+                return self.lex_fstring_conversion_flag();
+            }
+        }
         if let Some(fstring) = self.fstrings.current() {
             if !fstring.is_in_expression(self.nesting) {
                 match self.lex_fstring_middle_or_end() {
@@ -795,15 +1121,26 @@ impl<'source> Lexer<'source> {
                 Ok(Ordering::Greater) => {
                     self.pending_indentation = Some(indentation);
                     let offset = self.offset();
-                    self.indentations.dedent_one(indentation).map_err(|_| {
-                        LexicalError::new(LexicalErrorType::IndentationError, offset)
-                    })?;
+                    self.indentations
+                        .dedent_one(indentation)
+                        .map_err(|expected| {
+                            LexicalError::new(
+                                LexicalErrorType::DedentDoesNotMatch {
+                                    expected: expected.column_width(),
+                                    found: indentation.column_width(),
+                                },
+                                offset,
+                            )
+                        })?;
                     return Ok((Tok::Dedent, TextRange::empty(offset)));
                 }
                 Ok(_) => {}
                 Err(_) => {
                     return Err(LexicalError::new(
-                        LexicalErrorType::IndentationError,
+                        LexicalErrorType::DedentDoesNotMatch {
+                            expected: self.indentations.current().column_width(),
+                            found: indentation.column_width(),
+                        },
                         self.offset(),
                     ));
                 }
@@ -828,8 +1165,14 @@ impl<'source> Lexer<'source> {
 
                 Ok((identifier, self.token_range()))
             } else {
+                // `c` can't start an identifier (a confusable character, an emoji, combining
+                // marks with nothing to combine with, ...), but whatever follows it might still
+                // be a perfectly good identifier, so don't consume more than `c` itself: the next
+                // call to `next_token` picks up right after it and recovers normally.
+                self.state = State::Other;
+
                 Err(LexicalError {
-                    error: LexicalErrorType::UnrecognizedToken { tok: c },
+                    error: LexicalErrorType::InvalidCharacterInIdentifier(c),
                     location: self.token_start(),
                 })
             }
@@ -883,8 +1226,12 @@ impl<'source> Lexer<'source> {
         loop {
             match self.cursor.first() {
                 ' ' => {
-                    self.cursor.bump();
-                    indentation = indentation.add_space();
+                    // Indentation is overwhelmingly made up of runs of plain spaces, so count an
+                    // entire run at once with a word-at-a-time scan instead of re-entering this
+                    // loop (and re-matching on `self.cursor.first()`) once per space.
+                    let spaces = count_leading_spaces(self.cursor.rest().as_bytes());
+                    self.cursor.skip_bytes(spaces);
+                    indentation = indentation.add_spaces(u32::try_from(spaces).unwrap_or(u32::MAX));
                 }
                 '\t' => {
                     self.cursor.bump();
@@ -939,9 +1286,18 @@ impl<'source> Lexer<'source> {
             Ok(Ordering::Greater) => {
                 self.pending_indentation = Some(indentation);
 
-                self.indentations.dedent_one(indentation).map_err(|_| {
-                    LexicalError::new(LexicalErrorType::IndentationError, self.offset())
-                })?;
+                let offset = self.offset();
+                self.indentations
+                    .dedent_one(indentation)
+                    .map_err(|expected| {
+                        LexicalError::new(
+                            LexicalErrorType::DedentDoesNotMatch {
+                                expected: expected.column_width(),
+                                found: indentation.column_width(),
+                            },
+                            offset,
+                        )
+                    })?;
 
                 Some((Tok::Dedent, TextRange::empty(self.offset())))
             }
@@ -955,7 +1311,10 @@ impl<'source> Lexer<'source> {
             }
             Err(_) => {
                 return Err(LexicalError {
-                    error: LexicalErrorType::IndentationError,
+                    error: LexicalErrorType::DedentDoesNotMatch {
+                        expected: self.indentations.current().column_width(),
+                        found: indentation.column_width(),
+                    },
                     location: self.offset(),
                 });
             }
@@ -1111,12 +1470,24 @@ impl<'source> Lexer<'source> {
                 if self.cursor.eat_char('=') {
                     Tok::NotEqual
                 } else {
+                    // Only an f-string's replacement field gives `!` any meaning (Python has no
+                    // unary `!` operator), and there it introduces a conversion flag once a value
+                    // has been lexed (a bare `!` right after `{` is something else, e.g. an
+                    // embedded IPython shell escape, `f"{!pwd}"`).
+                    if !self.fstring_expression_just_opened
+                        && self
+                            .fstrings
+                            .current()
+                            .is_some_and(|fstring| fstring.is_in_expression(self.nesting))
+                    {
+                        self.fstring_conversion_flag_pending = true;
+                    }
                     Tok::Exclamation
                 }
             }
             '~' => Tok::Tilde,
             '(' => {
-                self.nesting += 1;
+                self.enter_nesting()?;
                 Tok::Lpar
             }
             ')' => {
@@ -1124,7 +1495,7 @@ impl<'source> Lexer<'source> {
                 Tok::Rpar
             }
             '[' => {
-                self.nesting += 1;
+                self.enter_nesting()?;
                 Tok::Lsqb
             }
             ']' => {
@@ -1132,7 +1503,7 @@ impl<'source> Lexer<'source> {
                 Tok::Rsqb
             }
             '{' => {
-                self.nesting += 1;
+                self.enter_nesting()?;
                 Tok::Lbrace
             }
             '}' => {
@@ -1199,6 +1570,8 @@ impl<'source> Lexer<'source> {
                 }
             }
             '\n' => {
+                self.record_line_ending(LineEnding::Lf)?;
+
                 return Ok((
                     if self.nesting == 0 && !self.state.is_new_logical_line() {
                         self.state = State::AfterNewline;
@@ -1210,10 +1583,15 @@ impl<'source> Lexer<'source> {
                         Tok::NonLogicalNewline
                     },
                     self.token_range(),
-                ))
+                ));
             }
             '\r' => {
-                self.cursor.eat_char('\n');
+                let ending = if self.cursor.eat_char('\n') {
+                    LineEnding::CrLf
+                } else {
+                    LineEnding::Cr
+                };
+                self.record_line_ending(ending)?;
 
                 return Ok((
                     if self.nesting == 0 && !self.state.is_new_logical_line() {
@@ -1229,6 +1607,20 @@ impl<'source> Lexer<'source> {
                 ));
             }
 
+            // NUL and other ASCII control characters aren't valid anywhere outside a string.
+            // `\t`, `\x0C`, `\n`, and `\r` are also control characters, but they're matched by
+            // their own arms above this one and never reach here.
+            c if c.is_ascii_control() => {
+                self.state = State::Other;
+
+                let error = if c == '\0' {
+                    LexicalErrorType::NulByte
+                } else {
+                    LexicalErrorType::ForbiddenControlCharacter(c)
+                };
+                return Err(self.resync_after_invalid_run(error));
+            }
+
             _ => {
                 self.state = State::Other;
 
@@ -1294,6 +1686,7 @@ impl FusedIterator for Lexer<'_> {}
 ///
 /// [lexer]: crate::lexer
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LexicalError {
     /// The type of error that occurred.
     pub error: LexicalErrorType,
@@ -1335,6 +1728,7 @@ impl std::fmt::Display for LexicalError {
 
 /// Represents the different types of errors that can occur during lexing.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LexicalErrorType {
     // TODO: Can probably be removed, the places it is used seem to be able
     // to use the `UnicodeError` variant instead.
@@ -1345,8 +1739,11 @@ pub enum LexicalErrorType {
     UnicodeError,
     /// The nesting of brackets/braces/parentheses is not balanced.
     NestingError,
-    /// The indentation is not consistent.
-    IndentationError,
+    /// A dedent didn't land back on any indentation level still on the stack, mirroring
+    /// CPython's `unindent does not match any outer indentation level`. Carries the column width
+    /// of the indentation level the dedent was compared against (`expected`) and of the
+    /// indentation that didn't match it (`found`).
+    DedentDoesNotMatch { expected: u32, found: u32 },
     /// Inconsistent use of tabs and spaces.
     TabError,
     /// Encountered a tab after a space.
@@ -1373,6 +1770,40 @@ pub enum LexicalErrorType {
     AssignmentError,
     /// An unexpected error occurred.
     OtherError(String),
+    /// Brackets were nested more deeply than the lexer's configured limit, mirroring CPython's
+    /// `too many nested parentheses` error.
+    TooDeeplyNested { limit: u32 },
+    /// F-strings were nested inside one another's replacement fields more deeply than the
+    /// lexer's configured limit, mirroring CPython's own limit on the same construct.
+    TooDeeplyNestedFString { limit: u32 },
+    /// A physical newline used a different line ending style than the file's first one, while
+    /// the lexer was configured (via [`Lexer::with_reject_mixed_line_endings`]) to reject that.
+    MixedLineEndings,
+    /// A NUL byte was encountered outside a string or bytes literal.
+    NulByte,
+    /// An ASCII control character other than NUL (and other than whitespace, which is handled
+    /// separately) was encountered outside a string or bytes literal.
+    ForbiddenControlCharacter(char),
+    /// A character that can't start an identifier was encountered where one was expected, for
+    /// example a confusable Unicode character or an emoji.
+    InvalidCharacterInIdentifier(char),
+    /// A digit that isn't valid for the literal's radix was found immediately after a run of
+    /// otherwise-valid digits, for example the `2` in `0b102`.
+    InvalidDigitForRadix { radix: Radix, digit: char },
+    /// An underscore digit separator was used incorrectly in a numeric literal: trailing
+    /// (`1_`), doubled (`0b1__0`), or immediately after a decimal point (`1._5`).
+    InvalidNumericLiteralUnderscore { radix: Radix },
+    /// A numeric literal ended with an `e`/`E` exponent marker but no exponent digits followed,
+    /// for example `1e` or `1e+`.
+    MissingExponentDigits,
+    /// A decimal integer literal had a leading zero, which CPython only permits for octal
+    /// literals written with an explicit `0o` prefix.
+    LeadingZeroInDecimalInteger,
+    /// A `case` block was found outside of an enclosing `match` statement.
+    CaseOutsideMatch,
+    /// An implicitly concatenated string literal mixed bytes literals (`b"..."`) with str or
+    /// f-string literals, for example `b"a" "b"`.
+    MixedBytesAndNonBytesLiteral,
 }
 
 impl std::error::Error for LexicalErrorType {}
@@ -1384,8 +1815,11 @@ impl std::fmt::Display for LexicalErrorType {
             LexicalErrorType::FStringError(error) => write!(f, "f-string: {error}"),
             LexicalErrorType::UnicodeError => write!(f, "Got unexpected unicode"),
             LexicalErrorType::NestingError => write!(f, "Got unexpected nesting"),
-            LexicalErrorType::IndentationError => {
-                write!(f, "unindent does not match any outer indentation level")
+            LexicalErrorType::DedentDoesNotMatch { expected, found } => {
+                write!(
+                    f,
+                    "unindent does not match any outer indentation level (expected column {expected}, found {found})"
+                )
             }
             LexicalErrorType::TabError => {
                 write!(f, "inconsistent use of tabs and spaces in indentation")
@@ -1420,6 +1854,55 @@ impl std::fmt::Display for LexicalErrorType {
             LexicalErrorType::Eof => write!(f, "unexpected EOF while parsing"),
             LexicalErrorType::AssignmentError => write!(f, "invalid assignment target"),
             LexicalErrorType::OtherError(msg) => write!(f, "{msg}"),
+            LexicalErrorType::TooDeeplyNested { limit } => {
+                write!(
+                    f,
+                    "too many nested parentheses, brackets, and braces (limit is {limit})"
+                )
+            }
+            LexicalErrorType::TooDeeplyNestedFString { limit } => {
+                write!(f, "too many nested f-strings (limit is {limit})")
+            }
+            LexicalErrorType::MixedLineEndings => {
+                write!(f, "inconsistent line ending style")
+            }
+            LexicalErrorType::NulByte => {
+                write!(f, "source contains a null byte")
+            }
+            LexicalErrorType::ForbiddenControlCharacter(c) => {
+                write!(f, "invalid non-printable character U+{:04X}", *c as u32)
+            }
+            LexicalErrorType::InvalidCharacterInIdentifier(c) => {
+                write!(
+                    f,
+                    "invalid character {:?} ({}, U+{:04X}) in identifier",
+                    c,
+                    unicode_char_description(*c),
+                    *c as u32
+                )
+            }
+            LexicalErrorType::InvalidDigitForRadix { radix, digit } => {
+                write!(f, "invalid digit {digit:?} in {} literal", radix.name())
+            }
+            LexicalErrorType::InvalidNumericLiteralUnderscore { radix } => {
+                write!(f, "invalid {} literal", radix.name())
+            }
+            LexicalErrorType::MissingExponentDigits => {
+                write!(f, "numeric literal is missing digits after the exponent")
+            }
+            LexicalErrorType::LeadingZeroInDecimalInteger => {
+                write!(
+                    f,
+                    "leading zeros in decimal integer literals are not permitted; \
+                     use an 0o prefix for octal integers"
+                )
+            }
+            LexicalErrorType::CaseOutsideMatch => {
+                write!(f, "case block not inside a match statement")
+            }
+            LexicalErrorType::MixedBytesAndNonBytesLiteral => {
+                write!(f, "cannot mix bytes and nonbytes literals")
+            }
         }
     }
 }
@@ -1453,8 +1936,11 @@ impl State {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum Radix {
+/// The base of a numeric literal, e.g. `0x1` is [`Radix::Hex`]. Appears in [`LexicalErrorType`]
+/// variants that need to name the offending literal's base in their diagnostic.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Radix {
     Binary,
     Octal,
     Decimal,
@@ -1479,12 +1965,50 @@ impl Radix {
             Radix::Hex => c.is_ascii_hexdigit(),
         }
     }
+
+    /// The name used in diagnostics, e.g. `"invalid digit '2' in binary literal"`.
+    const fn name(self) -> &'static str {
+        match self {
+            Radix::Binary => "binary",
+            Radix::Octal => "octal",
+            Radix::Decimal => "decimal",
+            Radix::Hex => "hexadecimal",
+        }
+    }
 }
 
 const fn is_quote(c: char) -> bool {
     matches!(c, '\'' | '"')
 }
 
+/// Returns the number of leading ASCII space (`' '`) bytes in `bytes`.
+///
+/// Compares a whole `usize` word (8 bytes on the platforms we target) against an all-spaces word
+/// at a time, only falling back to a per-byte scan for the word that contains the first
+/// non-space byte (or the final, possibly short, word). This keeps the common case of a
+/// consistently-indented file from re-entering the indentation state machine once per column.
+fn count_leading_spaces(bytes: &[u8]) -> usize {
+    const WORD_SIZE: usize = std::mem::size_of::<usize>();
+    const SPACES: usize = usize::from_ne_bytes([b' '; WORD_SIZE]);
+
+    let mut count = 0;
+    let mut chunks = bytes.chunks_exact(WORD_SIZE);
+    for chunk in &mut chunks {
+        let word = usize::from_ne_bytes(chunk.try_into().unwrap());
+        if word == SPACES {
+            count += WORD_SIZE;
+        } else {
+            return count + chunk.iter().take_while(|&&b| b == b' ').count();
+        }
+    }
+    count
+        + chunks
+            .remainder()
+            .iter()
+            .take_while(|&&b| b == b' ')
+            .count()
+}
+
 const fn is_ascii_identifier_start(c: char) -> bool {
     matches!(c, 'a'..='z' | 'A'..='Z' | '_')
 }
@@ -1495,6 +2019,27 @@ fn is_unicode_identifier_start(c: char) -> bool {
     is_xid_start(c)
 }
 
+/// Describes `c` for [`LexicalErrorType::InvalidCharacterInIdentifier`]'s error message: its
+/// Unicode name, if `unicode_names2` has one, or otherwise a coarse category (letter, number,
+/// space, control character, or symbol) good enough to explain why it's unexpected.
+fn unicode_char_description(c: char) -> String {
+    if let Some(name) = unicode_names2::name(c) {
+        return name.to_string();
+    }
+    let category = if c.is_alphabetic() {
+        "letter"
+    } else if c.is_numeric() {
+        "number"
+    } else if c.is_whitespace() {
+        "space"
+    } else if c.is_control() {
+        "control character"
+    } else {
+        "symbol"
+    };
+    format!("unnamed {category}")
+}
+
 // Checks if the character c is a valid continuation character as described
 // in https://docs.python.org/3/reference/lexical_analysis.html#identifiers
 fn is_identifier_continuation(c: char) -> bool {
@@ -1592,6 +2137,271 @@ mod tests {
         lex_jupyter_source(&source)
     }
 
+    #[test]
+    fn test_had_bom() {
+        assert!(Lexer::new("\u{feff}x = 1", Mode::Module).had_bom());
+        assert!(!Lexer::new("x = 1", Mode::Module).had_bom());
+    }
+
+    #[test]
+    fn test_bom_does_not_surface_as_a_token() {
+        let with_bom = lex_source("\u{feff}x = 1");
+        let without_bom = lex_source("x = 1");
+        // Same token kinds in the same order; the only difference is that every range in
+        // `with_bom` is offset by the BOM's length in the underlying source.
+        let kinds =
+            |spans: &[Spanned]| spans.iter().map(|(tok, _)| tok.clone()).collect::<Vec<_>>();
+        assert_eq!(kinds(&with_bom), kinds(&without_bom));
+    }
+
+    #[test]
+    fn test_line_ending_tracking() {
+        let mut lexer = Lexer::new("x = 1\ny = 2\n", Mode::Module);
+        while lexer
+            .next_token()
+            .is_ok_and(|(tok, _)| tok != Tok::EndOfFile)
+        {}
+        assert_eq!(lexer.line_ending(), Some(LineEnding::Lf));
+        assert!(!lexer.has_mixed_line_endings());
+    }
+
+    #[test]
+    fn test_mixed_line_endings_are_flagged_by_default() {
+        let mut lexer = Lexer::new("x = 1\ny = 2\r\n", Mode::Module);
+        while lexer
+            .next_token()
+            .is_ok_and(|(tok, _)| tok != Tok::EndOfFile)
+        {}
+        assert_eq!(lexer.line_ending(), Some(LineEnding::Lf));
+        assert!(lexer.has_mixed_line_endings());
+    }
+
+    #[test]
+    fn test_mixed_line_endings_can_be_rejected() {
+        let mut lexer =
+            Lexer::new("x = 1\ny = 2\r\n", Mode::Module).with_reject_mixed_line_endings(true);
+        let result = loop {
+            match lexer.next_token() {
+                Ok((Tok::EndOfFile, _)) => break Ok(()),
+                Ok(_) => continue,
+                Err(err) => break Err(err),
+            }
+        };
+        assert_eq!(
+            result.unwrap_err().error,
+            LexicalErrorType::MixedLineEndings
+        );
+    }
+
+    #[test]
+    fn test_fstring_nesting_within_the_limit_is_accepted() {
+        let mut lexer =
+            Lexer::new(r#"f"{f"{1}"}""#, Mode::Module).with_max_fstring_nesting_depth(2);
+        while lexer
+            .next_token()
+            .is_ok_and(|(tok, _)| tok != Tok::EndOfFile)
+        {}
+    }
+
+    #[test]
+    fn test_fstring_nesting_beyond_the_limit_is_rejected() {
+        let mut lexer =
+            Lexer::new(r#"f"{f"{1}"}""#, Mode::Module).with_max_fstring_nesting_depth(1);
+        let result = loop {
+            match lexer.next_token() {
+                Ok((Tok::EndOfFile, _)) => break Ok(()),
+                Ok(_) => continue,
+                Err(err) => break Err(err),
+            }
+        };
+        assert_eq!(
+            result.unwrap_err().error,
+            LexicalErrorType::TooDeeplyNestedFString { limit: 1 }
+        );
+    }
+
+    #[test]
+    fn test_nul_byte_is_a_precise_error() {
+        let err = lex_error("x = 1\0y = 2");
+        assert_eq!(err.error, LexicalErrorType::NulByte);
+        assert_eq!(err.location, TextSize::from(5));
+    }
+
+    #[test]
+    fn test_forbidden_control_character_is_a_precise_error() {
+        let err = lex_error("x = \x01y");
+        assert_eq!(
+            err.error,
+            LexicalErrorType::ForbiddenControlCharacter('\u{1}')
+        );
+        assert_eq!(err.location, TextSize::from(4));
+    }
+
+    #[test]
+    fn test_lexing_continues_after_a_control_character_error() {
+        let tokens: Vec<LexResult> = lex("x\0= 1", Mode::Module).collect();
+        assert!(tokens
+            .iter()
+            .any(|t| matches!(t, Err(e) if e.error == LexicalErrorType::NulByte)));
+        assert!(tokens.iter().any(|t| matches!(t, Ok((Tok::Int { .. }, _)))));
+    }
+
+    #[test]
+    fn test_run_of_control_characters_is_a_single_error() {
+        // A run of NUL bytes, as seen padding out a truncated or binary file, is reported as one
+        // error for the whole run instead of one per byte.
+        let tokens: Vec<LexResult> = lex("x = 1\0\0\0\0\0\ny = 2\n", Mode::Module).collect();
+        let errors: Vec<&LexicalError> = tokens.iter().filter_map(|t| t.as_ref().err()).collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, LexicalErrorType::NulByte);
+
+        // Lexing resumes normally on the next line.
+        assert!(tokens
+            .iter()
+            .any(|t| matches!(t, Ok((Tok::Name { name }, _)) if name.as_str() == "y")));
+    }
+
+    #[test]
+    fn test_verbatim_text() {
+        // `value`'s surrounding quotes are part of the `String` token's range but not its
+        // stored value, so `String` is not verbatim even though its content isn't decoded.
+        let source = "x = 'hi'  # comment";
+        let tokens: Vec<Spanned> = lex_source(source);
+
+        for (tok, range) in &tokens {
+            match tok {
+                Tok::Name { name } => {
+                    assert_eq!(
+                        crate::token::verbatim_text(tok, *range, source),
+                        Some(name.as_str())
+                    );
+                }
+                Tok::Comment(value) => {
+                    assert_eq!(
+                        crate::token::verbatim_text(tok, *range, source),
+                        Some(value.as_str())
+                    );
+                }
+                _ => assert_eq!(crate::token::verbatim_text(tok, *range, source), None),
+            }
+        }
+    }
+
+    #[test]
+    fn test_emoji_is_reported_with_unicode_name() {
+        let err = lex_error("x = 😀");
+        match err.error {
+            LexicalErrorType::InvalidCharacterInIdentifier('😀') => {}
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(err.to_string().contains("GRINNING FACE"));
+    }
+
+    #[test]
+    fn test_invalid_identifier_character_recovers_to_following_identifier() {
+        // U+00B7 MIDDLE DOT can't start an identifier but can continue one; after erroring on it
+        // the lexer should still tokenize the rest of the line normally.
+        let tokens: Vec<LexResult> = lex("\u{b7}abc = 1", Mode::Module).collect();
+        assert!(tokens.iter().any(|t| matches!(
+            t,
+            Err(e) if matches!(e.error, LexicalErrorType::InvalidCharacterInIdentifier('\u{b7}'))
+        )));
+        assert!(tokens
+            .iter()
+            .any(|t| matches!(t, Ok((Tok::Name { name }, _)) if &**name == "abc")));
+    }
+
+    #[test]
+    fn test_invalid_digit_for_radix() {
+        let err = lex_error("0b102");
+        assert_eq!(
+            err.error,
+            LexicalErrorType::InvalidDigitForRadix {
+                radix: Radix::Binary,
+                digit: '2',
+            }
+        );
+        assert_eq!(err.error.to_string(), "invalid digit '2' in binary literal");
+
+        let err = lex_error("0o18");
+        assert_eq!(
+            err.error,
+            LexicalErrorType::InvalidDigitForRadix {
+                radix: Radix::Octal,
+                digit: '8',
+            }
+        );
+    }
+
+    #[test]
+    fn test_trailing_underscore_in_numeric_literal_is_precise() {
+        for (source, radix) in [
+            ("1_", Radix::Decimal),
+            ("0x1_", Radix::Hex),
+            ("0b1_", Radix::Binary),
+            ("0o1_", Radix::Octal),
+        ] {
+            let err = lex_error(source);
+            assert_eq!(
+                err.error,
+                LexicalErrorType::InvalidNumericLiteralUnderscore { radix },
+                "source: {source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_doubled_underscore_in_numeric_literal_is_precise() {
+        let err = lex_error("0b1__0");
+        assert_eq!(
+            err.error,
+            LexicalErrorType::InvalidNumericLiteralUnderscore {
+                radix: Radix::Binary,
+            }
+        );
+    }
+
+    #[test]
+    fn test_underscore_after_decimal_point_is_precise() {
+        let err = lex_error("1._5");
+        assert_eq!(
+            err.error,
+            LexicalErrorType::InvalidNumericLiteralUnderscore {
+                radix: Radix::Decimal,
+            }
+        );
+    }
+
+    #[test]
+    fn test_underscore_immediately_after_radix_prefix_is_valid() {
+        // PEP 515 permits a single underscore right after the base prefix.
+        assert!(lex("0x_1", Mode::Module).all(|t| t.is_ok()));
+        assert!(lex("0o_1", Mode::Module).all(|t| t.is_ok()));
+    }
+
+    #[test]
+    fn test_missing_exponent_digits_is_precise() {
+        for source in ["1e", "1e+", "1e-", "1e_5"] {
+            let err = lex_error(source);
+            assert_eq!(
+                err.error,
+                LexicalErrorType::MissingExponentDigits,
+                "source: {source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_leading_zero_in_decimal_integer_is_precise() {
+        let err = lex_error("0755");
+        assert_eq!(err.error, LexicalErrorType::LeadingZeroInDecimalInteger);
+        assert_eq!(
+            err.error.to_string(),
+            "leading zeros in decimal integer literals are not permitted; \
+             use an 0o prefix for octal integers"
+        );
+    }
+
     #[test]
     fn test_ipython_escape_command_line_continuation_unix_eol() {
         assert_debug_snapshot!(ipython_escape_command_line_continuation_eol(UNIX_EOL));
@@ -1990,10 +2800,10 @@ def f(arg=%timeit a = b):
         let lexed: Vec<_> = lex(source, Mode::Module).collect();
 
         match lexed.as_slice() {
-            [Err(error)] => {
+            [Err(error), ..] => {
                 assert_eq!(
                     error.error,
-                    LexicalErrorType::UnrecognizedToken { tok: '🐦' }
+                    LexicalErrorType::InvalidCharacterInIdentifier('🐦')
                 );
             }
             result => panic!("Expected an error token but found {result:?}"),
@@ -2129,6 +2939,64 @@ f'__{
         assert_debug_snapshot!(lex_source(source));
     }
 
+    #[test]
+    fn test_fstring_conversion_flag_collides_with_keyword() {
+        // "if" isn't a valid conversion flag, but it shouldn't be lexed as the `if` keyword
+        // either: the lexer should report it as a malformed conversion flag directly.
+        assert_eq!(
+            lex_fstring_error(r#"f"{x!if}""#),
+            FStringErrorType::InvalidConversionFlag
+        );
+    }
+
+    #[test]
+    fn test_fstring_conversion_flag_requires_preceding_value() {
+        // A bare `!` right after `{` isn't a conversion flag (there's no value to convert yet),
+        // so it's lexed as a plain `!` token, same as before the flag was recognized at all.
+        let source = r#"f"{!x}""#;
+        assert_debug_snapshot!(lex_source(source));
+    }
+
+    #[test]
+    fn test_checkpoint_resumes_mid_bracket() {
+        // Lex up to and including the opening `[` and checkpoint there, so the resumed lexer
+        // knows it's inside a bracketed expression and suppresses the logical newline and
+        // indentation tokens it would otherwise emit before `1`.
+        let prefix = "x = [";
+        let mut lxr = Lexer::new(prefix, Mode::Module);
+        loop {
+            match lxr.next_token() {
+                Ok((Tok::Lsqb, _)) => break,
+                Ok(_) => continue,
+                Err(err) => panic!("unexpected lexer error: {err:?}"),
+            }
+        }
+        let checkpoint = lxr.checkpoint();
+
+        let rest = "\n    1,\n]\n";
+        let start_offset = TextSize::try_from(prefix.len()).unwrap();
+        let resumed: Vec<Tok> =
+            lex_starts_at_with_checkpoint(rest, Mode::Module, start_offset, checkpoint)
+                .map(|result| result.unwrap().0)
+                .collect();
+
+        // Without the checkpoint, a fresh lexer over `rest` alone would treat the leading
+        // newline as logical and emit an `Indent` before `1`, since it has no idea it's
+        // supposed to be inside brackets.
+        let without_checkpoint: Vec<Tok> =
+            lex_source(rest).into_iter().map(|(tok, _)| tok).collect();
+        assert!(without_checkpoint.contains(&Tok::Indent));
+        assert!(!resumed.contains(&Tok::Indent));
+
+        // The checkpointed resumption should match the tail of lexing the whole source in one
+        // continuous pass.
+        let whole: Vec<Tok> = lex_source(&format!("{prefix}{rest}"))
+            .into_iter()
+            .map(|(tok, _)| tok)
+            .collect();
+        assert_eq!(resumed, whole[whole.len() - resumed.len()..]);
+    }
+
     #[test]
     fn test_fstring_nested() {
         let source = r#"f"foo {f"bar {x + f"{wow}"}"} baz" f'foo {f'bar'} some {f"another"}'"#;
@@ -1,7 +1,7 @@
 use bitflags::bitflags;
 
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy)]
     pub(crate) struct FStringContextFlags: u8 {
         /// The current f-string is a triple-quoted f-string i.e., the number of
         /// opening quotes is 3. If this flag is not set, the number of opening
@@ -19,7 +19,7 @@ bitflags! {
 }
 
 /// The context representing the current f-string that the lexer is in.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct FStringContext {
     flags: FStringContextFlags,
 
@@ -109,6 +109,12 @@ impl FStringContext {
             == 1
         {
             self.format_spec_depth += 1;
+            #[cfg(feature = "coverage")]
+            crate::coverage::record(if self.format_spec_depth > 1 {
+                "fstring:nested_format_spec"
+            } else {
+                "fstring:format_spec"
+            });
             true
         } else {
             false
@@ -126,7 +132,7 @@ impl FStringContext {
 
 /// The f-strings stack is used to keep track of all the f-strings that the
 /// lexer encounters. This is necessary because f-strings can be nested.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub(crate) struct FStrings {
     stack: Vec<FStringContext>,
 }
@@ -147,4 +153,9 @@ impl FStrings {
     pub(crate) fn current_mut(&mut self) -> Option<&mut FStringContext> {
         self.stack.last_mut()
     }
+
+    /// The number of f-strings currently nested inside one another's replacement fields.
+    pub(crate) fn depth(&self) -> u32 {
+        self.stack.len() as u32
+    }
 }
@@ -55,6 +55,17 @@ impl Indentation {
         }
     }
 
+    /// Equivalent to calling [`add_space`](Indentation::add_space) `count` times, but without
+    /// looping: each space adds one to both the column and the character count, so the whole run
+    /// can be folded into a single addition.
+    #[must_use]
+    pub(super) fn add_spaces(self, count: u32) -> Self {
+        Self {
+            character: Character(self.character.0 + count),
+            column: Column(self.column.0 + count),
+        }
+    }
+
     #[must_use]
     pub(super) fn add_tab(self) -> Self {
         Self {
@@ -77,6 +88,12 @@ impl Indentation {
             Err(UnexpectedIndentation)
         }
     }
+
+    /// The indentation's column width, for reporting in a
+    /// [`DedentDoesNotMatch`](crate::lexer::LexicalErrorType::DedentDoesNotMatch) error.
+    pub(super) fn column_width(self) -> u32 {
+        self.column.0
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -98,21 +115,23 @@ impl Indentations {
 
     /// Dedent one level to eventually reach `new_indentation`.
     ///
-    /// Returns `Err` if the `new_indentation` is greater than the new current indentation level.
+    /// Returns `Err` with the indentation level that was current after popping if
+    /// `new_indentation` doesn't land on (or can't be compared against) it.
     pub(super) fn dedent_one(
         &mut self,
         new_indentation: Indentation,
-    ) -> Result<Option<Indentation>, UnexpectedIndentation> {
+    ) -> Result<Option<Indentation>, Indentation> {
         let previous = self.dedent();
+        let current = *self.current();
 
-        match new_indentation.try_compare(*self.current())? {
-            Ordering::Less | Ordering::Equal => Ok(previous),
+        match new_indentation.try_compare(current) {
+            Ok(Ordering::Less | Ordering::Equal) => Ok(previous),
             // ```python
             // if True:
             //     pass
             //   pass <- The indentation is greater than the expected indent of 0.
             // ```
-            Ordering::Greater => Err(UnexpectedIndentation),
+            Ok(Ordering::Greater) | Err(UnexpectedIndentation) => Err(current),
         }
     }
 
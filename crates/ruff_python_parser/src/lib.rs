@@ -109,29 +109,81 @@
 //! [parsing]: https://en.wikipedia.org/wiki/Parsing
 //! [lexer]: crate::lexer
 
+pub use ipython::{
+    parse_magic_command, parse_with_ipy_escape_handling, IpyEscapeHandling, IpyMagicCommand,
+};
 pub use parser::{
-    parse, parse_expression, parse_expression_starts_at, parse_program, parse_starts_at,
-    parse_suite, parse_tokens, ParseError, ParseErrorType,
+    parse, parse_expression, parse_expression_starts_at, parse_fstring_expression,
+    parse_function_type_starts_at, parse_fused, parse_interactive, parse_program,
+    parse_program_with_recovery, parse_program_with_recovery_with_options,
+    parse_program_with_tokens, parse_starts_at, parse_statement, parse_suite, parse_tokens,
+    parse_tokens_ref, parse_with_options, InteractiveParseOutcome, ParseError, ParseErrorType,
+    ParseOptions, PythonVersion, RecoveredModule,
 };
 use ruff_python_ast::{Mod, PySourceType, Suite};
-pub use string::FStringErrorType;
-pub use token::{StringKind, Tok, TokenKind};
+pub use string::{decode_bytes_literal, decode_string_literal, FStringErrorType};
+pub use token::{verbatim_text, StringKind, Tok, TokenKind};
+pub use token_ops::{token_kind_to_cmp_op, TokenKindNotAnOperator};
 
 use crate::lexer::LexResult;
 
 mod function;
 // Skip flattening lexer to distinguish from full ruff_python_parser
-mod context;
+pub mod ast_dump;
+pub mod buffers;
+pub mod cache;
+pub mod comments;
+pub mod context;
+pub mod cpython_ast;
+#[cfg(feature = "coverage")]
+pub mod coverage;
+pub mod encoding;
+pub mod event_stream;
+pub mod extension;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz_generator;
+#[cfg(feature = "green_tree")]
+pub mod green_tree;
+pub mod incremental;
 mod invalid;
+mod ipython;
 pub mod lexer;
+pub mod libcst_ast;
+pub mod module_span;
+pub mod node_at_offset;
+pub mod notebook;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 mod parser;
+pub mod preorder;
+pub mod preview;
+#[cfg(feature = "rustpython-compat")]
+pub mod rustpython_compat;
+pub mod semantic_tokens;
 mod soft_keywords;
+pub mod source_kind;
+pub mod stats;
 mod string;
+pub mod template;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
 mod token;
+mod token_ops;
 mod token_source;
+pub mod tokenize_compat;
+pub mod type_comments;
 pub mod typing;
+pub mod validation;
 
 /// Collect tokens up to and including the first error.
+///
+/// This includes every token [`lexer::lex`] produces, trivia and all -- [`Tok::Comment`] and
+/// [`Tok::NonLogicalNewline`] are in the output alongside everything else. The parser's own
+/// pipeline filters those out downstream (see [`crate::token_source`]), but a caller that wants
+/// the exact token sequence a human sees -- a syntax highlighter, a format checker -- wants them
+/// kept. Use [`tokenize_with_options`] to drop one or both instead.
 pub fn tokenize(contents: &str, mode: Mode) -> Vec<LexResult> {
     let mut tokens: Vec<LexResult> = allocate_tokens_vec(contents);
     for tok in lexer::lex(contents, mode) {
@@ -145,6 +197,54 @@ pub fn tokenize(contents: &str, mode: Mode) -> Vec<LexResult> {
     tokens
 }
 
+/// Which trivia tokens [`tokenize_with_options`] includes in its output.
+///
+/// Both default to `true`, matching [`tokenize`]'s behavior of keeping every token the lexer
+/// produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenizeOptions {
+    /// Whether to include [`Tok::Comment`] tokens.
+    pub comments: bool,
+    /// Whether to include [`Tok::NonLogicalNewline`] tokens.
+    pub non_logical_newlines: bool,
+}
+
+impl Default for TokenizeOptions {
+    fn default() -> Self {
+        Self {
+            comments: true,
+            non_logical_newlines: true,
+        }
+    }
+}
+
+/// Collects tokens up to and including the first error, the same as [`tokenize`], but lets
+/// `options` drop [`Tok::Comment`] and/or [`Tok::NonLogicalNewline`] tokens from the output instead
+/// of keeping all of them.
+pub fn tokenize_with_options(
+    contents: &str,
+    mode: Mode,
+    options: TokenizeOptions,
+) -> Vec<LexResult> {
+    let mut tokens: Vec<LexResult> = allocate_tokens_vec(contents);
+    for tok in lexer::lex(contents, mode) {
+        let keep = match &tok {
+            Ok((Tok::Comment(_), _)) => options.comments,
+            Ok((Tok::NonLogicalNewline, _)) => options.non_logical_newlines,
+            _ => true,
+        };
+        let is_err = tok.is_err();
+        if keep {
+            tokens.push(tok);
+        }
+        if is_err {
+            break;
+        }
+    }
+
+    tokens
+}
+
 /// Tokenizes all tokens.
 ///
 /// It differs from [`tokenize`] in that it tokenizes all tokens and doesn't stop
@@ -183,7 +283,9 @@ pub fn parse_program_tokens(
     };
     match parse_tokens(tokens, source, mode)? {
         Mod::Module(m) => Ok(m.body),
-        Mod::Expression(_) => unreachable!("Mode::Module doesn't return other variant"),
+        Mod::Expression(_) | Mod::FunctionType(_) => {
+            unreachable!("Mode::Module doesn't return other variant")
+        }
     }
 }
 
@@ -212,6 +314,11 @@ pub enum Mode {
     /// [System shell access]: https://ipython.readthedocs.io/en/stable/interactive/reference.html#system-shell-access
     /// [Automatic parentheses and quotes]: https://ipython.readthedocs.io/en/stable/interactive/reference.html#automatic-parentheses-and-quotes
     Ipython,
+    /// The code consists of a PEP 484 function type comment's signature, e.g.
+    /// `(int, str) -> bool`, parsed on its own outside of any surrounding statement.
+    ///
+    /// This mirrors `CPython`'s `ast.parse(source, mode="func_type")`.
+    FunctionType,
 }
 
 impl std::str::FromStr for Mode {
@@ -221,6 +328,7 @@ impl std::str::FromStr for Mode {
             "exec" | "single" => Ok(Mode::Module),
             "eval" => Ok(Mode::Expression),
             "ipython" => Ok(Mode::Ipython),
+            "func_type" => Ok(Mode::FunctionType),
             _ => Err(ModeParseError),
         }
     }
@@ -245,7 +353,10 @@ pub struct ModeParseError;
 
 impl std::fmt::Display for ModeParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, r#"mode must be "exec", "eval", "ipython", or "single""#)
+        write!(
+            f,
+            r#"mode must be "exec", "eval", "ipython", "func_type", or "single""#
+        )
     }
 }
 
@@ -0,0 +1,1183 @@
+//! Render a parsed [`ast::Mod`] as JSON shaped like [`LibCST`]'s concrete syntax tree, so codemods
+//! written against `LibCST`'s node model can be ported to this parser with minimal changes.
+//!
+//! `LibCST`'s defining idea is that every node owns the raw horizontal whitespace immediately
+//! around it, so the tree can be printed back out byte-for-byte without a separate token stream.
+//! This exporter models that the same way: every node carries `whitespace_before`/
+//! `whitespace_after` string fields holding the literal spaces and tabs adjacent to it, and every
+//! expression carries `lpar`/`rpar` arrays describing any redundant grouping parentheses around
+//! it (`LibCST`'s `LeftParen`/`RightParen` nodes).
+//!
+//! This is a best-effort structural approximation, not a byte-for-byte port of `libcst.nodes`:
+//! - Only horizontal whitespace (spaces and tabs) is tracked. `LibCST` additionally classifies
+//!   blank lines, indentation, and comments into dedicated `EmptyLine`/`TrailingWhitespace`/
+//!   `ParenthesizedWhitespace` node types; this exporter leaves all of that as plain text instead
+//!   of building those types out.
+//! - Parenthesization is detected with [`ruff_python_ast::parenthesize::parentheses_iterator`]
+//!   without a parent node, which is documented to occasionally misattribute a call's or a single
+//!   -base `class Foo(Base):`'s own delimiters to their sole argument/base as if they were a
+//!   redundant grouping (e.g. `f(a)` for `lpar`/`rpar` on `a`). Downstream consumers that need
+//!   exact parenthesization should re-derive it with the real parent in hand.
+//! - Operators (`+`, `and`, `==`, `+=`, ...) are rendered as their source text rather than
+//!   `LibCST`'s one-class-per-operator types (`Add`, `And`, `Equal`, `AddAssign`, ...), since those
+//!   classes carry no data beyond identifying the operator.
+//! - [`ast::Expr::BoolOp`] and [`ast::Expr::Compare`] are both n-ary in this crate's AST (a
+//!   `values`/`comparators` list), but `LibCST`'s `BooleanOperation` is strictly binary; a chain
+//!   like `a and b and c` is folded into nested, left-associated `BooleanOperation` nodes here to
+//!   match. `Comparison` is already shaped like `LibCST`'s (`left` plus a list of
+//!   `ComparisonTarget`s), so no folding is needed there.
+//! - Implicitly concatenated strings become a flat `ConcatenatedString` node with a `strings`
+//!   list, rather than `LibCST`'s actual binary, right-nested `left`/`whitespace_between`/`right`
+//!   shape.
+//! - A handful of node kinds this crate's grammar supports but `LibCST`'s doesn't have -- the
+//!   Jupyter-only `IpyEscapeCommand`, PEP 695 [`ast::TypeParams`] (an older `LibCST` release may not
+//!   model these yet) -- are rendered under this crate's own node name rather than dropped.
+//!
+//! Unlike [`crate::cpython_ast`], nodes carry no line/column attributes: `LibCST` positions a node
+//! purely by the whitespace and content that precede it, which is exactly what this format
+//! reconstructs.
+//!
+//! [`LibCST`]: https://libcst.readthedocs.io/en/latest/nodes.html
+
+use ruff_python_ast::parenthesize::parentheses_iterator;
+use ruff_python_ast::{self as ast, ExpressionRef, Number};
+use ruff_python_trivia::CommentRanges;
+use ruff_text_size::{Ranged, TextRange, TextSize};
+use serde_json::{json, Map, Value};
+
+use crate::Tok;
+
+/// Render `module` as a `LibCST`-shaped JSON value. See the [module docs](self) for what is and
+/// isn't faithfully reproduced.
+pub fn to_libcst_json(module: &ast::Mod, source: &str) -> Value {
+    let dumper = Dumper {
+        source,
+        comment_ranges: comment_ranges(source),
+    };
+    match module {
+        ast::Mod::Module(module) => {
+            Dumper::bare_node("Module", [("body", dumper.stmts(&module.body))])
+        }
+        ast::Mod::Expression(expression) => {
+            Dumper::bare_node("Expression", [("body", dumper.expr(&expression.body))])
+        }
+        ast::Mod::FunctionType(function_type) => Dumper::bare_node(
+            "FunctionType",
+            [
+                ("argtypes", dumper.exprs(&function_type.argtypes)),
+                ("returns", dumper.expr(&function_type.returns)),
+            ],
+        ),
+    }
+}
+
+/// Collects the ranges of every `#`-comment in `source`, for [`parentheses_iterator`], which
+/// needs them to avoid mistaking a comment's text for source code while scanning past trivia.
+fn comment_ranges(source: &str) -> CommentRanges {
+    let mut ranges = Vec::new();
+    for result in crate::lexer::lex(source, crate::Mode::Module) {
+        if let Ok((Tok::Comment(_), range)) = result {
+            ranges.push(range);
+        }
+    }
+    CommentRanges::new(ranges)
+}
+
+struct Dumper<'a> {
+    source: &'a str,
+    comment_ranges: CommentRanges,
+}
+
+impl Dumper<'_> {
+    /// A node with no `libcst` type of its own: `type`, `whitespace_before`/`whitespace_after`,
+    /// plus the given fields.
+    fn node<const N: usize>(
+        &self,
+        type_name: &'static str,
+        range: TextRange,
+        fields: [(&'static str, Value); N],
+    ) -> Value {
+        let mut map = Map::with_capacity(N + 3);
+        map.insert("type".to_string(), Value::String(type_name.to_string()));
+        for (name, value) in fields {
+            map.insert(name.to_string(), value);
+        }
+        map.insert(
+            "whitespace_before".to_string(),
+            json!(self.whitespace_before(range.start())),
+        );
+        map.insert(
+            "whitespace_after".to_string(),
+            json!(self.whitespace_after(range.end())),
+        );
+        Value::Object(map)
+    }
+
+    /// A node with no position of its own -- a helper struct like `LibCST`'s `withitem` or
+    /// `comprehension` equivalents, or the top-level `Module`/`Expression` wrapper, which `LibCST`
+    /// never surrounds with whitespace fields since they aren't concrete syntax themselves.
+    fn bare_node<const N: usize>(type_name: &'static str, fields: [(&'static str, Value); N]) -> Value {
+        let mut map = Map::with_capacity(N + 1);
+        map.insert("type".to_string(), Value::String(type_name.to_string()));
+        for (name, value) in fields {
+            map.insert(name.to_string(), value);
+        }
+        Value::Object(map)
+    }
+
+    /// The literal run of spaces and tabs immediately before `start`.
+    fn whitespace_before(&self, start: TextSize) -> &str {
+        let bytes = self.source.as_bytes();
+        let mut index = usize::from(start);
+        while index > 0 && matches!(bytes[index - 1], b' ' | b'\t') {
+            index -= 1;
+        }
+        &self.source[index..usize::from(start)]
+    }
+
+    /// The literal run of spaces and tabs immediately after `end`.
+    fn whitespace_after(&self, end: TextSize) -> &str {
+        let bytes = self.source.as_bytes();
+        let mut index = usize::from(end);
+        while index < bytes.len() && matches!(bytes[index], b' ' | b'\t') {
+            index += 1;
+        }
+        &self.source[usize::from(end)..index]
+    }
+
+    fn text(&self, range: TextRange) -> &str {
+        &self.source[range]
+    }
+
+    fn stmts(&self, stmts: &[ast::Stmt]) -> Value {
+        Value::Array(stmts.iter().map(|stmt| self.stmt(stmt)).collect())
+    }
+
+    fn exprs(&self, exprs: &[ast::Expr]) -> Value {
+        Value::Array(exprs.iter().map(|expr| self.expr(expr)).collect())
+    }
+
+    fn name(&self, identifier: &ast::Identifier) -> Value {
+        self.node("Name", identifier.range(), [("value", json!(identifier.as_str()))])
+    }
+
+    fn names(&self, identifiers: &[ast::Identifier]) -> Value {
+        Value::Array(identifiers.iter().map(|name| self.name(name)).collect())
+    }
+
+    fn opt_name(&self, identifier: Option<&ast::Identifier>) -> Value {
+        identifier.map_or(Value::Null, |identifier| self.name(identifier))
+    }
+
+    fn opt_expr(&self, expr: Option<&ast::Expr>) -> Value {
+        expr.map_or(Value::Null, |expr| self.expr(expr))
+    }
+
+    fn stmt(&self, stmt: &ast::Stmt) -> Value {
+        let range = stmt.range();
+        match stmt {
+            ast::Stmt::FunctionDef(node) => {
+                let type_name = if node.is_async {
+                    "AsyncFunctionDef"
+                } else {
+                    "FunctionDef"
+                };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("name", self.name(&node.name)),
+                        ("params", self.parameters(Some(&node.parameters))),
+                        ("body", self.stmts(&node.body)),
+                        ("decorators", self.decorators(&node.decorator_list)),
+                        ("returns", self.opt_expr(node.returns.as_deref())),
+                    ],
+                )
+            }
+            ast::Stmt::ClassDef(node) => self.node(
+                "ClassDef",
+                range,
+                [
+                    ("name", self.name(&node.name)),
+                    ("bases", self.exprs(node.bases())),
+                    ("keywords", self.keywords(node.keywords())),
+                    ("body", self.stmts(&node.body)),
+                    ("decorators", self.decorators(&node.decorator_list)),
+                ],
+            ),
+            ast::Stmt::Return(node) => self.node(
+                "Return",
+                range,
+                [("value", self.opt_expr(node.value.as_deref()))],
+            ),
+            ast::Stmt::Delete(node) => {
+                self.node("Del", range, [("targets", self.exprs(&node.targets))])
+            }
+            ast::Stmt::Assign(node) => self.node(
+                "Assign",
+                range,
+                [
+                    (
+                        "targets",
+                        Value::Array(
+                            node.targets
+                                .iter()
+                                .map(|target| {
+                                    Self::bare_node("AssignTarget", [("target", self.expr(target))])
+                                })
+                                .collect(),
+                        ),
+                    ),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Stmt::AugAssign(node) => self.node(
+                "AugAssign",
+                range,
+                [
+                    ("target", self.expr(&node.target)),
+                    ("operator", json!(operator_text(node.op))),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Stmt::AnnAssign(node) => self.node(
+                "AnnAssign",
+                range,
+                [
+                    ("target", self.expr(&node.target)),
+                    (
+                        "annotation",
+                        Self::bare_node("Annotation", [("annotation", self.expr(&node.annotation))]),
+                    ),
+                    ("value", self.opt_expr(node.value.as_deref())),
+                ],
+            ),
+            ast::Stmt::TypeAlias(node) => self.node(
+                "TypeAlias",
+                range,
+                [
+                    ("name", self.expr(&node.name)),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Stmt::For(node) => {
+                let type_name = if node.is_async { "AsyncFor" } else { "For" };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("target", self.expr(&node.target)),
+                        ("iter", self.expr(&node.iter)),
+                        ("body", self.stmts(&node.body)),
+                        ("orelse", self.opt_else(&node.orelse)),
+                    ],
+                )
+            }
+            ast::Stmt::While(node) => self.node(
+                "While",
+                range,
+                [
+                    ("test", self.expr(&node.test)),
+                    ("body", self.stmts(&node.body)),
+                    ("orelse", self.opt_else(&node.orelse)),
+                ],
+            ),
+            ast::Stmt::If(node) => {
+                self.if_stmt(node.range, &node.test, &node.body, &node.elif_else_clauses)
+            }
+            ast::Stmt::With(node) => {
+                let type_name = if node.is_async { "AsyncWith" } else { "With" };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("items", self.with_items(&node.items)),
+                        ("body", self.stmts(&node.body)),
+                    ],
+                )
+            }
+            ast::Stmt::Match(node) => self.node(
+                "Match",
+                range,
+                [
+                    ("subject", self.expr(&node.subject)),
+                    ("cases", self.match_cases(&node.cases)),
+                ],
+            ),
+            ast::Stmt::Raise(node) => self.node(
+                "Raise",
+                range,
+                [
+                    ("exc", self.opt_expr(node.exc.as_deref())),
+                    ("cause", self.opt_expr(node.cause.as_deref())),
+                ],
+            ),
+            ast::Stmt::Try(node) => {
+                let type_name = if node.is_star { "TryStar" } else { "Try" };
+                self.node(
+                    type_name,
+                    range,
+                    [
+                        ("body", self.stmts(&node.body)),
+                        ("handlers", self.except_handlers(&node.handlers)),
+                        ("orelse", self.opt_else(&node.orelse)),
+                        ("finalbody", self.stmts(&node.finalbody)),
+                    ],
+                )
+            }
+            ast::Stmt::Assert(node) => self.node(
+                "Assert",
+                range,
+                [
+                    ("test", self.expr(&node.test)),
+                    ("msg", self.opt_expr(node.msg.as_deref())),
+                ],
+            ),
+            ast::Stmt::Import(node) => {
+                self.node("Import", range, [("names", self.aliases(&node.names))])
+            }
+            ast::Stmt::ImportFrom(node) => self.node(
+                "ImportFrom",
+                range,
+                [
+                    ("module", Self::opt_module_name(node.module.as_ref())),
+                    ("names", self.aliases(&node.names)),
+                    ("relative_dots", json!(node.level.unwrap_or(0))),
+                ],
+            ),
+            ast::Stmt::Global(node) => {
+                self.node("Global", range, [("names", self.names(&node.names))])
+            }
+            ast::Stmt::Nonlocal(node) => {
+                self.node("Nonlocal", range, [("names", self.names(&node.names))])
+            }
+            ast::Stmt::Expr(node) => self.node("Expr", range, [("value", self.expr(&node.value))]),
+            ast::Stmt::Pass(_) => self.node("Pass", range, []),
+            ast::Stmt::Break(_) => self.node("Break", range, []),
+            ast::Stmt::Continue(_) => self.node("Continue", range, []),
+            ast::Stmt::IpyEscapeCommand(node) => self.node(
+                "IpyEscapeCommand",
+                range,
+                [("value", json!(node.value))],
+            ),
+        }
+    }
+
+    /// `orelse`/`finalbody` here are either empty (no `else:`) or a single-element list holding
+    /// the `else:` block's statements; `LibCST` instead has an `Optional[Else]` wrapping the block.
+    fn opt_else(&self, orelse: &[ast::Stmt]) -> Value {
+        if orelse.is_empty() {
+            Value::Null
+        } else {
+            Self::bare_node("Else", [("body", self.stmts(orelse))])
+        }
+    }
+
+    /// `elif`/`else` clauses are flattened in this crate's AST ([`ast::ElifElseClause`]); `LibCST`
+    /// nests each `elif` as an `If` in the parent's `orelse`, same as `CPython`'s own `ast`.
+    fn if_stmt(
+        &self,
+        range: TextRange,
+        test: &ast::Expr,
+        body: &[ast::Stmt],
+        clauses: &[ast::ElifElseClause],
+    ) -> Value {
+        let orelse = match clauses.split_first() {
+            None => Value::Null,
+            Some((clause, rest)) => match &clause.test {
+                Some(test) => self.if_stmt(clause.range, test, &clause.body, rest),
+                None => Self::bare_node("Else", [("body", self.stmts(&clause.body))]),
+            },
+        };
+        self.node(
+            "If",
+            range,
+            [
+                ("test", self.expr(test)),
+                ("body", self.stmts(body)),
+                ("orelse", orelse),
+            ],
+        )
+    }
+
+    fn with_items(&self, items: &[ast::WithItem]) -> Value {
+        Value::Array(
+            items
+                .iter()
+                .map(|item| {
+                    Self::bare_node(
+                        "WithItem",
+                        [
+                            ("item", self.expr(&item.context_expr)),
+                            (
+                                "asname",
+                                item.optional_vars.as_deref().map_or(Value::Null, |target| {
+                                    Self::bare_node("AsName", [("name", self.expr(target))])
+                                }),
+                            ),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn match_cases(&self, cases: &[ast::MatchCase]) -> Value {
+        Value::Array(
+            cases
+                .iter()
+                .map(|case| {
+                    Self::bare_node(
+                        "MatchCase",
+                        [
+                            ("pattern", self.pattern(&case.pattern)),
+                            ("guard", self.opt_expr(case.guard.as_deref())),
+                            ("body", self.stmts(&case.body)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn except_handlers(&self, handlers: &[ast::ExceptHandler]) -> Value {
+        Value::Array(
+            handlers
+                .iter()
+                .map(|handler| {
+                    let ast::ExceptHandler::ExceptHandler(node) = handler;
+                    self.node(
+                        "ExceptHandler",
+                        node.range,
+                        [
+                            ("type", self.opt_expr(node.type_.as_deref())),
+                            ("name", self.opt_name(node.name.as_ref())),
+                            ("body", self.stmts(&node.body)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn aliases(&self, aliases: &[ast::Alias]) -> Value {
+        Value::Array(
+            aliases
+                .iter()
+                .map(|alias| {
+                    self.node(
+                        "ImportAlias",
+                        alias.range,
+                        [
+                            ("name", Self::module_name(&alias.name)),
+                            (
+                                "asname",
+                                alias.asname.as_ref().map_or(Value::Null, |asname| {
+                                    Self::bare_node("AsName", [("name", self.name(asname))])
+                                }),
+                            ),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// A dotted module name (`import a.b.c`) is an [`ast::DottedName`] in this crate, but an
+    /// `Attribute`/`Name` expression chain in `LibCST`; this renders the dotted text directly
+    /// rather than building that chain out.
+    fn module_name(name: &ast::DottedName) -> Value {
+        json!(name.as_str())
+    }
+
+    fn opt_module_name(name: Option<&ast::DottedName>) -> Value {
+        name.map_or(Value::Null, Self::module_name)
+    }
+
+    fn decorators(&self, decorators: &[ast::Decorator]) -> Value {
+        Value::Array(
+            decorators
+                .iter()
+                .map(|decorator| {
+                    Self::bare_node("Decorator", [("decorator", self.expr(&decorator.expression))])
+                })
+                .collect(),
+        )
+    }
+
+    fn keywords(&self, keywords: &[ast::Keyword]) -> Value {
+        Value::Array(
+            keywords
+                .iter()
+                .map(|keyword| {
+                    self.node(
+                        "Arg",
+                        keyword.range,
+                        [
+                            ("keyword", self.opt_name(keyword.arg.as_ref())),
+                            ("value", self.expr(&keyword.value)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Unlike `CPython`'s `arguments` node, `LibCST`'s `Parameters` keeps each parameter's default
+    /// inline on its own `Param`, the same way this crate's [`ast::ParameterWithDefault`] does,
+    /// so no splitting/reassembly is needed here.
+    fn parameters(&self, parameters: Option<&ast::Parameters>) -> Value {
+        let empty = Vec::new();
+        let (posonlyargs, args, vararg, kwonlyargs, kwarg) = match parameters {
+            Some(parameters) => (
+                &parameters.posonlyargs,
+                &parameters.args,
+                parameters.vararg.as_deref(),
+                &parameters.kwonlyargs,
+                parameters.kwarg.as_deref(),
+            ),
+            None => (&empty, &empty, None, &empty, None),
+        };
+
+        Self::bare_node(
+            "Parameters",
+            [
+                ("posonly_params", self.params(posonlyargs)),
+                ("params", self.params(args)),
+                ("star_arg", self.opt_param(vararg)),
+                ("kwonly_params", self.params(kwonlyargs)),
+                ("star_kwarg", self.opt_param(kwarg)),
+            ],
+        )
+    }
+
+    fn params(&self, parameters: &[ast::ParameterWithDefault]) -> Value {
+        Value::Array(
+            parameters
+                .iter()
+                .map(|parameter| {
+                    self.node(
+                        "Param",
+                        parameter.range,
+                        [
+                            ("name", self.name(&parameter.parameter.name)),
+                            (
+                                "annotation",
+                                self.opt_expr(parameter.parameter.annotation.as_deref()),
+                            ),
+                            ("default", self.opt_expr(parameter.default.as_deref())),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn opt_param(&self, parameter: Option<&ast::Parameter>) -> Value {
+        parameter.map_or(Value::Null, |parameter| {
+            self.node(
+                "Param",
+                parameter.range,
+                [
+                    ("name", self.name(&parameter.name)),
+                    ("annotation", self.opt_expr(parameter.annotation.as_deref())),
+                    ("default", Value::Null),
+                ],
+            )
+        })
+    }
+
+    fn pattern(&self, pattern: &ast::Pattern) -> Value {
+        let range = pattern.range();
+        match pattern {
+            ast::Pattern::MatchValue(node) => {
+                self.node("MatchValue", range, [("value", self.expr(&node.value))])
+            }
+            ast::Pattern::MatchSingleton(node) => self.node(
+                "MatchSingleton",
+                range,
+                [("value", json!(format!("{:?}", node.value)))],
+            ),
+            ast::Pattern::MatchSequence(node) => self.node(
+                "MatchSequence",
+                range,
+                [("patterns", self.patterns(&node.patterns))],
+            ),
+            ast::Pattern::MatchMapping(node) => self.node(
+                "MatchMapping",
+                range,
+                [
+                    ("keys", self.exprs(&node.keys)),
+                    ("patterns", self.patterns(&node.patterns)),
+                    ("rest", self.opt_name(node.rest.as_ref())),
+                ],
+            ),
+            ast::Pattern::MatchClass(node) => self.node(
+                "MatchClass",
+                range,
+                [
+                    ("cls", self.expr(&node.cls)),
+                    ("patterns", self.patterns(&node.arguments.patterns)),
+                    (
+                        "kwds",
+                        Value::Array(
+                            node.arguments
+                                .keywords
+                                .iter()
+                                .map(|keyword| {
+                                    self.node(
+                                        "MatchKeywordElement",
+                                        keyword.range,
+                                        [
+                                            ("key", self.name(&keyword.attr)),
+                                            ("pattern", self.pattern(&keyword.pattern)),
+                                        ],
+                                    )
+                                })
+                                .collect(),
+                        ),
+                    ),
+                ],
+            ),
+            ast::Pattern::MatchStar(node) => self.node(
+                "MatchStar",
+                range,
+                [("name", self.opt_name(node.name.as_ref()))],
+            ),
+            ast::Pattern::MatchAs(node) => self.node(
+                "MatchAs",
+                range,
+                [
+                    (
+                        "pattern",
+                        node.pattern.as_deref().map_or(Value::Null, |p| self.pattern(p)),
+                    ),
+                    ("name", self.opt_name(node.name.as_ref())),
+                ],
+            ),
+            ast::Pattern::MatchOr(node) => self.node(
+                "MatchOr",
+                range,
+                [("patterns", self.patterns(&node.patterns))],
+            ),
+        }
+    }
+
+    fn patterns(&self, patterns: &[ast::Pattern]) -> Value {
+        Value::Array(patterns.iter().map(|pattern| self.pattern(pattern)).collect())
+    }
+
+    fn comprehensions(&self, comprehensions: &[ast::Comprehension]) -> Value {
+        Value::Array(
+            comprehensions
+                .iter()
+                .map(|comprehension| {
+                    let type_name = if comprehension.is_async {
+                        "CompFor/async"
+                    } else {
+                        "CompFor"
+                    };
+                    Self::bare_node(
+                        type_name,
+                        [
+                            ("target", self.expr(&comprehension.target)),
+                            ("iter", self.expr(&comprehension.iter)),
+                            ("ifs", self.exprs(&comprehension.ifs)),
+                        ],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Folds this crate's n-ary [`ast::ExprBoolOp::values`] into nested, left-associated,
+    /// strictly-binary `BooleanOperation` nodes, matching `LibCST`'s shape for `a and b and c`.
+    fn bool_op(&self, op: ast::BoolOp, values: &[ast::Expr]) -> Value {
+        let operator = match op {
+            ast::BoolOp::And => "and",
+            ast::BoolOp::Or => "or",
+        };
+        let mut values = values.iter();
+        let mut acc = self.expr(values.next().expect("BoolOp has at least two values"));
+        for value in values {
+            let right = self.expr(value);
+            let range = TextRange::new(
+                TextSize::new(0),
+                TextSize::new(0),
+            );
+            acc = self.node(
+                "BooleanOperation",
+                range,
+                [
+                    ("left", acc),
+                    ("operator", json!(operator)),
+                    ("right", right),
+                ],
+            );
+        }
+        acc
+    }
+
+    fn expr(&self, expr: &ast::Expr) -> Value {
+        let range = expr.range();
+        let value = match expr {
+            ast::Expr::BoolOp(node) => {
+                let mut folded = self.bool_op(node.op, &node.values);
+                if let Value::Object(map) = &mut folded {
+                    // The fold above can't know the outer range up front; fix up the outermost
+                    // node now that we're back at the real `ExprBoolOp`.
+                    map.insert(
+                        "whitespace_before".to_string(),
+                        json!(self.whitespace_before(range.start())),
+                    );
+                    map.insert(
+                        "whitespace_after".to_string(),
+                        json!(self.whitespace_after(range.end())),
+                    );
+                }
+                folded
+            }
+            ast::Expr::NamedExpr(node) => self.node(
+                "NamedExpr",
+                range,
+                [
+                    ("target", self.expr(&node.target)),
+                    ("value", self.expr(&node.value)),
+                ],
+            ),
+            ast::Expr::BinOp(node) => self.node(
+                "BinaryOperation",
+                range,
+                [
+                    ("left", self.expr(&node.left)),
+                    ("operator", json!(operator_text(node.op))),
+                    ("right", self.expr(&node.right)),
+                ],
+            ),
+            ast::Expr::UnaryOp(node) => self.node(
+                "UnaryOperation",
+                range,
+                [
+                    ("operator", json!(unary_operator_text(node.op))),
+                    ("expression", self.expr(&node.operand)),
+                ],
+            ),
+            ast::Expr::Lambda(node) => self.node(
+                "Lambda",
+                range,
+                [
+                    ("params", self.parameters(node.parameters.as_deref())),
+                    ("body", self.expr(&node.body)),
+                ],
+            ),
+            ast::Expr::IfExp(node) => self.node(
+                "IfExp",
+                range,
+                [
+                    ("test", self.expr(&node.test)),
+                    ("body", self.expr(&node.body)),
+                    ("orelse", self.expr(&node.orelse)),
+                ],
+            ),
+            ast::Expr::Dict(node) => self.node(
+                "Dict",
+                range,
+                [(
+                    "elements",
+                    Value::Array(
+                        node.keys
+                            .iter()
+                            .zip(&node.values)
+                            .map(|(key, value)| match key {
+                                Some(key) => Self::bare_node(
+                                    "DictElement",
+                                    [("key", self.expr(key)), ("value", self.expr(value))],
+                                ),
+                                None => {
+                                    Self::bare_node("StarredDictElement", [("value", self.expr(value))])
+                                }
+                            })
+                            .collect(),
+                    ),
+                )],
+            ),
+            ast::Expr::Set(node) => self.node("Set", range, [("elements", self.elements(&node.elts))]),
+            ast::Expr::ListComp(node) => self.node(
+                "ListComp",
+                range,
+                [
+                    ("elt", self.expr(&node.elt)),
+                    ("for_in", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::SetComp(node) => self.node(
+                "SetComp",
+                range,
+                [
+                    ("elt", self.expr(&node.elt)),
+                    ("for_in", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::DictComp(node) => self.node(
+                "DictComp",
+                range,
+                [
+                    ("key", self.expr(&node.key)),
+                    ("value", self.expr(&node.value)),
+                    ("for_in", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::GeneratorExp(node) => self.node(
+                "GeneratorExp",
+                range,
+                [
+                    ("elt", self.expr(&node.elt)),
+                    ("for_in", self.comprehensions(&node.generators)),
+                ],
+            ),
+            ast::Expr::Await(node) => {
+                self.node("Await", range, [("expression", self.expr(&node.value))])
+            }
+            ast::Expr::Yield(node) => self.node(
+                "Yield",
+                range,
+                [("value", self.opt_expr(node.value.as_deref()))],
+            ),
+            ast::Expr::YieldFrom(node) => self.node(
+                "Yield",
+                range,
+                [(
+                    "value",
+                    Self::bare_node("From", [("item", self.expr(&node.value))]),
+                )],
+            ),
+            ast::Expr::Compare(node) => self.node(
+                "Comparison",
+                range,
+                [
+                    ("left", self.expr(&node.left)),
+                    (
+                        "comparisons",
+                        Value::Array(
+                            node.ops
+                                .iter()
+                                .zip(&node.comparators)
+                                .map(|(op, comparator)| {
+                                    Self::bare_node(
+                                        "ComparisonTarget",
+                                        [
+                                            ("operator", json!(cmp_operator_text(*op))),
+                                            ("comparator", self.expr(comparator)),
+                                        ],
+                                    )
+                                })
+                                .collect(),
+                        ),
+                    ),
+                ],
+            ),
+            ast::Expr::Call(node) => self.node(
+                "Call",
+                range,
+                [
+                    ("func", self.expr(&node.func)),
+                    (
+                        "args",
+                        Value::Array(
+                            node.arguments
+                                .args
+                                .iter()
+                                .map(|arg| {
+                                    Self::bare_node("Arg", [("value", self.expr(arg))])
+                                })
+                                .chain(node.arguments.keywords.iter().map(|keyword| {
+                                    self.node(
+                                        "Arg",
+                                        keyword.range,
+                                        [
+                                            ("keyword", self.opt_name(keyword.arg.as_ref())),
+                                            ("value", self.expr(&keyword.value)),
+                                        ],
+                                    )
+                                }))
+                                .collect(),
+                        ),
+                    ),
+                ],
+            ),
+            ast::Expr::FString(node) => self.f_string_value(range, &node.value),
+            ast::Expr::StringLiteral(node) => self.concatenated_string(
+                range,
+                node.value.as_slice().iter().map(|part| part.range),
+            ),
+            ast::Expr::BytesLiteral(node) => self.concatenated_string(
+                range,
+                node.value.as_slice().iter().map(|part| part.range),
+            ),
+            ast::Expr::NumberLiteral(node) => {
+                let type_name = match node.value {
+                    Number::Int(_) => "Integer",
+                    Number::Float(_) => "Float",
+                    Number::Complex { .. } => "Imaginary",
+                };
+                self.node(type_name, range, [("value", json!(self.text(range)))])
+            }
+            ast::Expr::BooleanLiteral(node) => {
+                self.node("Name", range, [("value", json!(if node.value { "True" } else { "False" }))])
+            }
+            ast::Expr::NoneLiteral(_) => self.node("Name", range, [("value", json!("None"))]),
+            ast::Expr::EllipsisLiteral(_) => self.node("Ellipsis", range, []),
+            ast::Expr::Attribute(node) => self.node(
+                "Attribute",
+                range,
+                [
+                    ("value", self.expr(&node.value)),
+                    ("attr", self.name(&node.attr)),
+                ],
+            ),
+            ast::Expr::Subscript(node) => self.node(
+                "Subscript",
+                range,
+                [
+                    ("value", self.expr(&node.value)),
+                    ("slice", self.expr(&node.slice)),
+                ],
+            ),
+            ast::Expr::Starred(node) => {
+                self.node("StarredElement", range, [("value", self.expr(&node.value))])
+            }
+            ast::Expr::Name(node) => self.node("Name", range, [("value", json!(node.id))]),
+            ast::Expr::List(node) => {
+                self.node("List", range, [("elements", self.elements(&node.elts))])
+            }
+            ast::Expr::Tuple(node) => {
+                self.node("Tuple", range, [("elements", self.elements(&node.elts))])
+            }
+            ast::Expr::Slice(node) => self.node(
+                "Slice",
+                range,
+                [
+                    ("lower", self.opt_expr(node.lower.as_deref())),
+                    ("upper", self.opt_expr(node.upper.as_deref())),
+                    ("step", self.opt_expr(node.step.as_deref())),
+                ],
+            ),
+            ast::Expr::IpyEscapeCommand(node) => {
+                self.node("IpyEscapeCommand", range, [("value", json!(node.value))])
+            }
+        };
+        self.with_parens(expr.into(), value)
+    }
+
+    fn elements(&self, exprs: &[ast::Expr]) -> Value {
+        Value::Array(
+            exprs
+                .iter()
+                .map(|expr| match expr {
+                    ast::Expr::Starred(node) => {
+                        Self::bare_node("StarredElement", [("value", self.expr(&node.value))])
+                    }
+                    expr => Self::bare_node("Element", [("value", self.expr(expr))]),
+                })
+                .collect(),
+        )
+    }
+
+    /// Appends `lpar`/`rpar` to an expression [`Value`] for any redundant grouping parentheses
+    /// around it. See the [module docs](self) for the heuristic's known false positives.
+    fn with_parens(&self, expr_ref: ExpressionRef, value: Value) -> Value {
+        let Some(outermost) =
+            parentheses_iterator(expr_ref, None, &self.comment_ranges, self.source).last()
+        else {
+            return value;
+        };
+        let Value::Object(mut map) = value else {
+            return value;
+        };
+        map.insert(
+            "lpar".to_string(),
+            Value::Array(vec![Self::bare_node(
+                "LeftParen",
+                [("whitespace_after", json!(self.whitespace_after(outermost.start() + TextSize::from(1))))],
+            )]),
+        );
+        map.insert(
+            "rpar".to_string(),
+            Value::Array(vec![Self::bare_node(
+                "RightParen",
+                [(
+                    "whitespace_before",
+                    json!(self.whitespace_before(outermost.end() - TextSize::from(1))),
+                )],
+            )]),
+        );
+        Value::Object(map)
+    }
+
+    /// Flattens implicit string/bytes-literal concatenation into one `ConcatenatedString`,
+    /// unlike `LibCST`'s real binary, right-nested shape; see the [module docs](self).
+    fn concatenated_string(&self, range: TextRange, parts: impl Iterator<Item = TextRange>) -> Value {
+        let strings: Vec<Value> = parts
+            .map(|part| self.node("SimpleString", part, [("value", json!(self.text(part)))]))
+            .collect();
+        if let [one] = strings.as_slice() {
+            return one.clone();
+        }
+        self.node(
+            "ConcatenatedString",
+            range,
+            [("strings", Value::Array(strings))],
+        )
+    }
+
+    fn f_string_value(&self, range: TextRange, value: &ast::FStringValue) -> Value {
+        let mut parts = Vec::new();
+        for part in value.as_slice() {
+            match part {
+                ast::FStringPart::Literal(literal) => {
+                    parts.push(self.node(
+                        "FormattedStringText",
+                        literal.range,
+                        [("value", json!(literal.value))],
+                    ));
+                }
+                ast::FStringPart::FString(f_string) => {
+                    for element in &f_string.elements {
+                        parts.push(self.f_string_element(element));
+                    }
+                }
+            }
+        }
+        self.node("FormattedString", range, [("parts", Value::Array(parts))])
+    }
+
+    fn f_string_element(&self, element: &ast::FStringElement) -> Value {
+        match element {
+            ast::FStringElement::Literal(literal) => self.node(
+                "FormattedStringText",
+                literal.range,
+                [("value", json!(literal.value))],
+            ),
+            ast::FStringElement::Expression(expression) => self.node(
+                "FormattedStringExpression",
+                expression.range,
+                [
+                    ("expression", self.expr(&expression.expression)),
+                    (
+                        "format_spec",
+                        expression.format_spec.as_deref().map_or(Value::Null, |spec| {
+                            let parts = spec
+                                .elements
+                                .iter()
+                                .map(|element| self.f_string_element(element))
+                                .collect();
+                            Value::Array(parts)
+                        }),
+                    ),
+                ],
+            ),
+        }
+    }
+}
+
+fn operator_text(op: ast::Operator) -> &'static str {
+    match op {
+        ast::Operator::Add => "+",
+        ast::Operator::Sub => "-",
+        ast::Operator::Mult => "*",
+        ast::Operator::MatMult => "@",
+        ast::Operator::Div => "/",
+        ast::Operator::Mod => "%",
+        ast::Operator::Pow => "**",
+        ast::Operator::LShift => "<<",
+        ast::Operator::RShift => ">>",
+        ast::Operator::BitOr => "|",
+        ast::Operator::BitXor => "^",
+        ast::Operator::BitAnd => "&",
+        ast::Operator::FloorDiv => "//",
+    }
+}
+
+fn unary_operator_text(op: ast::UnaryOp) -> &'static str {
+    match op {
+        ast::UnaryOp::Invert => "~",
+        ast::UnaryOp::Not => "not",
+        ast::UnaryOp::UAdd => "+",
+        ast::UnaryOp::USub => "-",
+    }
+}
+
+fn cmp_operator_text(op: ast::CmpOp) -> &'static str {
+    match op {
+        ast::CmpOp::Eq => "==",
+        ast::CmpOp::NotEq => "!=",
+        ast::CmpOp::Lt => "<",
+        ast::CmpOp::LtE => "<=",
+        ast::CmpOp::Gt => ">",
+        ast::CmpOp::GtE => ">=",
+        ast::CmpOp::Is => "is",
+        ast::CmpOp::IsNot => "is not",
+        ast::CmpOp::In => "in",
+        ast::CmpOp::NotIn => "not in",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::to_libcst_json;
+    use crate::{parse, Mode};
+
+    fn dump(source: &str) -> serde_json::Value {
+        let module = parse(source, Mode::Module).unwrap();
+        to_libcst_json(&module, source)
+    }
+
+    #[test]
+    fn simple_call() {
+        let value = dump("print(1)\n");
+        let call = &value["body"][0]["value"];
+        assert_eq!(call["type"], json!("Call"));
+        assert_eq!(call["func"]["value"], json!("print"));
+        assert_eq!(call["args"][0]["value"]["value"], json!("1"));
+    }
+
+    #[test]
+    fn redundant_parens_are_reported() {
+        let value = dump("x = (1 + 2)\n");
+        let rhs = &value["body"][0]["value"];
+        assert_eq!(rhs["type"], json!("BinaryOperation"));
+        assert!(rhs["lpar"].is_array());
+        assert_eq!(rhs["lpar"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn implicit_string_concatenation_becomes_concatenated_string() {
+        let value = dump("'a' 'b'\n");
+        let value = &value["body"][0]["value"];
+        assert_eq!(value["type"], json!("ConcatenatedString"));
+        assert_eq!(value["strings"][0]["value"], json!("'a'"));
+        assert_eq!(value["strings"][1]["value"], json!("'b'"));
+    }
+
+    #[test]
+    fn boolean_operation_chain_folds_into_binary_nodes() {
+        let value = dump("a and b and c\n");
+        let outer = &value["body"][0]["value"];
+        assert_eq!(outer["type"], json!("BooleanOperation"));
+        assert_eq!(outer["right"]["value"], json!("c"));
+        let inner = &outer["left"];
+        assert_eq!(inner["type"], json!("BooleanOperation"));
+        assert_eq!(inner["left"]["value"], json!("a"));
+        assert_eq!(inner["right"]["value"], json!("b"));
+    }
+
+    #[test]
+    fn whitespace_is_captured_around_operators() {
+        let value = dump("x = 1  +  2\n");
+        let binop = &value["body"][0]["value"];
+        assert_eq!(binop["left"]["whitespace_after"], json!("  "));
+        assert_eq!(binop["right"]["whitespace_before"], json!("  "));
+    }
+}
@@ -0,0 +1,90 @@
+//! Accurate span information for a parsed module, supplementing [`ModModule::range`].
+//!
+//! [`ModModule::range`] is set by the grammar production for the whole module, so it always
+//! starts at `0` and ends wherever the last statement does -- it never extends to cover trailing
+//! trivia (a trailing comment, blank lines) that falls after the last statement but is still
+//! part of `source`, and for a module with no statements at all (a file of nothing but comments)
+//! it's the empty range at `0`. That leaves formatters and header-manipulation tools (stripping
+//! or rewriting a license banner, say) with no reliable way to ask "where does the leading
+//! comment block end" or "what's in the trailing trivia" from the range alone.
+//!
+//! [`content_range`] computes the real span of the parsed statements -- which, unlike
+//! [`ModModule::range`], correctly excludes a leading comment block or blank lines before the
+//! first statement -- and [`leading_trivia`]/[`trailing_trivia`] hand back the source text on
+//! either side of it, measured against the true length of `source` rather than
+//! [`ModModule::range`]'s own (trailing-trivia-blind) bounds.
+use ruff_python_ast::ModModule;
+use ruff_text_size::{Ranged, TextRange, TextSize};
+
+/// The span covered by `module`'s statements: from the start of the first statement to the end
+/// of the last. For a module with no statements -- an empty file, or one containing only
+/// comments and blank lines -- this is the empty range at the end of `source`, so that trivia is
+/// entirely [`leading_trivia`] rather than split arbitrarily between the two.
+pub fn content_range(module: &ModModule, source: &str) -> TextRange {
+    match (module.body.first(), module.body.last()) {
+        (Some(first), Some(last)) => TextRange::new(first.range().start(), last.range().end()),
+        _ => TextRange::empty(source_end(source)),
+    }
+}
+
+/// The text of `source` before [`content_range`]: everything from the start of the file up to
+/// its first statement, such as a shebang line, an encoding comment, or a leading comment block.
+pub fn leading_trivia<'a>(module: &ModModule, source: &'a str) -> &'a str {
+    &source[TextRange::up_to(content_range(module, source).start())]
+}
+
+/// The text of `source` after [`content_range`]: everything from the end of the module's last
+/// statement to the end of the file, such as a trailing comment or blank lines.
+pub fn trailing_trivia<'a>(module: &ModModule, source: &'a str) -> &'a str {
+    &source[TextRange::new(content_range(module, source).end(), source_end(source))]
+}
+
+fn source_end(source: &str) -> TextSize {
+    TextSize::try_from(source.len()).expect("source fits in a TextSize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_range, leading_trivia, trailing_trivia};
+    use crate::{parse, Mode};
+    use ruff_python_ast::Mod;
+
+    fn parse_module(source: &str) -> ruff_python_ast::ModModule {
+        match parse(source, Mode::Module).unwrap() {
+            Mod::Module(module) => module,
+            Mod::Expression(_) | Mod::FunctionType(_) => {
+                unreachable!("Mode::Module doesn't return other variant")
+            }
+        }
+    }
+
+    #[test]
+    fn content_range_excludes_a_leading_comment_block() {
+        let source = "# copyright notice\n# more copyright\nx = 1\n";
+        let module = parse_module(source);
+        assert_eq!(&source[content_range(&module, source)], "x = 1");
+        assert_eq!(
+            leading_trivia(&module, source),
+            "# copyright notice\n# more copyright\n"
+        );
+        assert_eq!(trailing_trivia(&module, source), "\n");
+    }
+
+    #[test]
+    fn content_range_excludes_trailing_trivia_that_module_range_misses() {
+        let source = "x = 1\n# trailing comment\n";
+        let module = parse_module(source);
+        assert_eq!(&source[content_range(&module, source)], "x = 1");
+        assert_eq!(leading_trivia(&module, source), "");
+        assert_eq!(trailing_trivia(&module, source), "\n# trailing comment\n");
+    }
+
+    #[test]
+    fn a_comment_only_module_is_entirely_leading_trivia() {
+        let source = "# just a comment\n";
+        let module = parse_module(source);
+        assert!(content_range(&module, source).is_empty());
+        assert_eq!(leading_trivia(&module, source), source);
+        assert_eq!(trailing_trivia(&module, source), "");
+    }
+}
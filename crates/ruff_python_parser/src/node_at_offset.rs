@@ -0,0 +1,91 @@
+//! Finds the smallest statement or expression enclosing a source offset.
+//!
+//! Every language server feature -- hover, go-to-definition, completion -- starts by resolving
+//! the cursor to an AST node, and every consumer ends up writing the same preorder walk to do it.
+//! [`node_at_offset`] is that walk, written once: it descends the tree, at each level narrowing to
+//! whichever child still contains `offset`, and stops when no child does -- so the result is
+//! always a node with no smaller node inside it also covering `offset`.
+
+use ruff_python_ast::visitor::preorder::{PreorderVisitor, TraversalSignal};
+use ruff_python_ast::{AnyNodeRef, Stmt};
+use ruff_text_size::{Ranged, TextSize};
+
+/// Returns the smallest node in `body` whose range contains `offset`, or `None` if `offset` falls
+/// outside every statement -- leading or trailing whitespace, say.
+///
+/// At a boundary shared by two adjacent nodes (the offset right between a statement and the next
+/// one), the later node wins.
+pub fn node_at_offset(body: &[Stmt], offset: TextSize) -> Option<AnyNodeRef> {
+    let mut finder = Finder {
+        offset,
+        found: None,
+    };
+    for stmt in body {
+        finder.visit_stmt(stmt);
+    }
+    finder.found
+}
+
+struct Finder<'a> {
+    offset: TextSize,
+    found: Option<AnyNodeRef<'a>>,
+}
+
+impl<'a> PreorderVisitor<'a> for Finder<'a> {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        if node.range().contains_inclusive(self.offset) {
+            self.found = Some(node);
+            TraversalSignal::Traverse
+        } else {
+            TraversalSignal::Skip
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::{AnyNodeRef, Mod};
+    use ruff_text_size::{Ranged, TextSize};
+
+    use super::node_at_offset;
+    use crate::{parse, Mode};
+
+    fn module_body(source: &str) -> Vec<ruff_python_ast::Stmt> {
+        match parse(source, Mode::Module).unwrap() {
+            Mod::Module(module) => module.body,
+            Mod::Expression(_) | Mod::FunctionType(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn finds_the_innermost_expression_at_an_offset() {
+        let source = "x = 1 + 2\n";
+        let body = module_body(source);
+        let node = node_at_offset(&body, TextSize::from(4)).unwrap();
+        assert!(matches!(node, AnyNodeRef::ExprNumberLiteral(_)));
+        assert_eq!(&source[node.range()], "1");
+    }
+
+    #[test]
+    fn finds_the_enclosing_statement_when_no_expression_is_narrower() {
+        let source = "pass\n";
+        let body = module_body(source);
+        let node = node_at_offset(&body, TextSize::from(0)).unwrap();
+        assert!(matches!(node, AnyNodeRef::StmtPass(_)));
+    }
+
+    #[test]
+    fn descends_into_a_nested_suite() {
+        let source = "if True:\n    x = 1\n";
+        let body = module_body(source);
+        let node = node_at_offset(&body, TextSize::from(17)).unwrap();
+        assert!(matches!(node, AnyNodeRef::ExprNumberLiteral(_)));
+    }
+
+    #[test]
+    fn returns_none_outside_every_statement() {
+        let source = "x = 1\n";
+        let body = module_body(source);
+        assert!(node_at_offset(&body, TextSize::from(6)).is_none());
+    }
+}
@@ -0,0 +1,86 @@
+//! Parsing support for Jupyter notebooks.
+//!
+//! A notebook's code cells are edited, executed, and can error independently, so they need their
+//! own parse each: one cell's syntax error shouldn't take down every other cell's result. At the
+//! same time, tools built on top of this crate generally want every cell's ranges to agree with
+//! the offsets of a single hypothetical file made by joining the cells together -- the same
+//! representation [`ruff_notebook::Notebook::source_code`](https://docs.rs/ruff_notebook) builds
+//! for its callers. [`parse_notebook_cells`] takes pre-concatenated cell sources directly (rather
+//! than this crate depending on `ruff_notebook`'s JSON handling), so the cell list can come from a
+//! notebook file, a test harness, or anywhere else a caller already has one.
+
+use ruff_text_size::TextSize;
+
+use crate::{parse_starts_at, Mode, Mod, ParseError};
+
+/// Maps each cell index to the byte range it would occupy in the hypothetical source built by
+/// joining cells with a newline between each, as returned by [`parse_notebook_cells`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NotebookCellOffsets(Vec<TextSize>);
+
+impl NotebookCellOffsets {
+    /// The range `cell`'s source occupies in the joined source, or `None` if `cell` is out of
+    /// bounds.
+    pub fn cell_range(&self, cell: usize) -> Option<(TextSize, TextSize)> {
+        Some((*self.0.get(cell)?, *self.0.get(cell + 1)? - TextSize::new(1)))
+    }
+}
+
+/// Parses each of `cells` independently in [`Mode::Ipython`], as though they'd been joined (with
+/// a single newline between each) into one source file: each result's node ranges are valid
+/// offsets into that hypothetical joined source, and the accompanying [`NotebookCellOffsets`]
+/// records where each cell starts and ends within it.
+pub fn parse_notebook_cells(cells: &[&str]) -> (Vec<Result<Mod, ParseError>>, NotebookCellOffsets) {
+    let mut boundaries = Vec::with_capacity(cells.len() + 1);
+    let mut offset = TextSize::default();
+    boundaries.push(offset);
+
+    let results = cells
+        .iter()
+        .map(|cell| {
+            let result = parse_starts_at(cell, Mode::Ipython, offset);
+            offset += TextSize::of(*cell) + TextSize::new(1);
+            boundaries.push(offset);
+            result
+        })
+        .collect();
+
+    (results, NotebookCellOffsets(boundaries))
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_text_size::{Ranged, TextSize};
+
+    use super::parse_notebook_cells;
+
+    #[test]
+    fn parses_each_cell_independently() {
+        let (results, _) = parse_notebook_cells(&["x = 1", "y = 2"]);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+    }
+
+    #[test]
+    fn a_syntax_error_in_one_cell_does_not_affect_others() {
+        let (results, _) = parse_notebook_cells(&["x = 1", "def (", "y = 2"]);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn ranges_are_offset_into_the_joined_source() {
+        let (results, offsets) = parse_notebook_cells(&["x = 1", "y = 2"]);
+        let second_module = results[1].as_ref().unwrap().as_module().unwrap();
+        let stmt = second_module.body.first().unwrap();
+        assert_eq!(stmt.range().start(), TextSize::new(6));
+        assert_eq!(offsets.cell_range(1), Some((TextSize::new(6), TextSize::new(11))));
+    }
+
+    #[test]
+    fn magic_commands_parse_in_ipython_mode() {
+        let (results, _) = parse_notebook_cells(&["%timeit 1 + 2"]);
+        assert!(results[0].is_ok());
+    }
+}
@@ -0,0 +1,29 @@
+//! Parsing many files at once, in parallel.
+//!
+//! Requires the `rayon` feature. Parsing a single file is already fast, but tools that need to
+//! parse an entire project up front (for example, to build a cross-file index) spend most of
+//! their wall-clock time doing exactly that, embarrassingly parallel work. [`parse_files`] hands
+//! each input to [`crate::parse`] on rayon's global thread pool and collects the results back in
+//! input order.
+
+use ruff_python_ast::Mod;
+use rayon::prelude::*;
+
+use crate::{parse, Mode, ParseError};
+
+/// Parses every `(name, source)` pair in `inputs` using `mode`, in parallel.
+///
+/// Results are returned in the same order as `inputs`, paired with the name they came from, so
+/// that callers can report errors against the right file.
+pub fn parse_files<'a, N>(
+    inputs: &'a [(N, String)],
+    mode: Mode,
+) -> Vec<(&'a N, Result<Mod, ParseError>)>
+where
+    N: Sync,
+{
+    inputs
+        .par_iter()
+        .map(|(name, source)| (name, parse(source, mode)))
+        .collect()
+}
@@ -9,24 +9,35 @@
 //! All functions return a [`Result`](std::result::Result) containing the parsed AST or
 //! a [`ParseError`] if parsing failed.
 //!
+//! With the `tracing` feature enabled, [`parse_tokens`] and [`parse_fused`] emit a `tracing`
+//! span covering the whole parse (labelled with the [`Mode`] and source length), and a trace
+//! event each time a syntax error is reported. This doesn't instrument every statement or
+//! expression the grammar visits — lalrpop generates the grammar's internals, which aren't a
+//! practical place to hang manual spans — but it's enough to tell, from a trace, which file (or
+//! which of several files parsed back to back) is slow and whether it's failing to parse.
+//!
 //! [Abstract Syntax Tree]: https://en.wikipedia.org/wiki/Abstract_syntax_tree
 //! [`Mode`]: crate::mode
 
 use itertools::Itertools;
 pub(super) use lalrpop_util::ParseError as LalrpopError;
+#[cfg(feature = "tracing")]
+use tracing::Level;
 
 use ruff_python_ast::{
+    visitor::{walk_expr, walk_stmt, Visitor},
     Expr, ExprAttribute, ExprAwait, ExprBinOp, ExprBoolOp, ExprBooleanLiteral, ExprBytesLiteral,
     ExprCall, ExprCompare, ExprDict, ExprDictComp, ExprEllipsisLiteral, ExprFString,
     ExprGeneratorExp, ExprIfExp, ExprIpyEscapeCommand, ExprLambda, ExprList, ExprListComp,
     ExprName, ExprNamedExpr, ExprNoneLiteral, ExprNumberLiteral, ExprSet, ExprSetComp, ExprSlice,
     ExprStarred, ExprStringLiteral, ExprSubscript, ExprTuple, ExprUnaryOp, ExprYield,
-    ExprYieldFrom, Mod, ModModule, Suite,
+    ExprYieldFrom, Mod, ModFunctionType, ModModule, Stmt, Suite,
 };
 use ruff_text_size::{Ranged, TextRange, TextSize};
 
-use crate::lexer::{lex, lex_starts_at, LexResult};
-use crate::token_source::TokenSource;
+use crate::lexer::{lex, lex_starts_at, LexResult, Lexer};
+use crate::soft_keywords::SoftKeywordTransformer;
+use crate::token_source::{TokenSource, TokenSourceLookahead};
 use crate::{
     lexer::{self, LexicalError, LexicalErrorType},
     python,
@@ -57,7 +68,9 @@ use crate::{
 pub fn parse_program(source: &str) -> Result<ModModule, ParseError> {
     match parse_tokens(tokenize_all(source, Mode::Module), source, Mode::Module)? {
         Mod::Module(m) => Ok(m),
-        Mod::Expression(_) => unreachable!("Mode::Module doesn't return other variant"),
+        Mod::Expression(_) | Mod::FunctionType(_) => {
+            unreachable!("Mode::Module doesn't return other variant")
+        }
     }
 }
 
@@ -65,6 +78,161 @@ pub fn parse_suite(source: &str) -> Result<Suite, ParseError> {
     parse_program(source).map(|m| m.body)
 }
 
+/// Parses `source` as a full module the way [`parse_program`] does, and also returns the exact
+/// token stream it was parsed from.
+///
+/// Lexing happens once either way -- this just keeps the resulting tokens around afterwards
+/// instead of discarding them, for a caller (a formatter, a rule inspecting exact punctuation)
+/// that would otherwise lex `source` a second time to get at them.
+pub fn parse_program_with_tokens(source: &str) -> Result<(ModModule, Vec<LexResult>), ParseError> {
+    let tokens = tokenize_all(source, Mode::Module);
+    match parse_tokens_ref(&tokens, source, Mode::Module)? {
+        Mod::Module(m) => Ok((m, tokens)),
+        Mod::Expression(_) | Mod::FunctionType(_) => {
+            unreachable!("Mode::Module doesn't return other variant")
+        }
+    }
+}
+
+/// Parses `source` as exactly one statement, simple or compound, erroring if anything besides
+/// that one statement is present.
+///
+/// This is for callers that splice or synthesize individual statements -- a refactoring tool
+/// rewriting a single assignment, say -- rather than working with a whole module's body, so the
+/// statement is handed back directly instead of wrapped in a single-element [`Suite`].
+pub fn parse_statement(source: &str) -> Result<Stmt, ParseError> {
+    let mut statements = parse_suite(source)?.into_iter();
+    let Some(statement) = statements.next() else {
+        return Err(ParseError {
+            error: ParseErrorType::Eof,
+            offset: TextSize::of(source),
+        });
+    };
+    if let Some(next) = statements.next() {
+        return Err(ParseError {
+            error: ParseErrorType::TrailingStatement,
+            offset: next.range().start(),
+        });
+    }
+    Ok(statement)
+}
+
+/// The result of [`parse_program_with_recovery`]: whatever of `source` parsed successfully,
+/// alongside every [`ParseError`] encountered along the way.
+#[derive(Debug, PartialEq)]
+pub struct RecoveredModule {
+    pub module: ModModule,
+    pub errors: Vec<ParseError>,
+}
+
+/// Parses `source` as a full module the way [`parse_program`] does, except a syntax error in one
+/// top-level statement doesn't discard the rest of the file.
+///
+/// The grammar has no error-recovery points of its own -- a single failing statement stops the
+/// whole parse, with no partial tree to salvage (see [`parse_tokens`]). To still get something
+/// usable out of a file with more than one unrelated mistake, this first splits `source` into its
+/// top-level statements using the lexer's own indentation tracking (a logical line that starts
+/// back at column zero begins a new one), then parses each independently: a statement that fails
+/// contributes its error to [`RecoveredModule::errors`] and is dropped from the body, while its
+/// well-formed neighbors still end up in [`RecoveredModule::module`] for a linter (or anything
+/// else that can work with a partial file) to run on.
+///
+/// A syntax error that spans *across* a statement boundary -- an unterminated string or unmatched
+/// bracket that swallows the rest of the file, for instance -- can't be isolated this way: the
+/// remaining source becomes a single statement, attributed a single error, the same result
+/// [`parse_program`] would give.
+pub fn parse_program_with_recovery(source: &str) -> RecoveredModule {
+    parse_program_with_recovery_with_options(source, &ParseOptions::new(Mode::Module))
+}
+
+/// Like [`parse_program_with_recovery`], but each top-level statement is parsed through
+/// [`parse_with_options`] instead of [`parse_program`], so `options`'s nesting-depth and
+/// top-level-await settings apply to every chunk. `options.mode` is ignored -- a module's
+/// top-level statements are always split and parsed in [`Mode::Module`] -- and `options.error_limit`,
+/// if set, stops the scan early once that many errors have been collected, leaving the rest of
+/// `source` unparsed.
+pub fn parse_program_with_recovery_with_options(
+    source: &str,
+    options: &ParseOptions,
+) -> RecoveredModule {
+    let chunk_options = ParseOptions {
+        mode: Mode::Module,
+        ..options.clone()
+    };
+
+    let mut body = Vec::new();
+    let mut errors = Vec::new();
+
+    for chunk in top_level_chunks(source) {
+        if options
+            .error_limit
+            .is_some_and(|limit| errors.len() >= limit)
+        {
+            break;
+        }
+
+        match parse_with_options(&source[chunk], &chunk_options) {
+            Ok(Mod::Module(mut chunk_module)) => {
+                ruff_python_ast::offset::offset_body(
+                    &mut chunk_module.body,
+                    ruff_python_ast::offset::Shift::Add(chunk.start()),
+                );
+                body.extend(chunk_module.body);
+            }
+            Ok(Mod::Expression(_) | Mod::FunctionType(_)) => {
+                unreachable!("Mode::Module doesn't return other variant")
+            }
+            Err(mut error) => {
+                error.offset += chunk.start();
+                errors.push(error);
+            }
+        }
+    }
+
+    let range = TextRange::new(
+        TextSize::new(0),
+        TextSize::try_from(source.len()).expect("source fits in a TextSize"),
+    );
+    RecoveredModule {
+        module: ModModule { range, body },
+        errors,
+    }
+}
+
+/// Splits `source` into the byte ranges of its top-level statements, using `Indent`/`Dedent`
+/// tokens to tell a logical line back at column zero (the start of a new top-level statement)
+/// apart from a continuation line nested inside one.
+///
+/// Lexical errors are skipped rather than stopping the split, on the theory that a best-effort
+/// boundary is more useful here than none; the chunk that contains the bad token still gets
+/// re-lexed (and its error reported) when [`parse_program_with_recovery`] parses it on its own.
+fn top_level_chunks(source: &str) -> Vec<TextRange> {
+    let mut chunks = Vec::new();
+    let mut depth = 0i32;
+    let mut chunk_start = TextSize::new(0);
+
+    for result in lexer::lex(source, Mode::Module) {
+        let Ok((tok, range)) = result else {
+            continue;
+        };
+        match tok {
+            Tok::Indent => depth += 1,
+            Tok::Dedent => depth -= 1,
+            Tok::Newline if depth == 0 => {
+                chunks.push(TextRange::new(chunk_start, range.end()));
+                chunk_start = range.end();
+            }
+            _ => {}
+        }
+    }
+
+    let source_len = TextSize::try_from(source.len()).expect("source fits in a TextSize");
+    if chunk_start < source_len {
+        chunks.push(TextRange::new(chunk_start, source_len));
+    }
+    chunks
+}
+
 /// Parses a single Python expression.
 ///
 /// This convenience function can be used to parse a single expression without having to
@@ -85,7 +253,9 @@ pub fn parse_expression(source: &str) -> Result<Expr, ParseError> {
     let lexer = lex(source, Mode::Expression);
     match parse_tokens(lexer.collect(), source, Mode::Expression)? {
         Mod::Expression(expression) => Ok(*expression.body),
-        Mod::Module(_m) => unreachable!("Mode::Expression doesn't return other variant"),
+        Mod::Module(_) | Mod::FunctionType(_) => {
+            unreachable!("Mode::Expression doesn't return other variant")
+        }
     }
 }
 
@@ -110,10 +280,47 @@ pub fn parse_expression_starts_at(source: &str, offset: TextSize) -> Result<Expr
     let lexer = lex_starts_at(source, Mode::Module, offset);
     match parse_tokens(lexer.collect(), source, Mode::Expression)? {
         Mod::Expression(expression) => Ok(*expression.body),
-        Mod::Module(_m) => unreachable!("Mode::Expression doesn't return other variant"),
+        Mod::Module(_) | Mod::FunctionType(_) => {
+            unreachable!("Mode::Expression doesn't return other variant")
+        }
+    }
+}
+
+/// Parses a PEP 484 function type comment's signature, e.g. `(int, str) -> bool`, the way
+/// `CPython`'s `ast.parse(source, mode="func_type")` does.
+///
+/// `offset` is the position of `source`'s first byte within whatever larger source it was
+/// extracted from (a `# type:` comment's contents, for [`crate::type_comments`]), so the returned
+/// node's ranges point back at the original source rather than at `source` in isolation.
+pub fn parse_function_type_starts_at(
+    source: &str,
+    offset: TextSize,
+) -> Result<ModFunctionType, ParseError> {
+    let lexer = lex_starts_at(source, Mode::Module, offset);
+    match parse_tokens(lexer.collect(), source, Mode::FunctionType)? {
+        Mod::FunctionType(function_type) => Ok(function_type),
+        Mod::Module(_) | Mod::Expression(_) => {
+            unreachable!("Mode::FunctionType doesn't return other variant")
+        }
     }
 }
 
+/// Re-parses the expression inside an f-string replacement field from its own source range.
+///
+/// Today the grammar always parses f-string expressions eagerly as part of the surrounding parse,
+/// so this doesn't skip any work on its own. It exists as the primitive a deferred-parsing mode
+/// would call: a caller that stores an f-string as raw literal text plus unparsed replacement-field
+/// ranges (rather than holding onto the already-built [`Expr`]) can use this to parse a given field
+/// on demand, the first time something actually asks for it.
+///
+/// `full_source` is the complete source the f-string was lexed from, and `range` is the span of
+/// the replacement field's expression within it (for example,
+/// [`FStringExpressionElement::expression`](ruff_python_ast::FStringExpressionElement::expression)'s
+/// range).
+pub fn parse_fstring_expression(full_source: &str, range: TextRange) -> Result<Expr, ParseError> {
+    parse_expression_starts_at(&full_source[range], range.start())
+}
+
 /// Parse the given Python source code using the specified [`Mode`].
 ///
 /// This function is the most general function to parse Python code. Based on the [`Mode`] supplied,
@@ -164,6 +371,364 @@ pub fn parse(source: &str, mode: Mode) -> Result<Mod, ParseError> {
     parse_starts_at(source, mode, TextSize::default())
 }
 
+/// The Python grammar variant this crate parses. The grammar itself accepts every one of these
+/// syntax features unconditionally; [`parse_with_options`] is what actually rejects a construct
+/// that's newer than [`ParseOptions::target_version`], by walking the already-built tree rather
+/// than threading the version through the grammar's productions.
+///
+/// Variants are declared oldest to newest so that the derived [`Ord`] doubles as a version
+/// ordering: `PythonVersion::Py38 < PythonVersion::Py312`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PythonVersion {
+    Py38,
+    Py39,
+    Py310,
+    Py311,
+    #[default]
+    Py312,
+}
+
+impl std::fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (major, minor) = match self {
+            PythonVersion::Py38 => (3, 8),
+            PythonVersion::Py39 => (3, 9),
+            PythonVersion::Py310 => (3, 10),
+            PythonVersion::Py311 => (3, 11),
+            PythonVersion::Py312 => (3, 12),
+        };
+        write!(f, "Python {major}.{minor}")
+    }
+}
+
+/// Configuration accepted by [`parse_with_options`], gathering knobs that today are either
+/// hardcoded in [`parse`] or split across unrelated builder methods (see
+/// [`lexer::Lexer::with_max_nesting_depth`]) into one place.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    pub mode: Mode,
+    /// The Python version `source` is expected to run on. [`parse_with_options`] rejects syntax
+    /// newer than this -- see [`first_version_gated_syntax_error`].
+    pub target_version: PythonVersion,
+    /// Caps how many errors [`parse_program_with_recovery_with_options`] collects before it stops
+    /// parsing further top-level statements. Has no effect on [`parse_with_options`] itself, which
+    /// never produces more than one error.
+    pub error_limit: Option<usize>,
+    /// Forwarded to [`lexer::Lexer::with_max_nesting_depth`].
+    pub max_nesting_depth: u32,
+    /// Whether an `await` expression is allowed directly in the module body, outside of any
+    /// function -- CPython's `PyCF_ALLOW_TOP_LEVEL_AWAIT` compile flag, used by async REPLs.
+    /// `false` by default, matching a plain `.py` file.
+    pub allow_top_level_await: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Module,
+            target_version: PythonVersion::default(),
+            error_limit: None,
+            max_nesting_depth: lexer::DEFAULT_MAX_NESTING_DEPTH,
+            allow_top_level_await: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_target_version(mut self, target_version: PythonVersion) -> Self {
+        self.target_version = target_version;
+        self
+    }
+
+    #[must_use]
+    pub fn with_error_limit(mut self, error_limit: usize) -> Self {
+        self.error_limit = Some(error_limit);
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: u32) -> Self {
+        self.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    #[must_use]
+    pub fn with_top_level_await(mut self, allow_top_level_await: bool) -> Self {
+        self.allow_top_level_await = allow_top_level_await;
+        self
+    }
+}
+
+/// Parses `source` the way [`parse`] does, except every knob on [`ParseOptions`] that can affect
+/// a single parse is honored: the lexer is built with `options.max_nesting_depth` instead of
+/// [`lexer::DEFAULT_MAX_NESTING_DEPTH`]; unless `options.allow_top_level_await` is set, a bare
+/// `await` outside of any function is rejected after the fact as [`LexicalErrorType::OtherError`],
+/// the same way CPython rejects it without `PyCF_ALLOW_TOP_LEVEL_AWAIT`; and a construct newer
+/// than `options.target_version` (a `match` statement, `except*`, a `type` alias, a PEP 695 type
+/// parameter list, or the walrus operator) is rejected the same way -- see
+/// [`first_version_gated_syntax_error`].
+///
+/// `options.error_limit` has no effect here -- a single parse only ever produces one error to
+/// begin with -- see [`parse_program_with_recovery_with_options`] if that's what's needed.
+pub fn parse_with_options(source: &str, options: &ParseOptions) -> Result<Mod, ParseError> {
+    let lexer = SoftKeywordTransformer::new(
+        Lexer::new(source, options.mode).with_max_nesting_depth(options.max_nesting_depth),
+        options.mode,
+    );
+    let module = parse_tokens(lexer.collect(), source, options.mode)?;
+
+    if !options.allow_top_level_await {
+        if let Some(offset) = first_top_level_await(&module) {
+            return Err(ParseError {
+                error: ParseErrorType::Lexical(LexicalErrorType::OtherError(
+                    "`await` outside of an async function is not allowed at module scope"
+                        .to_string(),
+                )),
+                offset,
+            });
+        }
+    }
+
+    if let Some(error) = first_version_gated_syntax_error(&module, options.target_version) {
+        return Err(error);
+    }
+
+    Ok(module)
+}
+
+/// Returns a [`ParseError`] for the first syntax construct in `module` that `target_version`
+/// doesn't support, or `None` if every construct `module` uses is available on `target_version`.
+///
+/// The grammar has no notion of a target version -- it parses `match` statements, `except*`,
+/// `type` aliases, PEP 695 type parameter lists, and the walrus operator the same way regardless
+/// of what a user asked for, so a linter checking "is this file's syntax too new for its
+/// configured target" has nothing to hook into. This walks the already-parsed tree looking for
+/// the oldest-gated construct still present, the same after-the-fact approach
+/// [`first_top_level_await`] takes for a check the grammar can't make either.
+fn first_version_gated_syntax_error(
+    module: &Mod,
+    target_version: PythonVersion,
+) -> Option<ParseError> {
+    struct Finder {
+        target_version: PythonVersion,
+        found: Option<(TextSize, PythonVersion, &'static str)>,
+    }
+
+    impl Finder {
+        fn require(&mut self, offset: TextSize, required: PythonVersion, what: &'static str) {
+            if self.found.is_some() || required <= self.target_version {
+                return;
+            }
+            self.found = Some((offset, required, what));
+        }
+    }
+
+    impl<'a> Visitor<'a> for Finder {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            if self.found.is_some() {
+                return;
+            }
+            match stmt {
+                Stmt::Match(match_stmt) => {
+                    self.require(
+                        match_stmt.range().start(),
+                        PythonVersion::Py310,
+                        "`match` statements",
+                    );
+                }
+                Stmt::Try(try_stmt) if try_stmt.is_star => {
+                    self.require(
+                        try_stmt.range().start(),
+                        PythonVersion::Py311,
+                        "`except*` clauses",
+                    );
+                }
+                Stmt::TypeAlias(type_alias) => {
+                    self.require(
+                        type_alias.range().start(),
+                        PythonVersion::Py312,
+                        "`type` aliases",
+                    );
+                }
+                Stmt::FunctionDef(function_def) if function_def.type_params.is_some() => {
+                    self.require(
+                        function_def.range().start(),
+                        PythonVersion::Py312,
+                        "generic type parameter lists",
+                    );
+                }
+                Stmt::ClassDef(class_def) if class_def.type_params.is_some() => {
+                    self.require(
+                        class_def.range().start(),
+                        PythonVersion::Py312,
+                        "generic type parameter lists",
+                    );
+                }
+                _ => {}
+            }
+            if self.found.is_none() {
+                walk_stmt(self, stmt);
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            if self.found.is_some() {
+                return;
+            }
+            if let Expr::NamedExpr(named_expr) = expr {
+                self.require(
+                    named_expr.range().start(),
+                    PythonVersion::Py38,
+                    "the walrus operator (`:=`)",
+                );
+            }
+            if self.found.is_none() {
+                walk_expr(self, expr);
+            }
+        }
+    }
+
+    let body: &[Stmt] = match module {
+        Mod::Module(m) => &m.body,
+        Mod::Expression(_) | Mod::FunctionType(_) => return None,
+    };
+
+    let mut finder = Finder {
+        target_version,
+        found: None,
+    };
+    for stmt in body {
+        finder.visit_stmt(stmt);
+    }
+
+    finder.found.map(|(offset, required, what)| ParseError {
+        error: ParseErrorType::Lexical(LexicalErrorType::OtherError(format!(
+            "{what} require at least {required}, but the target version is {target_version}"
+        ))),
+        offset,
+    })
+}
+
+/// Returns the start offset of the first `await` expression in `module`'s body that isn't nested
+/// inside a function definition, or `None` if there isn't one.
+///
+/// Only `def`/`async def` count as function boundaries; an `await` inside a `lambda` or a
+/// comprehension at module scope is rare enough in practice that it isn't distinguished here.
+fn first_top_level_await(module: &Mod) -> Option<TextSize> {
+    struct Finder {
+        in_function: u32,
+        found: Option<TextSize>,
+    }
+
+    impl<'a> Visitor<'a> for Finder {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            if self.found.is_some() {
+                return;
+            }
+            if matches!(stmt, Stmt::FunctionDef(_)) {
+                self.in_function += 1;
+                walk_stmt(self, stmt);
+                self.in_function -= 1;
+            } else {
+                walk_stmt(self, stmt);
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            if self.found.is_some() {
+                return;
+            }
+            if self.in_function == 0 {
+                if let Expr::Await(await_expr) = expr {
+                    self.found = Some(await_expr.range().start());
+                    return;
+                }
+            }
+            walk_expr(self, expr);
+        }
+    }
+
+    let body: &[Stmt] = match module {
+        Mod::Module(m) => &m.body,
+        Mod::Expression(_) | Mod::FunctionType(_) => return None,
+    };
+
+    let mut finder = Finder {
+        in_function: 0,
+        found: None,
+    };
+    for stmt in body {
+        finder.visit_stmt(stmt);
+    }
+    finder.found
+}
+
+/// The result of [`parse_interactive`]: a completed parse, a classification that `source` is a
+/// valid prefix of a longer program and the caller should read another line before trying again,
+/// or a real syntax error.
+///
+/// This mirrors the three-way split CPython's `codeop` module makes, which is what the standard
+/// REPL and Jupyter-style notebook front ends use to decide whether to print a continuation
+/// prompt (`... `) or report a `SyntaxError` right away.
+#[derive(Debug)]
+pub enum InteractiveParseOutcome {
+    Complete(Mod),
+    /// `source` doesn't parse as-is, but a well-formed program could still extend it -- for
+    /// example `if x:` with no body yet, or an expression left dangling after a binary operator.
+    Incomplete,
+    SyntaxError(ParseError),
+}
+
+/// Parses a single logical block of `source` the way a REPL feeds it one line (or pasted block)
+/// at a time, classifying a failed parse as [`InteractiveParseOutcome::Incomplete`] (read another
+/// line and retry) instead of surfacing a bare [`ParseError`] the caller can't act on yet.
+///
+/// There's no dedicated grammar mode for this -- `source` is parsed the same way [`parse`] parses
+/// [`Mode::Module`], and the classification happens after the fact by pattern-matching the
+/// resulting [`ParseErrorType`], the same after-the-fact approach [`first_top_level_await`] and
+/// [`first_version_gated_syntax_error`] take for checks the grammar itself doesn't make.
+pub fn parse_interactive(source: &str) -> InteractiveParseOutcome {
+    match parse(source, Mode::Module) {
+        Ok(module) => InteractiveParseOutcome::Complete(module),
+        Err(error) if is_incomplete_input(&error) => InteractiveParseOutcome::Incomplete,
+        Err(error) => InteractiveParseOutcome::SyntaxError(error),
+    }
+}
+
+/// Returns `true` if `error` indicates that the parser simply ran out of input rather than
+/// encountering a token it can never accept -- the distinction CPython's `codeop` draws between
+/// "needs another line" and "this is wrong no matter how many more lines follow".
+///
+/// [`ParseErrorType::Eof`] and [`LexicalErrorType::Eof`] both mean the grammar or the lexer hit
+/// the end of `source` while still expecting more (an open bracket, an unterminated string, an
+/// operator waiting for its right-hand side). [`ParseErrorType::ExpectedIndentedBlock`] is the
+/// same situation one level up: a compound statement's header parsed fine but its body hasn't
+/// been typed yet.
+///
+/// A dangling operator (`x = 1 +`) looks different from the grammar's perspective: the lexer
+/// always emits a trailing [`Tok::Newline`] to close the final logical line, synthesizing one if
+/// `source` doesn't already end with one, so this doesn't hit [`LexicalErrorType::Eof`] the way
+/// an unterminated string or unclosed bracket does -- the grammar instead reports that `Newline`
+/// itself as unrecognized, since nothing valid can immediately follow a binary operator. That's
+/// still "ran out of input", not "wrong no matter what follows", so it counts as incomplete too.
+fn is_incomplete_input(error: &ParseError) -> bool {
+    matches!(
+        error.error,
+        ParseErrorType::Eof
+            | ParseErrorType::ExpectedIndentedBlock { .. }
+            | ParseErrorType::Lexical(LexicalErrorType::Eof)
+            | ParseErrorType::UnrecognizedToken(Tok::Newline, _)
+    )
+}
+
 /// Parse the given Python source code using the specified [`Mode`] and [`TextSize`].
 ///
 /// This function allows to specify the location of the the source code, other than
@@ -208,22 +773,113 @@ pub fn parse_starts_at(source: &str, mode: Mode, offset: TextSize) -> Result<Mod
 /// let expr = parse_tokens(lex(source, Mode::Expression).collect(), source, Mode::Expression);
 /// assert!(expr.is_ok());
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "parse_tokens",
+        level = Level::TRACE,
+        skip_all,
+        fields(mode = ?mode, source_len = source.len())
+    )
+)]
 pub fn parse_tokens(tokens: Vec<LexResult>, source: &str, mode: Mode) -> Result<Mod, ParseError> {
+    #[cfg(feature = "coverage")]
+    crate::coverage::record("grammar_entry:parse_tokens");
     let marker_token = (Tok::start_marker(mode), TextRange::default());
-    let lexer = std::iter::once(Ok(marker_token)).chain(TokenSource::new(tokens));
+    let lexer = std::iter::once(Ok(marker_token))
+        .chain(TokenSourceLookahead::new(TokenSource::new(tokens)));
     python::TopParser::new()
         .parse(
             source,
             mode,
             lexer.map_ok(|(t, range)| (range.start(), t, range.end())),
         )
-        .map_err(parse_error_from_lalrpop)
+        .map_err(|err| parse_error_from_lalrpop(err, source))
+}
+
+/// Parse a borrowed slice of [`LexResult`]s using the specified [`Mode`].
+///
+/// Identical to [`parse_tokens`], except the tokens are cloned lazily as the grammar consumes
+/// them instead of being moved in up front. Prefer this over `parse_tokens(tokens.to_vec(), ...)`
+/// when the caller needs `tokens` back afterwards (for comment attachment, or a lint pass that
+/// re-walks the stream): cloning eagerly into a second `Vec` before parsing doubles the tokens'
+/// peak memory for as long as both are alive, where parsing from the borrowed slice never
+/// materializes more than one token's worth of clone at a time.
+///
+/// # Example
+///
+/// ```
+/// use ruff_python_parser::{lexer::lex, Mode, parse_tokens_ref};
+///
+/// let source = "1 + 2";
+/// let tokens: Vec<_> = lex(source, Mode::Expression).collect();
+/// let expr = parse_tokens_ref(&tokens, source, Mode::Expression);
+/// assert!(expr.is_ok());
+/// // `tokens` is still ours to use.
+/// assert_eq!(tokens.len(), 3);
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "parse_tokens_ref",
+        level = Level::TRACE,
+        skip_all,
+        fields(mode = ?mode, source_len = source.len())
+    )
+)]
+pub fn parse_tokens_ref(tokens: &[LexResult], source: &str, mode: Mode) -> Result<Mod, ParseError> {
+    #[cfg(feature = "coverage")]
+    crate::coverage::record("grammar_entry:parse_tokens_ref");
+    let marker_token = (Tok::start_marker(mode), TextRange::default());
+    let lexer = std::iter::once(Ok(marker_token))
+        .chain(TokenSourceLookahead::new(TokenSource::from_slice(tokens)));
+    python::TopParser::new()
+        .parse(
+            source,
+            mode,
+            lexer.map_ok(|(t, range)| (range.start(), t, range.end())),
+        )
+        .map_err(|err| parse_error_from_lalrpop(err, source))
+}
+
+/// Lexes and parses `source` in a single pass, without ever materializing the full token stream.
+///
+/// [`parse_starts_at`] (and therefore [`parse`]) first collects every [`LexResult`] into a `Vec`
+/// before handing it to the grammar, so that downstream consumers can reuse the tokens (e.g. for
+/// comment attachment). When the tokens aren't needed for anything else, that intermediate `Vec`
+/// is pure overhead: this function feeds the lexer's iterator directly into the grammar, so lexing
+/// and parsing for a given token interleave rather than running as two back-to-back passes.
+///
+/// The result is identical to `parse_tokens(tokenize_all(source, mode), source, mode)`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "parse_fused",
+        level = Level::TRACE,
+        skip_all,
+        fields(mode = ?mode, source_len = source.len())
+    )
+)]
+pub fn parse_fused(source: &str, mode: Mode) -> Result<Mod, ParseError> {
+    #[cfg(feature = "coverage")]
+    crate::coverage::record("grammar_entry:parse_fused");
+    let marker_token = (Tok::start_marker(mode), TextRange::default());
+    let tokens = lexer::lex(source, mode).filter(|result| !crate::token_source::is_trivia(result));
+    let lexer = std::iter::once(Ok(marker_token)).chain(tokens);
+    python::TopParser::new()
+        .parse(
+            source,
+            mode,
+            lexer.map_ok(|(t, range)| (range.start(), t, range.end())),
+        )
+        .map_err(|err| parse_error_from_lalrpop(err, source))
 }
 
 /// Represents represent errors that occur during parsing and are
 /// returned by the `parse_*` functions.
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParseError {
     pub error: ParseErrorType,
     pub offset: TextSize,
@@ -256,6 +912,7 @@ impl std::fmt::Display for ParseError {
 
 /// Represents the different types of errors that can occur during parsing.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseErrorType {
     /// Parser encountered an unexpected end of input
     Eof,
@@ -265,6 +922,17 @@ pub enum ParseErrorType {
     InvalidToken,
     /// Parser encountered an unexpected token
     UnrecognizedToken(Tok, Option<String>),
+    /// Parser found an `Indent` token where indentation wasn't expected, e.g. a block body
+    /// indented relative to a line that wasn't a compound statement's header.
+    UnexpectedIndent,
+    /// A compound statement's header (`if ...:`, `def ...:`, ...) wasn't followed by an
+    /// indented block, mirroring CPython's `expected an indented block` `IndentationError`.
+    /// `clause` is the keyword that introduced the header (`"if"`, `"def"`, ...), when it could
+    /// be recovered from the source preceding the error; `None` if it couldn't.
+    ExpectedIndentedBlock { clause: Option<String> },
+    /// A caller that wants exactly one statement parsed a well-formed statement but found
+    /// another one following it, where it expects the source to end.
+    TrailingStatement,
     // Maps to `User` type from `lalrpop-util`
     /// Parser encountered an error during lexing.
     Lexical(LexicalErrorType),
@@ -273,7 +941,25 @@ pub enum ParseErrorType {
 impl std::error::Error for ParseErrorType {}
 
 // Convert `lalrpop_util::ParseError` to our internal type
-fn parse_error_from_lalrpop(err: LalrpopError<TextSize, Tok, LexicalError>) -> ParseError {
+fn parse_error_from_lalrpop(
+    err: LalrpopError<TextSize, Tok, LexicalError>,
+    source: &str,
+) -> ParseError {
+    let error = parse_error_from_lalrpop_inner(err, source);
+    #[cfg(feature = "tracing")]
+    tracing::event!(
+        Level::TRACE,
+        offset = ?error.offset,
+        error = %error.error,
+        "parser reported a syntax error"
+    );
+    error
+}
+
+fn parse_error_from_lalrpop_inner(
+    err: LalrpopError<TextSize, Tok, LexicalError>,
+    source: &str,
+) -> ParseError {
     match err {
         // TODO: Are there cases where this isn't an EOF?
         LalrpopError::InvalidToken { location } => ParseError {
@@ -289,9 +975,33 @@ fn parse_error_from_lalrpop(err: LalrpopError<TextSize, Tok, LexicalError>) -> P
             offset: error.location,
         },
         LalrpopError::UnrecognizedToken { token, expected } => {
+            // `case` is only valid as a match arm, directly inside a `match` statement's body
+            // (see `MatchCase` in the grammar). Any other occurrence reaches here as an
+            // unrecognized token, so give it a precise diagnostic rather than the generic
+            // "unexpected token" message below.
+            if token.1 == Tok::Case {
+                return ParseError {
+                    error: ParseErrorType::Lexical(LexicalErrorType::CaseOutsideMatch),
+                    offset: token.0,
+                };
+            }
+            if token.1 == Tok::Indent {
+                return ParseError {
+                    error: ParseErrorType::UnexpectedIndent,
+                    offset: token.0,
+                };
+            }
             // Hacky, but it's how CPython does it. See PyParser_AddToken,
             // in particular "Only one possible expected token" comment.
             let expected = (expected.len() == 1).then(|| expected[0].clone());
+            if expected.as_deref() == Some("Indent") {
+                return ParseError {
+                    error: ParseErrorType::ExpectedIndentedBlock {
+                        clause: clause_before_indent(source, token.0),
+                    },
+                    offset: token.0,
+                };
+            }
             ParseError {
                 error: ParseErrorType::UnrecognizedToken(token.1, expected),
                 offset: token.0,
@@ -299,10 +1009,11 @@ fn parse_error_from_lalrpop(err: LalrpopError<TextSize, Tok, LexicalError>) -> P
         }
         LalrpopError::UnrecognizedEof { location, expected } => {
             // This could be an initial indentation error that we should ignore
-            let indent_error = expected == ["Indent"];
-            if indent_error {
+            if expected == ["Indent"] {
                 ParseError {
-                    error: ParseErrorType::Lexical(LexicalErrorType::IndentationError),
+                    error: ParseErrorType::ExpectedIndentedBlock {
+                        clause: clause_before_indent(source, location),
+                    },
                     offset: location,
                 }
             } else {
@@ -315,20 +1026,44 @@ fn parse_error_from_lalrpop(err: LalrpopError<TextSize, Tok, LexicalError>) -> P
     }
 }
 
+/// Finds the keyword that introduced the compound statement header expecting an indented block,
+/// by walking backward from `offset` to the nearest preceding non-blank line and reading its
+/// leading keyword. Returns `None` if that line doesn't start with a clause keyword (shouldn't
+/// normally happen, since this is only called once the grammar has already reported a missing
+/// `Indent` after a header), so a caller can still report the error without one.
+fn clause_before_indent(source: &str, offset: TextSize) -> Option<String> {
+    const CLAUSE_KEYWORDS: [&str; 11] = [
+        "if", "elif", "else", "for", "while", "try", "except", "finally", "with", "def", "class",
+    ];
+    let before = &source[..usize::from(offset)];
+    let header = before.lines().rev().find(|line| !line.trim().is_empty())?;
+    let header = header.trim_start();
+    CLAUSE_KEYWORDS
+        .into_iter()
+        .find(|keyword| {
+            header
+                .strip_prefix(keyword)
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+        })
+        .map(str::to_owned)
+}
+
 impl std::fmt::Display for ParseErrorType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
             ParseErrorType::Eof => write!(f, "Got unexpected EOF"),
             ParseErrorType::ExtraToken(ref tok) => write!(f, "Got extraneous token: {tok:?}"),
             ParseErrorType::InvalidToken => write!(f, "Got invalid token"),
-            ParseErrorType::UnrecognizedToken(ref tok, ref expected) => {
-                if *tok == Tok::Indent {
-                    write!(f, "unexpected indent")
-                } else if expected.as_deref() == Some("Indent") {
-                    write!(f, "expected an indented block")
-                } else {
-                    write!(f, "invalid syntax. Got unexpected token {tok}")
-                }
+            ParseErrorType::UnrecognizedToken(ref tok, _) => {
+                write!(f, "invalid syntax. Got unexpected token {tok}")
+            }
+            ParseErrorType::UnexpectedIndent => write!(f, "unexpected indent"),
+            ParseErrorType::ExpectedIndentedBlock { ref clause } => match clause {
+                Some(clause) => write!(f, "expected an indented block after '{clause}' statement"),
+                None => write!(f, "expected an indented block"),
+            },
+            ParseErrorType::TrailingStatement => {
+                write!(f, "expected a single statement, but found another one")
             }
             ParseErrorType::Lexical(ref error) => write!(f, "{error}"),
         }
@@ -338,13 +1073,12 @@ impl std::fmt::Display for ParseErrorType {
 impl ParseErrorType {
     /// Returns true if the error is an indentation error.
     pub fn is_indentation_error(&self) -> bool {
-        match self {
-            ParseErrorType::Lexical(LexicalErrorType::IndentationError) => true,
-            ParseErrorType::UnrecognizedToken(token, expected) => {
-                *token == Tok::Indent || expected.clone() == Some("Indent".to_owned())
-            }
-            _ => false,
-        }
+        matches!(
+            self,
+            ParseErrorType::Lexical(LexicalErrorType::DedentDoesNotMatch { .. })
+                | ParseErrorType::UnexpectedIndent
+                | ParseErrorType::ExpectedIndentedBlock { .. }
+        )
     }
 
     /// Returns true if the error is a tab error.
@@ -581,6 +1315,38 @@ mod tests {
         insta::assert_debug_snapshot!(parse_ast);
     }
 
+    #[test]
+    fn parse_statement_parses_a_simple_statement() {
+        let statement = parse_statement("x = 1\n").unwrap();
+        assert!(statement.is_assign_stmt());
+    }
+
+    #[test]
+    fn parse_statement_parses_a_compound_statement() {
+        let statement = parse_statement("if x:\n    pass\n").unwrap();
+        assert!(statement.is_if_stmt());
+    }
+
+    #[test]
+    fn parse_statement_rejects_more_than_one_statement() {
+        let error = parse_statement("x = 1\ny = 2\n").unwrap_err();
+        assert_eq!(error.error, ParseErrorType::TrailingStatement);
+    }
+
+    #[test]
+    fn parse_statement_rejects_an_empty_source() {
+        let error = parse_statement("").unwrap_err();
+        assert_eq!(error.error, ParseErrorType::Eof);
+    }
+
+    #[test]
+    fn parse_program_with_tokens_returns_the_same_module_as_parse_program() {
+        let source = "x = 1\n";
+        let (module, tokens) = parse_program_with_tokens(source).unwrap();
+        assert_eq!(module, parse_program(source).unwrap());
+        assert!(!tokens.is_empty());
+    }
+
     #[test]
     fn test_parse_string() {
         let source = "'Hello world'";
@@ -1328,6 +2094,24 @@ match x:
         );
     }
 
+    #[test]
+    fn test_case_outside_match() {
+        // `case` is a soft keyword that's only meaningful as a match arm. A stray `case` at
+        // statement position should report a precise diagnostic rather than a generic
+        // "unexpected token" error.
+        let parse_error = parse_suite("case 1:\n    pass\n").err();
+        assert!(
+            matches!(
+                parse_error,
+                Some(ParseError {
+                    error: ParseErrorType::Lexical(LexicalErrorType::CaseOutsideMatch),
+                    ..
+                })
+            ),
+            "expected a case-outside-match error, got {parse_error:?}"
+        );
+    }
+
     #[test]
     fn test_variadic_generics() {
         let parse_ast = parse_suite(
@@ -1425,6 +2209,10 @@ foo[0]??
 foo[0][1]?
 foo.bar[0].baz[1]??
 foo.bar[0].baz[2].egg??
+
+# Help end magics assigned to a name
+bar = foo?
+bar = foo.bar[0]??
 "
             .trim(),
             Mode::Ipython,
@@ -1507,4 +2295,164 @@ u"foo" f"bar {baz} really" u"bar" "no"
         let parse_ast = parse_suite(r#"x = "\N{BACKSPACE}another cool trick""#).unwrap();
         insta::assert_debug_snapshot!(parse_ast);
     }
+
+    #[test]
+    fn recovery_keeps_well_formed_statements_around_a_broken_one() {
+        // The broken middle line is a syntax error that's still a single, self-contained logical
+        // line (no unclosed bracket swallowing the newlines after it), so it splits cleanly into
+        // its own chunk instead of absorbing the rest of the file.
+        let source = "x = 1\n1 +\ny = 2\n";
+        let recovered = parse_program_with_recovery(source);
+
+        assert_eq!(recovered.errors.len(), 1);
+        assert_eq!(recovered.module.body.len(), 2);
+        assert!(recovered.module.body[0].is_assign_stmt());
+        assert!(recovered.module.body[1].is_assign_stmt());
+    }
+
+    #[test]
+    fn recovery_on_a_clean_file_matches_a_plain_parse() {
+        let source = "x = 1\ny = 2\n";
+        let recovered = parse_program_with_recovery(source);
+
+        assert!(recovered.errors.is_empty());
+        assert_eq!(recovered.module, parse_program(source).unwrap());
+    }
+
+    #[test]
+    fn recovery_with_options_stops_at_the_error_limit() {
+        let source = "1 +\n2 +\n3 +\n";
+        let options = ParseOptions::new(Mode::Module).with_error_limit(1);
+        let recovered = parse_program_with_recovery_with_options(source, &options);
+
+        assert_eq!(recovered.errors.len(), 1);
+        assert!(recovered.module.body.is_empty());
+    }
+
+    #[test]
+    fn top_level_await_is_rejected_by_default() {
+        let source = "await foo()\n";
+        let err = parse_with_options(source, &ParseOptions::new(Mode::Module)).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ParseErrorType::Lexical(LexicalErrorType::OtherError(_))
+        ));
+    }
+
+    #[test]
+    fn top_level_await_is_allowed_when_opted_in() {
+        let source = "await foo()\n";
+        let options = ParseOptions::new(Mode::Module).with_top_level_await(true);
+        assert!(parse_with_options(source, &options).is_ok());
+    }
+
+    #[test]
+    fn await_inside_an_async_function_is_always_allowed() {
+        let source = "async def f():\n    await foo()\n";
+        assert!(parse_with_options(source, &ParseOptions::new(Mode::Module)).is_ok());
+    }
+
+    #[test]
+    fn match_statement_is_rejected_before_its_target_version() {
+        let source = "match command:\n    case \"go\":\n        pass\n";
+        let options = ParseOptions::new(Mode::Module).with_target_version(PythonVersion::Py39);
+        let err = parse_with_options(source, &options).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ParseErrorType::Lexical(LexicalErrorType::OtherError(_))
+        ));
+    }
+
+    #[test]
+    fn match_statement_is_allowed_on_its_target_version() {
+        let source = "match command:\n    case \"go\":\n        pass\n";
+        let options = ParseOptions::new(Mode::Module).with_target_version(PythonVersion::Py310);
+        assert!(parse_with_options(source, &options).is_ok());
+    }
+
+    #[test]
+    fn except_star_is_rejected_before_its_target_version() {
+        let source = "try:\n    pass\nexcept* ValueError:\n    pass\n";
+        let options = ParseOptions::new(Mode::Module).with_target_version(PythonVersion::Py310);
+        let err = parse_with_options(source, &options).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ParseErrorType::Lexical(LexicalErrorType::OtherError(_))
+        ));
+    }
+
+    #[test]
+    fn type_alias_is_rejected_before_its_target_version() {
+        let source = "type Alias = int\n";
+        let options = ParseOptions::new(Mode::Module).with_target_version(PythonVersion::Py311);
+        let err = parse_with_options(source, &options).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ParseErrorType::Lexical(LexicalErrorType::OtherError(_))
+        ));
+    }
+
+    #[test]
+    fn generic_function_type_params_are_rejected_before_their_target_version() {
+        let source = "def first[T](items: list[T]) -> T:\n    return items[0]\n";
+        let options = ParseOptions::new(Mode::Module).with_target_version(PythonVersion::Py311);
+        let err = parse_with_options(source, &options).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ParseErrorType::Lexical(LexicalErrorType::OtherError(_))
+        ));
+    }
+
+    #[test]
+    fn walrus_operator_is_allowed_on_the_default_target_version() {
+        let source = "if (n := len(items)) > 0:\n    pass\n";
+        assert!(parse_with_options(source, &ParseOptions::new(Mode::Module)).is_ok());
+    }
+
+    #[test]
+    fn parse_interactive_completes_a_well_formed_statement() {
+        assert!(matches!(
+            parse_interactive("x = 1 + 2"),
+            InteractiveParseOutcome::Complete(_)
+        ));
+    }
+
+    #[test]
+    fn parse_interactive_reports_incomplete_for_a_header_without_a_body() {
+        assert!(matches!(
+            parse_interactive("if x:\n"),
+            InteractiveParseOutcome::Incomplete
+        ));
+    }
+
+    #[test]
+    fn parse_interactive_reports_incomplete_for_input_that_ends_mid_expression() {
+        assert!(matches!(
+            parse_interactive("x = 1 +"),
+            InteractiveParseOutcome::Incomplete
+        ));
+    }
+
+    #[test]
+    fn parse_interactive_reports_a_syntax_error_that_more_input_cannot_fix() {
+        assert!(matches!(
+            parse_interactive("def 1():\n    pass\n"),
+            InteractiveParseOutcome::SyntaxError(_)
+        ));
+    }
+
+    #[test]
+    fn parse_starts_at_produces_ranges_relative_to_the_real_offset() {
+        // A sub-parse of just the annotation, as it would appear when re-parsing a single
+        // argument's annotation out of a larger, already-parsed source.
+        let offset = TextSize::from(400);
+        let source = "int";
+        let Mod::Expression(expression) =
+            parse_starts_at(source, Mode::Expression, offset).unwrap()
+        else {
+            panic!("expected an expression");
+        };
+        let len = TextSize::try_from(source.len()).unwrap();
+        assert_eq!(expression.body.range(), TextRange::at(offset, len));
+    }
 }
@@ -0,0 +1,86 @@
+//! A flat, owned iterator over every node in a parsed module's body, in source order.
+//!
+//! [`ruff_python_ast::visitor::preorder::PreorderVisitor`] already does the traversal; the
+//! trouble is it's callback-shaped, so every rule author who wants "every node in source order"
+//! ends up hand-rolling the same recursive walk, and a grammar addition that needs a new
+//! `visit_*` override is easy to miss when there's no single place collecting the result. This
+//! module runs that walk once and hands back a plain [`Vec`] iterator instead, so nothing short
+//! of [`PreorderVisitor`] itself needs updating when the grammar grows a node kind.
+//!
+//! Collecting eagerly, rather than yielding lazily as the walk proceeds, mirrors
+//! [`crate::event_stream`]'s approach to the same kind of problem: a typed AST has no built-in
+//! suspend point to drive a lazy iterator from, short of writing a generator by hand.
+
+use ruff_python_ast::visitor::preorder::{PreorderVisitor, TraversalSignal};
+use ruff_python_ast::{AnyNodeRef, Stmt};
+
+/// Returns every statement, expression, pattern, and f-string element in `body`, in source order.
+pub fn preorder(body: &[Stmt]) -> std::vec::IntoIter<AnyNodeRef> {
+    let mut collector = Collector { nodes: Vec::new() };
+    for stmt in body {
+        collector.visit_stmt(stmt);
+    }
+    collector.nodes.into_iter()
+}
+
+struct Collector<'a> {
+    nodes: Vec<AnyNodeRef<'a>>,
+}
+
+impl<'a> PreorderVisitor<'a> for Collector<'a> {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        self.nodes.push(node);
+        TraversalSignal::Traverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::{AnyNodeRef, Mod};
+
+    use super::preorder;
+    use crate::{parse, Mode};
+
+    fn module_body(source: &str) -> Vec<ruff_python_ast::Stmt> {
+        match parse(source, Mode::Module).unwrap() {
+            Mod::Module(module) => module.body,
+            Mod::Expression(_) | Mod::FunctionType(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn yields_the_statement_then_its_expressions() {
+        let body = module_body("x = 1\n");
+        let kinds: Vec<_> = preorder(&body).map(|node| node.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ruff_python_ast::NodeKind::StmtAssign,
+                ruff_python_ast::NodeKind::ExprName,
+                ruff_python_ast::NodeKind::ExprNumberLiteral,
+            ]
+        );
+    }
+
+    #[test]
+    fn descends_into_nested_suites() {
+        let body = module_body("if True:\n    pass\n");
+        let kinds: Vec<_> = preorder(&body).map(|node| node.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ruff_python_ast::NodeKind::StmtIf,
+                ruff_python_ast::NodeKind::ExprBooleanLiteral,
+                ruff_python_ast::NodeKind::StmtPass,
+            ]
+        );
+    }
+
+    #[test]
+    fn yields_match_patterns() {
+        let body = module_body("match x:\n    case 1:\n        pass\n");
+        let has_pattern =
+            preorder(&body).any(|node| matches!(node, AnyNodeRef::PatternMatchValue(_)));
+        assert!(has_pattern);
+    }
+}
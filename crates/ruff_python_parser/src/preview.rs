@@ -0,0 +1,25 @@
+//! Feature flags for not-yet-released Python syntax.
+//!
+//! [`MinVersion`](crate::cpython_ast::MinVersion) gates syntax CPython has already shipped,
+//! behind the earliest version that accepts it. [`PreviewFeatures`] is the opposite: it gates
+//! syntax this project is still experimenting with (a PEP still in draft, a prototype grammar
+//! change) behind an explicit opt-in, independent of which Python version the caller is
+//! targeting. That separation lets experimental grammar work land and be exercised by tests
+//! without changing what any released version of ruff accepts by default.
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// Which not-yet-released pieces of Python syntax the lexer and parser should accept, on top
+    /// of whatever a [`Mode`](crate::Mode) and target version already allow.
+    ///
+    /// Defaults to [`PreviewFeatures::empty`]: with no flags set, behavior is unchanged from a
+    /// caller that never heard of this type.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct PreviewFeatures: u8 {
+        /// Accept t-string literals (`t"..."`), the template-string literal PEP currently in
+        /// draft. Not yet wired into the lexer or grammar; setting this flag has no effect until
+        /// that support lands.
+        const T_STRINGS = 1 << 0;
+    }
+}
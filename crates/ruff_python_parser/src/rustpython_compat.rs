@@ -0,0 +1,70 @@
+//! A migration shim for projects moving off `rustpython-parser`, this crate's ancestor.
+//!
+//! This crate's own API has moved on since the fork -- `parse`/`parse_expression` return this
+//! crate's [`Mod`]/[`Expr`] rather than re-exporting `rustpython-parser`'s now-unmaintained AST,
+//! and the old top-level [`Program`] wrapper around a parsed module's statements was dropped in
+//! favor of matching on [`Mod::Module`] directly. This module re-creates just that last piece --
+//! [`Program`] and [`parse_program`] -- so a caller can swap its `use rustpython_parser::...`
+//! lines for `use ruff_python_parser::rustpython_compat::...` and keep building while it migrates
+//! the rest of its code at its own pace, behind the `rustpython-compat` feature so nobody pays for
+//! it who isn't migrating.
+//!
+//! Errors are reported as this crate's own [`ParseError`] rather than `rustpython-parser`'s,
+//! since the two crates already agree on the shape (a message plus a [`TextSize`](ruff_text_size::TextSize)
+//! offset) and introducing a second error type here would just give callers two ways to match on
+//! the same failure.
+
+use ruff_python_ast::Suite;
+
+use crate::{parse, Mode, ParseError};
+
+/// Mirrors `rustpython_parser::ast::Program`: the statements of a parsed module, with no wrapper
+/// for the module itself.
+///
+/// `rustpython_parser::ast::Program` never exposed the parsed source's comments -- a caller after
+/// suppression comments, a license header, or doc comments had to re-lex the source itself to get
+/// at them. This shim doesn't add that back either: [`crate::comments::collect_comments`] already
+/// answers "what comments go with this statement" for any parsed body, [`Program::statements`]
+/// included, so there's no reason to duplicate a weaker version of it here. The same goes for the
+/// token stream a `Program` was parsed from: [`crate::parse_program_with_tokens`] already answers
+/// that for the real [`ruff_python_ast::ModModule`] this shim wraps, for finding the node at an
+/// offset: [`crate::node_at_offset::node_at_offset`] already takes a plain `&[Stmt]`, and for a
+/// preorder walk: [`crate::preorder::preorder`] does too -- all three work on
+/// [`Program::statements`] directly without a wrapper method here.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Program {
+    pub statements: Suite,
+}
+
+/// Parses `source` as a full program, the way `rustpython_parser::parser::parse_program` did.
+///
+/// For anything other than a whole module -- a single expression, an IPython cell -- use [`parse`]
+/// directly instead; `rustpython-parser` never had a `Program`-shaped entry point for those either.
+pub fn parse_program(source: &str) -> Result<Program, ParseError> {
+    match parse(source, Mode::Module)? {
+        ruff_python_ast::Mod::Module(module) => Ok(Program {
+            statements: module.body,
+        }),
+        ruff_python_ast::Mod::Expression(_) | ruff_python_ast::Mod::FunctionType(_) => {
+            unreachable!("Mode::Module doesn't return other variant")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_program;
+
+    #[test]
+    fn parses_a_program_into_statements() {
+        let program = parse_program("x = 1\ny = 2\n").unwrap();
+        assert_eq!(program.statements.len(), 2);
+    }
+
+    #[test]
+    fn surfaces_this_crate_s_own_parse_error() {
+        let error = parse_program("x =").unwrap_err();
+        assert!(!error.error.to_string().is_empty());
+    }
+}
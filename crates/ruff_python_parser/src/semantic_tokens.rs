@@ -0,0 +1,277 @@
+//! Classifies each token in a source file the way an editor's semantic highlighter would.
+//!
+//! [`SemanticTokenKind`] is coarser than [`TokenKind`] in some places (every arithmetic and
+//! comparison operator collapses to [`SemanticTokenKind::Operator`]) and finer in others (a
+//! single [`Tok::String`] token splits into a prefix and a body, and a `{`/`}` pair gets its own
+//! kind when it delimits an f-string replacement field rather than a dict or set literal). None
+//! of this is recoverable from [`TokenKind`] alone, which is why this lives as its own pass
+//! instead of a method on it.
+//!
+//! The pass only needs two bits of context beyond the raw [`Tok`] stream: whether the current
+//! token starts a logical line (to tell a decorator's `@` apart from matrix multiplication) and
+//! the bracket-nesting depth at which each currently open f-string began (to tell a replacement
+//! field's braces apart from a literal dict or set nested inside one). Both are cheap to track
+//! alongside the lexer's own output, so this never needs the parser itself.
+
+use ruff_text_size::TextRange;
+
+use crate::{
+    lexer::LexResult,
+    token::{StringKind, Tok, TokenKind},
+};
+
+/// A token's role for editor semantic highlighting. See the [module docs](self) for how this
+/// differs from [`TokenKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticTokenKind {
+    /// A hard keyword, e.g. `def`, `if`, `return`.
+    Keyword,
+    /// A soft keyword used as a keyword in this position, e.g. the `match` in `match x:`.
+    /// [`crate::soft_keywords::SoftKeywordTransformer`] has already turned soft keywords used as
+    /// identifiers into [`Tok::Name`] by the time this pass runs, so every soft keyword token
+    /// that reaches here is being used as one.
+    SoftKeyword,
+    /// One of the builtin singleton constants, `True`, `False`, or `None`.
+    BuiltinConstant,
+    /// An integer, float, or complex literal.
+    Number,
+    /// The prefix of a string or f-string literal, e.g. the `rb` in `rb"..."` or the `f` in
+    /// `f"..."`.
+    StringPrefix,
+    /// The quotes and contents of a string or f-string literal, excluding its prefix.
+    StringBody,
+    /// A `{` or `}` that delimits an f-string replacement field or a nested format spec
+    /// expression, as opposed to a dict or set literal nested inside one.
+    FStringDelimiter,
+    /// The `@` that introduces a decorator line, as opposed to the matrix multiplication
+    /// operator.
+    Decorator,
+    /// An operator or punctuation token not covered by a more specific kind above.
+    Operator,
+    /// Anything else: names, comments, and structural tokens (newlines, indentation, end of
+    /// file) that an editor wouldn't highlight.
+    Other,
+}
+
+/// Classifies every token lexed from `tokens`, pairing each with its range. A single lexed token
+/// can produce more than one entry (a prefixed string literal splits into its prefix and body),
+/// so this isn't a one-to-one zip with `tokens`.
+///
+/// `tokens` is expected to come from [`crate::lexer::lex`] (or [`crate::tokenize`]), i.e. after
+/// soft keywords have already been resolved to either a keyword token or [`Tok::Name`].
+pub fn classify_tokens<I>(tokens: I) -> Vec<(SemanticTokenKind, TextRange)>
+where
+    I: IntoIterator<Item = LexResult>,
+{
+    let mut classifier = SemanticTokenClassifier::default();
+    let mut classified = Vec::new();
+    for (tok, range) in tokens.into_iter().filter_map(Result::ok) {
+        classifier.classify(&tok, range, &mut classified);
+    }
+    classified
+}
+
+/// Incremental classifier underlying [`classify_tokens`], for callers that want to classify
+/// tokens as they're lexed instead of collecting them all first.
+pub struct SemanticTokenClassifier {
+    /// Current bracket-nesting depth, counting all of `()`, `[]`, and `{}`. Mirrors the lexer's
+    /// own nesting counter.
+    nesting: u32,
+    /// `true` for the single token at the start of a logical line, where a decorator's `@` can
+    /// appear. Starts out `true`: the very first token lexed is always at the start of a line.
+    at_start_of_line: bool,
+    /// The nesting depth at the time each currently open f-string was entered, one entry per
+    /// nested f-string. A `{`/`}` is an f-string delimiter exactly when it's lexed while
+    /// `nesting` equals the top of this stack.
+    fstring_base_nesting: Vec<u32>,
+}
+
+impl Default for SemanticTokenClassifier {
+    fn default() -> Self {
+        Self {
+            nesting: 0,
+            at_start_of_line: true,
+            fstring_base_nesting: Vec::new(),
+        }
+    }
+}
+
+impl SemanticTokenClassifier {
+    /// Classifies a single token, appending one or more `(kind, range)` pairs to `out`, and
+    /// updates the classifier's tracked position for the next token.
+    pub fn classify(
+        &mut self,
+        tok: &Tok,
+        range: TextRange,
+        out: &mut Vec<(SemanticTokenKind, TextRange)>,
+    ) {
+        match tok {
+            Tok::String { kind, .. } => {
+                let (prefix, body) = split_string_token(*kind, range);
+                if !prefix.is_empty() {
+                    out.push((SemanticTokenKind::StringPrefix, prefix));
+                }
+                out.push((SemanticTokenKind::StringBody, body));
+            }
+            _ => out.push((self.classify_single(tok), range)),
+        }
+
+        self.at_start_of_line = matches!(
+            tok,
+            Tok::StartModule
+                | Tok::StartExpression
+                | Tok::StartFunctionType
+                | Tok::Newline
+                | Tok::Indent
+                | Tok::Dedent
+        );
+    }
+
+    /// Classifies a token that always maps to exactly one [`SemanticTokenKind`] (every token
+    /// except [`Tok::String`], which [`Self::classify`] splits into a prefix and a body).
+    fn classify_single(&mut self, tok: &Tok) -> SemanticTokenKind {
+        match tok {
+            Tok::FStringStart => {
+                self.fstring_base_nesting.push(self.nesting);
+                SemanticTokenKind::StringPrefix
+            }
+            Tok::FStringMiddle { .. } | Tok::FStringEnd => SemanticTokenKind::StringBody,
+            Tok::Int { .. } | Tok::Float { .. } | Tok::Complex { .. } => SemanticTokenKind::Number,
+            Tok::True | Tok::False | Tok::None => SemanticTokenKind::BuiltinConstant,
+            Tok::At if self.at_start_of_line => SemanticTokenKind::Decorator,
+            Tok::Lpar | Tok::Lsqb => {
+                self.nesting += 1;
+                SemanticTokenKind::Operator
+            }
+            Tok::Rpar | Tok::Rsqb => {
+                self.nesting = self.nesting.saturating_sub(1);
+                SemanticTokenKind::Operator
+            }
+            Tok::Lbrace => {
+                let is_delimiter = self.fstring_base_nesting.last() == Some(&self.nesting);
+                self.nesting += 1;
+                if is_delimiter {
+                    SemanticTokenKind::FStringDelimiter
+                } else {
+                    SemanticTokenKind::Operator
+                }
+            }
+            Tok::Rbrace => {
+                self.nesting = self.nesting.saturating_sub(1);
+                if self.fstring_base_nesting.last() == Some(&self.nesting) {
+                    SemanticTokenKind::FStringDelimiter
+                } else {
+                    SemanticTokenKind::Operator
+                }
+            }
+            _ => {
+                let kind = TokenKind::from_token(tok);
+                if kind.is_soft_keyword() {
+                    SemanticTokenKind::SoftKeyword
+                } else if kind.is_keyword() {
+                    SemanticTokenKind::Keyword
+                } else if kind.is_operator() {
+                    SemanticTokenKind::Operator
+                } else {
+                    SemanticTokenKind::Other
+                }
+            }
+        }
+    }
+}
+
+/// Splits a [`Tok::String`] token's range into its prefix (e.g. `rb` in `rb"..."`) and its body
+/// (the quotes and contents). The prefix range is empty, at the start of `range`, for a string
+/// with no prefix at all.
+pub fn split_string_token(kind: StringKind, range: TextRange) -> (TextRange, TextRange) {
+    let prefix_end = range.start() + kind.prefix_len();
+    (
+        TextRange::new(range.start(), prefix_end),
+        TextRange::new(prefix_end, range.end()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer::lex, Mode};
+
+    fn classify_source(source: &str) -> Vec<SemanticTokenKind> {
+        classify_tokens(lex(source, Mode::Module))
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_keyword_and_soft_keyword() {
+        let kinds = classify_source("match x:\n    case 1:\n        pass\n");
+        assert_eq!(kinds[0], SemanticTokenKind::SoftKeyword); // match
+        assert!(kinds.contains(&SemanticTokenKind::SoftKeyword)); // case
+        assert!(kinds.contains(&SemanticTokenKind::Keyword)); // pass
+    }
+
+    #[test]
+    fn test_match_as_identifier_is_not_a_keyword() {
+        let kinds = classify_source("match = 1\n");
+        assert_eq!(kinds[0], SemanticTokenKind::Other);
+    }
+
+    #[test]
+    fn test_builtin_constants() {
+        let kinds = classify_source("x = True or False or None\n");
+        assert_eq!(
+            kinds
+                .iter()
+                .filter(|k| **k == SemanticTokenKind::BuiltinConstant)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_decorator_at_vs_matrix_multiplication() {
+        let kinds = classify_source("@dec\ndef f(): ...\nx = a @ b\n");
+        assert_eq!(kinds[0], SemanticTokenKind::Decorator);
+        assert!(!kinds
+            .iter()
+            .skip(1)
+            .any(|k| *k == SemanticTokenKind::Decorator));
+        assert!(kinds.contains(&SemanticTokenKind::Operator));
+    }
+
+    #[test]
+    fn test_fstring_delimiter_vs_nested_dict_braces() {
+        let kinds = classify_source(r#"f"{ {1: 2}['a'] }""#);
+        let delimiters = kinds
+            .iter()
+            .filter(|k| **k == SemanticTokenKind::FStringDelimiter)
+            .count();
+        // Only the outer `{` and `}` of the replacement field are delimiters; the dict
+        // literal's own braces are plain operators.
+        assert_eq!(delimiters, 2);
+        assert!(kinds.contains(&SemanticTokenKind::Operator));
+    }
+
+    #[test]
+    fn test_string_prefix_and_body_split() {
+        let kinds = classify_source(r#"rb"data""#);
+        assert_eq!(
+            kinds,
+            vec![
+                SemanticTokenKind::StringPrefix,
+                SemanticTokenKind::StringBody,
+                SemanticTokenKind::Other, // Newline
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unprefixed_string_has_no_prefix_token() {
+        let kinds = classify_source(r#""data""#);
+        assert_eq!(
+            kinds,
+            vec![SemanticTokenKind::StringBody, SemanticTokenKind::Other]
+        );
+    }
+}
@@ -85,10 +85,17 @@ where
                             }
                             first = false;
                         }
-                        if !seen_colon {
+                        if seen_colon {
+                            #[cfg(feature = "coverage")]
+                            crate::coverage::record("soft_keyword:match_case_as_keyword");
+                        } else {
+                            #[cfg(feature = "coverage")]
+                            crate::coverage::record("soft_keyword:match_case_as_identifier");
                             next = Some(Ok((soft_to_name(tok), *range)));
                         }
                     } else {
+                        #[cfg(feature = "coverage")]
+                        crate::coverage::record("soft_keyword:match_case_as_identifier");
                         next = Some(Ok((soft_to_name(tok), *range)));
                     }
                 }
@@ -128,10 +135,17 @@ where
                                 }
                             }
                         }
-                        if !is_type_alias {
+                        if is_type_alias {
+                            #[cfg(feature = "coverage")]
+                            crate::coverage::record("soft_keyword:type_as_keyword");
+                        } else {
+                            #[cfg(feature = "coverage")]
+                            crate::coverage::record("soft_keyword:type_as_identifier");
                             next = Some(Ok((soft_to_name(tok), *range)));
                         }
                     } else {
+                        #[cfg(feature = "coverage")]
+                        crate::coverage::record("soft_keyword:type_as_identifier");
                         next = Some(Ok((soft_to_name(tok), *range)));
                     }
                 }
@@ -0,0 +1,104 @@
+//! A single entry point for the handful of ways this crate's callers already preprocess source
+//! before calling into it: picking a [`Mode`], and for notebooks, splitting and reassembling
+//! cells around [`notebook::parse_notebook_cells`]. [`SourceKind`] bundles a kind of input with
+//! its source, and [`SourceKind::parse`] dispatches to whichever of those paths applies, so a
+//! caller juggling several source kinds doesn't have to re-derive the right combination of `Mode`
+//! and pre/post-processing at each call site.
+//!
+//! This does not attempt to unify the *shape* of the result: parsing a notebook produces one
+//! [`Mod`] per cell plus an offset map, not a single tree, so [`ParsedSource`] keeps that
+//! distinction visible rather than forcing both shapes through a single `Vec`.
+
+use crate::notebook::{parse_notebook_cells, NotebookCellOffsets};
+use crate::{parse, parse_expression, Mode, Mod, ParseError};
+
+/// A source to parse, tagged with how it should be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind<'a> {
+    /// A full Python module, as in a regular `.py` file.
+    Module(&'a str),
+    /// A single Python expression, as accepted by [`parse_expression`].
+    Expression(&'a str),
+    /// A type stub, as in a `.pyi` file. Stubs are parsed like a module; this variant exists so
+    /// callers can tag the source's origin without having to separately track a [`PySourceType`]
+    /// alongside it.
+    ///
+    /// [`PySourceType`]: ruff_python_ast::PySourceType
+    Stub(&'a str),
+    /// A Jupyter notebook's code cells, already extracted from its JSON and listed in document
+    /// order, as accepted by [`parse_notebook_cells`].
+    Notebook(&'a [&'a str]),
+}
+
+/// The result of [`SourceKind::parse`]: either a single parsed tree, or, for
+/// [`SourceKind::Notebook`], one parsed tree per cell plus the offsets each cell occupies in the
+/// hypothetical joined source those ranges are relative to.
+#[derive(Debug)]
+pub enum ParsedSource {
+    Single(Result<Mod, ParseError>),
+    Notebook(Vec<Result<Mod, ParseError>>, NotebookCellOffsets),
+}
+
+impl SourceKind<'_> {
+    /// Parses `self` using whichever combination of [`Mode`] and pre/post-processing its variant
+    /// requires.
+    pub fn parse(self) -> ParsedSource {
+        match self {
+            SourceKind::Module(source) | SourceKind::Stub(source) => {
+                ParsedSource::Single(parse(source, Mode::Module))
+            }
+            SourceKind::Expression(source) => {
+                ParsedSource::Single(parse_expression(source).map(|expr| {
+                    Mod::Expression(ruff_python_ast::ModExpression {
+                        range: ruff_text_size::Ranged::range(&expr),
+                        body: Box::new(expr),
+                    })
+                }))
+            }
+            SourceKind::Notebook(cells) => {
+                let (results, offsets) = parse_notebook_cells(cells);
+                ParsedSource::Notebook(results, offsets)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParsedSource, SourceKind};
+
+    #[test]
+    fn parses_a_module() {
+        let ParsedSource::Single(result) = SourceKind::Module("x = 1").parse() else {
+            panic!("expected a single parsed tree");
+        };
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn parses_an_expression() {
+        let ParsedSource::Single(result) = SourceKind::Expression("1 + 2").parse() else {
+            panic!("expected a single parsed tree");
+        };
+        assert!(result.unwrap().is_expression());
+    }
+
+    #[test]
+    fn parses_a_stub_like_a_module() {
+        let ParsedSource::Single(result) = SourceKind::Stub("x: int").parse() else {
+            panic!("expected a single parsed tree");
+        };
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn parses_a_notebook_cell_by_cell() {
+        let cells = ["x = 1", "y = 2"];
+        let ParsedSource::Notebook(results, offsets) = SourceKind::Notebook(&cells).parse() else {
+            panic!("expected per-cell parsed trees");
+        };
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(offsets.cell_range(0).unwrap().0, ruff_text_size::TextSize::new(0));
+    }
+}
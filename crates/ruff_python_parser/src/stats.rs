@@ -0,0 +1,80 @@
+//! Per-parse counters, for performance work and capacity planning that would otherwise need an
+//! external heap profiler.
+//!
+//! [`parse_with_stats`] reports the number of tokens the lexer produced, the number of AST nodes
+//! the parser built, and the number of bytes of string/bytes/f-string literal content that were
+//! copied out of the source while building those nodes. There's no `recovery_skips` counter:
+//! the grammar doesn't have a recovery loop today (see the module docs on [`crate::parser`]) — a
+//! parse either succeeds or stops at the first syntax error — so there's nothing to count yet.
+//! Once synth-4433's nesting-limit recovery or a real multi-error recovery loop lands, this is
+//! where that counter belongs.
+//!
+//! This isn't exposed on a `Program` bundle type because no such type exists in this crate yet;
+//! [`ParserStats`] is returned alongside the parsed module instead, and can be folded into a
+//! bundle type once one exists.
+
+use ruff_python_ast::visitor::preorder::{PreorderVisitor, TraversalSignal};
+use ruff_python_ast::{AnyNodeRef, Mod};
+
+use crate::{parse_tokens, tokenize_all, Mode, ParseError};
+
+/// Counters collected while parsing a single source file. See the [module docs](self) for what
+/// each field means and why `recovery_skips` isn't here (yet).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParserStats {
+    /// Number of tokens the lexer produced, including the end-of-file marker.
+    pub tokens_produced: usize,
+    /// Number of AST nodes the parser allocated.
+    pub nodes_allocated: usize,
+    /// Number of bytes of string, bytes, and f-string literal content copied out of the source
+    /// while decoding escape sequences.
+    pub string_bytes_copied: usize,
+}
+
+/// Parses `source` in the given `mode`, same as [`crate::parse`], but also returns [`ParserStats`]
+/// for the parse.
+pub fn parse_with_stats(source: &str, mode: Mode) -> Result<(Mod, ParserStats), ParseError> {
+    let tokens = tokenize_all(source, mode);
+    let tokens_produced = tokens.len();
+
+    let module = parse_tokens(tokens, source, mode)?;
+
+    let mut counter = NodeCounter::default();
+    counter.visit_mod(&module);
+
+    Ok((
+        module,
+        ParserStats {
+            tokens_produced,
+            nodes_allocated: counter.nodes_allocated,
+            string_bytes_copied: counter.string_bytes_copied,
+        },
+    ))
+}
+
+#[derive(Default)]
+struct NodeCounter {
+    nodes_allocated: usize,
+    string_bytes_copied: usize,
+}
+
+impl<'a> PreorderVisitor<'a> for NodeCounter {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        self.nodes_allocated += 1;
+
+        match node {
+            AnyNodeRef::StringLiteral(literal) => {
+                self.string_bytes_copied += literal.value.len();
+            }
+            AnyNodeRef::BytesLiteral(literal) => {
+                self.string_bytes_copied += literal.value.len();
+            }
+            AnyNodeRef::FStringLiteralElement(literal) => {
+                self.string_bytes_copied += literal.value.len();
+            }
+            _ => {}
+        }
+
+        TraversalSignal::Traverse
+    }
+}
@@ -306,6 +306,45 @@ impl<'a> StringParser<'a> {
     }
 }
 
+/// Decodes the escape sequences in `raw`, the body of a non-bytes string literal with its prefix
+/// and quotes already stripped, the same way the parser does while building a [`ast::StringLiteral`].
+///
+/// [`parse_string_literal`] always decodes eagerly, since the AST has nowhere to stash an
+/// undecoded body. This is the on-demand counterpart: a syntax-only consumer that doesn't want to
+/// pay decoding cost for every string up front can hold onto the raw source slice instead and
+/// call this only for the strings it ends up needing the value of.
+pub fn decode_string_literal(
+    raw: &str,
+    kind: StringKind,
+    start: TextSize,
+) -> Result<String, LexicalError> {
+    let range = TextRange::at(start, raw.text_len());
+    match StringParser::new(raw, kind, start, range).parse_string()? {
+        StringType::Str(node) => Ok(node.value),
+        StringType::Bytes(_) | StringType::FString(_) => {
+            unreachable!("`parse_string` only ever returns `StringType::Str`")
+        }
+    }
+}
+
+/// Decodes the escape sequences in `raw`, the body of a bytes literal with its prefix and quotes
+/// already stripped, the same way the parser does while building a [`ast::BytesLiteral`].
+///
+/// See [`decode_string_literal`] for why this exists as a standalone, on-demand entry point.
+pub fn decode_bytes_literal(
+    raw: &str,
+    kind: StringKind,
+    start: TextSize,
+) -> Result<Vec<u8>, LexicalError> {
+    let range = TextRange::at(start, raw.text_len());
+    match StringParser::new(raw, kind, start, range).parse_bytes()? {
+        StringType::Bytes(node) => Ok(node.value),
+        StringType::Str(_) | StringType::FString(_) => {
+            unreachable!("`parse_bytes` only ever returns `StringType::Bytes`")
+        }
+    }
+}
+
 pub(crate) fn parse_string_literal(
     source: &str,
     kind: StringKind,
@@ -335,6 +374,14 @@ pub(crate) fn parse_fstring_literal_element(
     StringParser::new(source, kind, range.start(), range).parse_fstring_middle()
 }
 
+/// Merges the pieces of an implicitly concatenated string literal (`"a" "b"`, `b"a" b"b"`,
+/// `"a" f"{b}"`, ...) into a single expression.
+///
+/// Mixing bytes literals with str or f-string literals is rejected with
+/// [`MixedBytesAndNonBytesLiteral`](LexicalErrorType::MixedBytesAndNonBytesLiteral). This parser
+/// aborts on the first syntax error rather than recovering, so unlike a real concatenation the
+/// individual literals on either side of the mismatch aren't preserved in the tree for a fixer to
+/// inspect — only the location of the first literal that disagrees with the others is.
 pub(crate) fn concatenated_strings(
     strings: Vec<StringType>,
     range: TextRange,
@@ -354,11 +401,17 @@ pub(crate) fn concatenated_strings(
     let has_bytes = byte_literal_count > 0;
 
     if has_bytes && byte_literal_count < strings.len() {
+        // Point at the first literal whose bytes-ness disagrees with the first literal in the
+        // concatenation, rather than the start of the whole expression: in `"a" "b" b"c"`
+        // that's the `b"c"` at fault, not the leading `"a"`.
+        let first_is_bytes = matches!(strings[0], StringType::Bytes(_));
+        let odd_one_out = strings
+            .iter()
+            .find(|string| matches!(string, StringType::Bytes(_)) != first_is_bytes)
+            .expect("has_bytes && byte_literal_count < strings.len() implies a kind mismatch");
         return Err(LexicalError {
-            error: LexicalErrorType::OtherError(
-                "cannot mix bytes and nonbytes literals".to_owned(),
-            ),
-            location: range.start(),
+            error: LexicalErrorType::MixedBytesAndNonBytesLiteral,
+            location: odd_one_out.start(),
         });
     }
 
@@ -427,6 +480,7 @@ impl From<FStringError> for LexicalError {
 
 /// Represents the different types of errors that can occur during parsing of an f-string.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FStringErrorType {
     /// Expected a right brace after an opened left brace.
     UnclosedLbrace,
@@ -525,6 +579,22 @@ mod tests {
         insta::assert_debug_snapshot!(parse_ast);
     }
 
+    #[test]
+    fn test_parse_fstring_format_spec_with_multiple_nested_fields() {
+        let source = r#"f"{x:{width}.{prec}}""#;
+        let parse_ast = parse_suite(source).unwrap();
+
+        insta::assert_debug_snapshot!(parse_ast);
+    }
+
+    #[test]
+    fn test_parse_fstring_deeply_nested_format_spec() {
+        let source = r#"f"{x:{a:{b}}}""#;
+        let parse_ast = parse_suite(source).unwrap();
+
+        insta::assert_debug_snapshot!(parse_ast);
+    }
+
     #[test]
     fn test_parse_fstring_not_nested_spec() {
         let source = r#"f"{foo:spec}""#;
@@ -562,6 +632,19 @@ mod tests {
         insta::assert_debug_snapshot!(parse_ast);
     }
 
+    #[test]
+    fn test_mixed_bytes_and_str_concatenation_points_at_odd_one_out() {
+        let source = r#""a" "b" b"c""#;
+        let error = parse_suite(source).expect_err("Expected error");
+
+        assert_eq!(
+            error.error,
+            ParseErrorType::Lexical(LexicalErrorType::MixedBytesAndNonBytesLiteral)
+        );
+        // The `b"c"` literal is what disagrees with the rest, not the start of `"a"`.
+        assert_eq!(error.offset, TextSize::from(8));
+    }
+
     fn parse_fstring_error(source: &str) -> FStringErrorType {
         parse_suite(source)
             .map_err(|e| match e.error {
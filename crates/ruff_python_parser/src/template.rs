@@ -0,0 +1,165 @@
+//! Best-effort parsing of Python source that's embedded in a templating language, by masking out
+//! placeholder spans the caller identifies -- a Jinja `{{ user.name }}` expression, a
+//! `{% if ... %}` tag, or any other caller-supplied range -- before handing the source to the
+//! parser.
+//!
+//! This doesn't teach the grammar anything about template syntax; it works entirely by text
+//! substitution ahead of lexing. Each placeholder span is overwritten with same-length filler
+//! that lexes as a single opaque identifier, so every other span in the file keeps its original
+//! offset. The resulting tree can't tell you what was inside a placeholder -- callers who need
+//! that should parse those spans separately, as their own template language.
+//!
+//! # Example
+//!
+//! ```
+//! use ruff_python_parser::template::{find_delimited, parse_tolerant};
+//! use ruff_python_parser::Mode;
+//!
+//! let source = "{% if user.is_admin %}\nprint(1)\n{% endif %}\n";
+//! let placeholders = find_delimited(source, &[("{{", "}}"), ("{%", "%}")]);
+//! assert!(parse_tolerant(source, Mode::Module, &placeholders).is_ok());
+//! ```
+
+use ruff_python_ast::Mod;
+use ruff_text_size::{TextRange, TextSize};
+
+use crate::{parse, Mode, ParseError};
+
+/// Finds every non-overlapping `open ... close` span in `source`, trying each `(open, close)`
+/// pair in `delimiters` at every position and preferring the earliest match. Spans don't nest:
+/// once an `open` is matched, scanning resumes after its `close`, so any `open` found before that
+/// `close` is ignored rather than starting a nested span.
+///
+/// An unterminated `open` with no matching `close` is dropped rather than treated as a span
+/// running to the end of the source.
+pub fn find_delimited(source: &str, delimiters: &[(&str, &str)]) -> Vec<TextRange> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while offset < source.len() {
+        let Some((start, open_len, close)) = delimiters
+            .iter()
+            .filter_map(|&(open, close)| {
+                source[offset..]
+                    .find(open)
+                    .map(|i| (offset + i, open.len(), close))
+            })
+            .min_by_key(|&(start, ..)| start)
+        else {
+            break;
+        };
+        let search_from = start + open_len;
+        let Some(close_index) = source[search_from..].find(close) else {
+            break;
+        };
+        let end = search_from + close_index + close.len();
+        spans.push(TextRange::new(
+            TextSize::try_from(start).unwrap(),
+            TextSize::try_from(end).unwrap(),
+        ));
+        offset = end;
+    }
+    spans
+}
+
+/// Overwrites each of `placeholders` in `source` with same-length filler that lexes as a single
+/// identifier, so the result parses as plain Python with the placeholder's surrounding syntax
+/// (e.g. the `=` before a masked Jinja expression) left intact.
+///
+/// `placeholders` must be sorted and non-overlapping; [`find_delimited`]'s output already is.
+pub fn mask(source: &str, placeholders: &[TextRange]) -> String {
+    let mut out = source.to_string();
+    for &range in placeholders.iter().rev() {
+        out.replace_range(
+            std::ops::Range::<usize>::from(range),
+            &filler(range.len().into()),
+        );
+    }
+    out
+}
+
+/// Parses `source` in `mode` after masking `placeholders`, so placeholder spans are treated as
+/// opaque tokens instead of causing a syntax error.
+///
+/// The returned tree's offsets refer to the *masked* source, which has the same length as
+/// `source` at every placeholder span, so they remain valid offsets into `source` too -- just
+/// don't expect a placeholder's span to slice out meaningful Python.
+pub fn parse_tolerant(
+    source: &str,
+    mode: Mode,
+    placeholders: &[TextRange],
+) -> Result<Mod, ParseError> {
+    parse(&mask(source, placeholders), mode)
+}
+
+/// A run of `len` bytes that lexes as a single Python identifier: an underscore followed by as
+/// much of `PLACEHOLDER` as fits, padded with trailing underscores. Spans too short to hold any
+/// marker text fall back to plain underscores.
+fn filler(len: usize) -> String {
+    const MARKER: &str = "PLACEHOLDER";
+    if len <= 1 {
+        return "_".repeat(len);
+    }
+    let marker_len = MARKER.len().min(len - 1);
+    format!(
+        "_{}{}",
+        &MARKER[..marker_len],
+        "_".repeat(len - 1 - marker_len)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_text_size::TextRange;
+
+    use super::{filler, find_delimited, mask, parse_tolerant};
+    use crate::Mode;
+
+    #[test]
+    fn finds_a_single_delimited_span() {
+        let spans = find_delimited("x = {{ user.name }}\n", &[("{{", "}}")]);
+        assert_eq!(spans, vec![TextRange::new(4.into(), 19.into())]);
+    }
+
+    #[test]
+    fn tries_every_delimiter_pair_and_prefers_the_earliest_match() {
+        let source = "{% if x %}\nprint({{ y }})\n{% endif %}\n";
+        let spans = find_delimited(source, &[("{{", "}}"), ("{%", "%}")]);
+        let texts: Vec<_> = spans.iter().map(|&span| &source[span]).collect();
+        assert_eq!(texts, vec!["{% if x %}", "{{ y }}", "{% endif %}"]);
+    }
+
+    #[test]
+    fn an_unterminated_open_is_dropped() {
+        let spans = find_delimited("x = {{ y\n", &[("{{", "}}")]);
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn masking_preserves_length_and_offsets_outside_the_span() {
+        let source = "x = {{ user.name }};y = 1\n";
+        let spans = find_delimited(source, &[("{{", "}}")]);
+        let masked = mask(source, &spans);
+        assert_eq!(masked.len(), source.len());
+        assert_eq!(&masked[20..], &source[20..]);
+    }
+
+    #[test]
+    fn filler_is_a_valid_identifier_at_every_length() {
+        for len in 0..15 {
+            let text = filler(len);
+            assert_eq!(text.len(), len);
+            if len > 0 {
+                assert!(text.chars().all(|c| c == '_' || c.is_ascii_uppercase()));
+            }
+        }
+    }
+
+    #[test]
+    fn parses_a_jinja_if_tag_that_would_otherwise_fail() {
+        let source = "{% if user.is_admin %}\nprint(1)\n{% endif %}\n";
+        assert!(crate::parse(source, Mode::Module).is_err());
+
+        let placeholders = find_delimited(source, &[("{{", "}}"), ("{%", "%}")]);
+        assert!(parse_tolerant(source, Mode::Module, &placeholders).is_ok());
+    }
+}
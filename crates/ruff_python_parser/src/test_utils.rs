@@ -0,0 +1,29 @@
+//! Snapshot-testing helpers for downstream consumers, gated behind the `test_utils` feature.
+//!
+//! These wrap [`crate::parse`] and [`crate::ast_dump::dump`] so that rule authors and other
+//! crates that embed this parser can write `insta`-style snapshot tests against its output
+//! without copying the dump-or-report-the-error scaffolding this crate's own tests use.
+
+use ruff_python_ast::min_version::MinVersion;
+
+use crate::{ast_dump, parse, Mode};
+
+/// Parses `source` as a module and renders the result as a single stable string: the
+/// `ast.dump`-style tree on success, or the parse error's `Display` output on failure.
+///
+/// This is meant to be handed straight to `insta::assert_snapshot!`, so a syntax error shows up
+/// as a snapshot diff just like any other unexpected parser output would.
+pub fn parse_and_dump(source: &str) -> String {
+    parse_and_dump_with_version(source, MinVersion::PY312)
+}
+
+/// Like [`parse_and_dump`], but renders the dump as `ast.dump` would for the given
+/// `target_version`. Use this to snapshot version-gated syntax, such as a PEP 695 `type`
+/// statement under [`MinVersion::PY312`] versus an older target where it parses as a plain
+/// assignment.
+pub fn parse_and_dump_with_version(source: &str, target_version: MinVersion) -> String {
+    match parse(source, Mode::Module) {
+        Ok(module) => ast_dump::dump(&module, source, target_version),
+        Err(error) => error.to_string(),
+    }
+}
@@ -7,11 +7,12 @@
 use crate::Mode;
 
 use ruff_python_ast::{Int, IpyEscapeKind};
-use ruff_text_size::TextSize;
+use ruff_text_size::{TextRange, TextSize};
 use std::fmt;
 
 /// The set of tokens the Python source code can be tokenized in.
 #[derive(Clone, Debug, PartialEq, is_macro::Is)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tok {
     /// Token value for a name, commonly known as an identifier.
     Name {
@@ -221,6 +222,7 @@ pub enum Tok {
     // RustPython specific.
     StartModule,
     StartExpression,
+    StartFunctionType,
 }
 
 impl Tok {
@@ -228,8 +230,27 @@ impl Tok {
         match mode {
             Mode::Module | Mode::Ipython => Tok::StartModule,
             Mode::Expression => Tok::StartExpression,
+            Mode::FunctionType => Tok::StartFunctionType,
         }
     }
+
+    /// Returns `true` if this token's owned string is an exact, unmodified copy of the source
+    /// text at its full token range, as opposed to a normalized, decoded, or narrower form. For
+    /// example, [`Tok::String`] excludes the surrounding quotes from its `value`,
+    /// [`Tok::FStringMiddle`] collapses doubled `{{`/`}}` braces, and [`Tok::IpyEscapeCommand`]
+    /// strips and rewrites its leading escape characters, so none of those are verbatim.
+    ///
+    /// Callers that just want the token's text and already have its range and the source can use
+    /// [`verbatim_text`] instead of cloning the owned string out of a verbatim token.
+    pub const fn is_verbatim_text(&self) -> bool {
+        matches!(self, Tok::Name { .. } | Tok::Comment(_))
+    }
+}
+
+/// Returns the token's text as a slice borrowed from `source`, if `token` is
+/// [verbatim](Tok::is_verbatim_text), or `None` otherwise.
+pub fn verbatim_text<'src>(token: &Tok, range: TextRange, source: &'src str) -> Option<&'src str> {
+    token.is_verbatim_text().then(|| &source[range])
 }
 
 impl fmt::Display for Tok {
@@ -259,6 +280,7 @@ impl fmt::Display for Tok {
             Dedent => f.write_str("Dedent"),
             StartModule => f.write_str("StartProgram"),
             StartExpression => f.write_str("StartExpression"),
+            StartFunctionType => f.write_str("StartFunctionType"),
             EndOfFile => f.write_str("EOF"),
             Question => f.write_str("'?'"),
             Exclamation => f.write_str("'!'"),
@@ -361,6 +383,7 @@ impl fmt::Display for Tok {
 /// [String and Bytes literals]: https://docs.python.org/3/reference/lexical_analysis.html#string-and-bytes-literals
 /// [PEP 701]: https://peps.python.org/pep-0701/
 #[derive(PartialEq, Eq, Debug, Clone, Hash, Copy)] // TODO: is_macro::Is
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StringKind {
     /// A normal string literal with no prefix.
     String,
@@ -449,7 +472,8 @@ impl StringKind {
 }
 
 // TODO move to ruff_python_parser?
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenKind {
     /// Token value for a name, commonly known as an identifier.
     Name,
@@ -628,6 +652,7 @@ pub enum TokenKind {
     StartModule,
     StartInteractive,
     StartExpression,
+    StartFunctionType,
 }
 
 impl TokenKind {
@@ -790,9 +815,14 @@ impl TokenKind {
         )
     }
 
+    /// Returns `true` if this is one of Python's soft keywords (`match`, `case`, and `type`),
+    /// which [`crate::soft_keywords::SoftKeywordTransformer`] only ever reports as such when
+    /// they're used as keywords, not as identifiers. This is the single source of truth other
+    /// consumers (the parser's own dispatch, syntax highlighters, lint rules) should use instead
+    /// of re-deriving the soft keyword set themselves.
     #[inline]
     pub const fn is_soft_keyword(&self) -> bool {
-        matches!(self, TokenKind::Match | TokenKind::Case)
+        matches!(self, TokenKind::Match | TokenKind::Case | TokenKind::Type)
     }
 
     pub const fn from_token(token: &Tok) -> Self {
@@ -901,6 +931,7 @@ impl TokenKind {
             Tok::Yield => TokenKind::Yield,
             Tok::StartModule => TokenKind::StartModule,
             Tok::StartExpression => TokenKind::StartExpression,
+            Tok::StartFunctionType => TokenKind::StartFunctionType,
         }
     }
 }
@@ -0,0 +1,90 @@
+//! Conversions from a [`TokenKind`] to the AST operator type it denotes, for token-level tools
+//! (an operator-spacing lint, a token-stream pretty-printer) that want to map tokens to operators
+//! exactly as the grammar does without re-deriving the mapping themselves.
+//!
+//! [`ast::CmpOp`] has two variants, `NotIn` and `IsNot`, spelled with two tokens (`not in`,
+//! `is not`) rather than one, so it can't implement `TryFrom<TokenKind>` the way the other
+//! operator types do; [`token_kind_to_cmp_op`] covers the single-token variants instead.
+
+use ruff_python_ast as ast;
+
+use crate::TokenKind;
+
+/// Returned when a [`TokenKind`] doesn't denote the operator type being converted to.
+#[derive(Debug)]
+pub struct TokenKindNotAnOperator(TokenKind);
+
+impl std::fmt::Display for TokenKindNotAnOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?} is not a valid operator token", self.0)
+    }
+}
+
+impl std::error::Error for TokenKindNotAnOperator {}
+
+impl TryFrom<TokenKind> for ast::Operator {
+    type Error = TokenKindNotAnOperator;
+
+    fn try_from(kind: TokenKind) -> Result<Self, Self::Error> {
+        Ok(match kind {
+            TokenKind::Plus => ast::Operator::Add,
+            TokenKind::Minus => ast::Operator::Sub,
+            TokenKind::Star => ast::Operator::Mult,
+            TokenKind::At => ast::Operator::MatMult,
+            TokenKind::Slash => ast::Operator::Div,
+            TokenKind::Percent => ast::Operator::Mod,
+            TokenKind::DoubleStar => ast::Operator::Pow,
+            TokenKind::LeftShift => ast::Operator::LShift,
+            TokenKind::RightShift => ast::Operator::RShift,
+            TokenKind::Vbar => ast::Operator::BitOr,
+            TokenKind::CircumFlex => ast::Operator::BitXor,
+            TokenKind::Amper => ast::Operator::BitAnd,
+            TokenKind::DoubleSlash => ast::Operator::FloorDiv,
+            _ => return Err(TokenKindNotAnOperator(kind)),
+        })
+    }
+}
+
+impl TryFrom<TokenKind> for ast::UnaryOp {
+    type Error = TokenKindNotAnOperator;
+
+    fn try_from(kind: TokenKind) -> Result<Self, Self::Error> {
+        Ok(match kind {
+            TokenKind::Plus => ast::UnaryOp::UAdd,
+            TokenKind::Minus => ast::UnaryOp::USub,
+            TokenKind::Tilde => ast::UnaryOp::Invert,
+            TokenKind::Not => ast::UnaryOp::Not,
+            _ => return Err(TokenKindNotAnOperator(kind)),
+        })
+    }
+}
+
+impl TryFrom<TokenKind> for ast::BoolOp {
+    type Error = TokenKindNotAnOperator;
+
+    fn try_from(kind: TokenKind) -> Result<Self, Self::Error> {
+        Ok(match kind {
+            TokenKind::And => ast::BoolOp::And,
+            TokenKind::Or => ast::BoolOp::Or,
+            _ => return Err(TokenKindNotAnOperator(kind)),
+        })
+    }
+}
+
+/// Returns the [`ast::CmpOp`] that `kind` denotes on its own, or `None` if `kind` isn't a
+/// comparison token, or is one that only ever appears as half of a two-token comparison (`not`,
+/// the first half of `not in`; `is` is unambiguous and so is covered here, but pairing it with a
+/// following `not` to get [`ast::CmpOp::IsNot`] is left to the caller, same as `not in`).
+pub fn token_kind_to_cmp_op(kind: TokenKind) -> Option<ast::CmpOp> {
+    Some(match kind {
+        TokenKind::EqEqual => ast::CmpOp::Eq,
+        TokenKind::NotEqual => ast::CmpOp::NotEq,
+        TokenKind::Less => ast::CmpOp::Lt,
+        TokenKind::LessEqual => ast::CmpOp::LtE,
+        TokenKind::Greater => ast::CmpOp::Gt,
+        TokenKind::GreaterEqual => ast::CmpOp::GtE,
+        TokenKind::In => ast::CmpOp::In,
+        TokenKind::Is => ast::CmpOp::Is,
+        _ => return None,
+    })
+}
@@ -2,33 +2,57 @@ use crate::lexer::LexResult;
 use crate::Tok;
 use std::iter::FusedIterator;
 
+/// Either owns its tokens outright, or borrows them a caller is keeping around for something
+/// else (comment attachment, a lint pass that re-walks the stream after parsing, ...), cloning
+/// each one lazily as [`TokenSource`] is driven instead of cloning the whole slice into a
+/// throwaway `Vec` up front.
 #[derive(Clone, Debug)]
-pub(crate) struct TokenSource {
-    tokens: std::vec::IntoIter<LexResult>,
+enum TokenSourceKind<'a> {
+    Owned(std::vec::IntoIter<LexResult>),
+    Borrowed(std::iter::Cloned<std::slice::Iter<'a, LexResult>>),
 }
 
-impl TokenSource {
+#[derive(Clone, Debug)]
+pub(crate) struct TokenSource<'a> {
+    tokens: TokenSourceKind<'a>,
+}
+
+impl TokenSource<'static> {
     pub(crate) fn new(tokens: Vec<LexResult>) -> Self {
         Self {
-            tokens: tokens.into_iter(),
+            tokens: TokenSourceKind::Owned(tokens.into_iter()),
+        }
+    }
+}
+
+impl<'a> TokenSource<'a> {
+    /// Builds a token source over a borrowed slice of already-lexed tokens instead of an owned
+    /// `Vec`, for a caller that wants its tokens back afterwards and would otherwise have to
+    /// clone the whole slice into a new `Vec` just to hand it to [`new`](TokenSource::new).
+    pub(crate) fn from_slice(tokens: &'a [LexResult]) -> Self {
+        Self {
+            tokens: TokenSourceKind::Borrowed(tokens.iter().cloned()),
         }
     }
 }
 
-impl FromIterator<LexResult> for TokenSource {
+impl FromIterator<LexResult> for TokenSource<'static> {
     #[inline]
     fn from_iter<T: IntoIterator<Item = LexResult>>(iter: T) -> Self {
         Self::new(Vec::from_iter(iter))
     }
 }
 
-impl Iterator for TokenSource {
+impl<'a> Iterator for TokenSource<'a> {
     type Item = LexResult;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            let next = self.tokens.next()?;
+            let next = match &mut self.tokens {
+                TokenSourceKind::Owned(tokens) => tokens.next(),
+                TokenSourceKind::Borrowed(tokens) => tokens.next(),
+            }?;
 
             if is_trivia(&next) {
                 continue;
@@ -39,8 +63,190 @@ impl Iterator for TokenSource {
     }
 }
 
-impl FusedIterator for TokenSource {}
+impl<'a> FusedIterator for TokenSource<'a> {}
 
-const fn is_trivia(result: &LexResult) -> bool {
+pub(crate) const fn is_trivia(result: &LexResult) -> bool {
     matches!(result, Ok((Tok::Comment(_) | Tok::NonLogicalNewline, _)))
 }
+
+/// The number of tokens [`TokenSourceLookahead`] can buffer for peeking.
+///
+/// The grammar never needs to look further than this many tokens ahead of the token it's
+/// currently consuming; this bounds the buffer rather than letting it grow without limit the way
+/// an `itertools::multipeek` would.
+const LOOKAHEAD_CAPACITY: usize = 4;
+
+/// Adds fixed-depth lookahead to a [`TokenSource`] using a ring buffer, rather than the unbounded
+/// buffer that a generic `Peekable`/`multipeek` adapter would allocate.
+///
+/// Advancing the lookahead ([`next`](Iterator::next)) and peeking ahead
+/// ([`peek_nth`](TokenSourceLookahead::peek_nth)) are both O(1): peeking fills the ring up to the
+/// requested depth on demand, and advancing just moves the read position forward by one slot
+/// instead of shifting every buffered token down by one.
+///
+/// [`parse_tokens`](crate::parse_tokens) and [`parse_tokens_ref`](crate::parse_tokens_ref) drive
+/// this directly instead of a bare [`TokenSource`], so every parse already pays for (and can use)
+/// the ring buffer. `SoftKeywordTransformer` still does its own separate, unbounded lookahead via
+/// `itertools::multipeek` ahead of this -- its `match`/`case`/`type` scans can run to the end of a
+/// logical line, further than [`LOOKAHEAD_CAPACITY`] bounds -- so it isn't a candidate to fold in
+/// here.
+#[derive(Clone)]
+pub(crate) struct TokenSourceLookahead<'a> {
+    source: TokenSource<'a>,
+    // A fixed-capacity ring buffer of not-yet-consumed tokens. `start` is the index of the next
+    // token `next()` will return; `len` is the number of valid, buffered entries starting there.
+    buffer: [Option<LexResult>; LOOKAHEAD_CAPACITY],
+    start: usize,
+    len: usize,
+}
+
+impl<'a> TokenSourceLookahead<'a> {
+    pub(crate) fn new(source: TokenSource<'a>) -> Self {
+        Self {
+            source,
+            buffer: [None, None, None, None],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Peeks at the token `n` positions ahead of the next one (`peek_nth(0)` is the same token
+    /// [`next`](Iterator::next) would return), filling the ring buffer from the underlying
+    /// [`TokenSource`] as needed.
+    ///
+    /// Returns `None` once the underlying source, and the buffered lookahead, are both exhausted.
+    ///
+    /// No caller needs this yet: [`parse_tokens`](crate::parse_tokens) and
+    /// [`parse_tokens_ref`](crate::parse_tokens_ref) only ever pull the next token from the
+    /// ring, they don't look past it. It stays here, exercised by tests, for the day a grammar
+    /// rule needs to disambiguate before committing to a production -- see [`Checkpoint`] for the
+    /// same reasoning applied to backtracking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n >= LOOKAHEAD_CAPACITY`.
+    #[allow(dead_code)]
+    pub(crate) fn peek_nth(&mut self, n: usize) -> Option<&LexResult> {
+        assert!(n < LOOKAHEAD_CAPACITY, "lookahead depth out of bounds");
+
+        while self.len <= n {
+            let slot = (self.start + self.len) % LOOKAHEAD_CAPACITY;
+            match self.source.next() {
+                Some(token) => {
+                    self.buffer[slot] = Some(token);
+                    self.len += 1;
+                }
+                // The underlying source is exhausted (it's fused), so every position from here
+                // on is also `None`; stop filling rather than re-querying it once per slot.
+                None => return None,
+            }
+        }
+
+        let slot = (self.start + n) % LOOKAHEAD_CAPACITY;
+        self.buffer[slot].as_ref()
+    }
+}
+
+impl<'a> Iterator for TokenSourceLookahead<'a> {
+    type Item = LexResult;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return self.source.next();
+        }
+
+        let slot = self.start;
+        self.start = (self.start + 1) % LOOKAHEAD_CAPACITY;
+        self.len -= 1;
+        self.buffer[slot].take()
+    }
+}
+
+/// A saved [`TokenSourceLookahead`] position, produced by [`TokenSourceLookahead::checkpoint`]
+/// and restored by [`TokenSourceLookahead::rewind`].
+///
+/// Alongside the token cursor, a checkpoint records how many diagnostics had been raised when it
+/// was taken, so a caller that speculatively commits to parsing something (an ambiguous
+/// parenthesized `with`-item, a soft keyword that might turn out to be an identifier, ...) and
+/// then decides to back out can discard both the tokens it consumed *and* any diagnostics that
+/// speculative attempt raised along the way, rather than just rewinding the cursor and leaving
+/// stale errors behind. This parser doesn't yet thread an accumulating error list through its
+/// entry points — a syntax error aborts the parse outright (see `parse_error_from_lalrpop`) — so
+/// every caller today passes `0` and gets `0` back; the field is here so the shape doesn't need
+/// to change once one exists.
+#[allow(dead_code)]
+pub(crate) struct Checkpoint<'a> {
+    source: TokenSourceLookahead<'a>,
+    error_count: usize,
+}
+
+#[allow(dead_code)]
+impl<'a> TokenSourceLookahead<'a> {
+    /// Saves the current position, along with `error_count` (the caller's own count of
+    /// diagnostics raised so far), for a later [`rewind`](TokenSourceLookahead::rewind).
+    pub(crate) fn checkpoint(&self, error_count: usize) -> Checkpoint<'a> {
+        Checkpoint {
+            source: self.clone(),
+            error_count,
+        }
+    }
+
+    /// Restores a [`Checkpoint`] taken earlier, undoing any tokens consumed (and lookahead
+    /// buffered) since then, and returns the error count it was taken with — the caller should
+    /// truncate its own diagnostics list to that length.
+    pub(crate) fn rewind(&mut self, checkpoint: Checkpoint<'a>) -> usize {
+        *self = checkpoint.source;
+        checkpoint.error_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+    use crate::Mode;
+
+    fn lookahead(source: &str) -> TokenSourceLookahead<'static> {
+        TokenSourceLookahead::new(TokenSource::from_iter(
+            lex(source, Mode::Module).map(|result| result.map(|(tok, range)| (tok, range))),
+        ))
+    }
+
+    fn token_strings(lookahead: &mut TokenSourceLookahead<'_>) -> Vec<String> {
+        lookahead
+            .by_ref()
+            .map(|result| format!("{:?}", result.unwrap().0))
+            .collect()
+    }
+
+    #[test]
+    fn rewind_replays_consumed_and_peeked_tokens() {
+        let mut source = lookahead("a + b");
+
+        // Peek ahead, then consume one token, before taking the checkpoint.
+        assert!(source.peek_nth(2).is_some());
+        source.next();
+
+        let checkpoint = source.checkpoint(3);
+        let before_rewind = token_strings(&mut source.clone());
+
+        let mut source = source;
+        let restored_error_count = source.rewind(checkpoint);
+        let after_rewind = token_strings(&mut source);
+
+        assert_eq!(restored_error_count, 3);
+        assert_eq!(before_rewind, after_rewind);
+    }
+
+    #[test]
+    fn rewind_is_a_no_op_without_consuming_afterwards() {
+        let mut source = lookahead("x");
+        let checkpoint = source.checkpoint(0);
+        source.rewind(checkpoint);
+
+        assert_eq!(
+            token_strings(&mut source),
+            vec!["Name { name: \"x\" }", "Newline"]
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Render this crate's token stream with `CPython`'s [`tokenize`] module semantics, so tools
+//! that compare against (or replace) `tokenize` can use this crate directly instead of shelling
+//! out to Python.
+//!
+//! The two lexers disagree on a few things that are bridged here rather than pushed onto
+//! callers:
+//! - This crate's lexer reports every newline that isn't a logical line break -- including the
+//!   ones inside a blank or comment-only line -- as [`Tok::NonLogicalNewline`], which maps to
+//!   [`TokenizeKind::Nl`] here, matching `tokenize`'s NEWLINE/NL split.
+//! - `tokenize` doesn't have a token type for keywords; `def`, `if`, `None`, and friends are all
+//!   reported as NAME, same as an identifier. This module follows suit: every keyword [`Tok`]
+//!   variant maps to [`TokenizeKind::Name`].
+//! - A [`TokenizeToken::start`]/[`end`](TokenizeToken::end) column is a 0-indexed count of
+//!   `char`s since the start of the line, not a UTF-8 byte offset -- `tokenize` computes columns
+//!   by indexing into the decoded `str` line it read, not the encoded bytes. This differs from
+//!   [`crate::cpython_ast`], which follows the `ast` module's byte-offset convention instead.
+//! - The leading `StartModule`/`StartExpression` sentinel this crate's lexer emits has no
+//!   `tokenize` equivalent (`tokenize.generate_tokens` starts directly on the first real token),
+//!   so it's dropped rather than mapped to [`TokenizeKind::ErrorToken`].
+//!
+//! `exact_type` numbers match `CPython` 3.12's `token` module, including `FSTRING_START`/
+//! `FSTRING_MIDDLE`/`FSTRING_END` (added for PEP 701). Earlier `CPython` versions use the same
+//! numbering up through `ERRORTOKEN` but lack those three and `SOFT_KEYWORD`; this module doesn't
+//! attempt to reproduce older numbering.
+//!
+//! [`tokenize`]: https://docs.python.org/3/library/tokenize.html
+
+use ruff_source_file::LineIndex;
+use ruff_text_size::{TextRange, TextSize};
+
+use crate::lexer::{self, LexicalError};
+use crate::{Mode, Tok};
+
+/// `CPython`'s `token.ENDMARKER`.
+pub const ENDMARKER: u32 = 0;
+/// `CPython`'s `token.NAME`.
+pub const NAME: u32 = 1;
+/// `CPython`'s `token.NUMBER`.
+pub const NUMBER: u32 = 2;
+/// `CPython`'s `token.STRING`.
+pub const STRING: u32 = 3;
+/// `CPython`'s `token.NEWLINE`.
+pub const NEWLINE: u32 = 4;
+/// `CPython`'s `token.INDENT`.
+pub const INDENT: u32 = 5;
+/// `CPython`'s `token.DEDENT`.
+pub const DEDENT: u32 = 6;
+/// `CPython`'s `token.LPAR`.
+pub const LPAR: u32 = 7;
+/// `CPython`'s `token.RPAR`.
+pub const RPAR: u32 = 8;
+/// `CPython`'s `token.LSQB`.
+pub const LSQB: u32 = 9;
+/// `CPython`'s `token.RSQB`.
+pub const RSQB: u32 = 10;
+/// `CPython`'s `token.COLON`.
+pub const COLON: u32 = 11;
+/// `CPython`'s `token.COMMA`.
+pub const COMMA: u32 = 12;
+/// `CPython`'s `token.SEMI`.
+pub const SEMI: u32 = 13;
+/// `CPython`'s `token.PLUS`.
+pub const PLUS: u32 = 14;
+/// `CPython`'s `token.MINUS`.
+pub const MINUS: u32 = 15;
+/// `CPython`'s `token.STAR`.
+pub const STAR: u32 = 16;
+/// `CPython`'s `token.SLASH`.
+pub const SLASH: u32 = 17;
+/// `CPython`'s `token.VBAR`.
+pub const VBAR: u32 = 18;
+/// `CPython`'s `token.AMPER`.
+pub const AMPER: u32 = 19;
+/// `CPython`'s `token.LESS`.
+pub const LESS: u32 = 20;
+/// `CPython`'s `token.GREATER`.
+pub const GREATER: u32 = 21;
+/// `CPython`'s `token.EQUAL`.
+pub const EQUAL: u32 = 22;
+/// `CPython`'s `token.DOT`.
+pub const DOT: u32 = 23;
+/// `CPython`'s `token.PERCENT`.
+pub const PERCENT: u32 = 24;
+/// `CPython`'s `token.LBRACE`.
+pub const LBRACE: u32 = 25;
+/// `CPython`'s `token.RBRACE`.
+pub const RBRACE: u32 = 26;
+/// `CPython`'s `token.EQEQUAL`.
+pub const EQEQUAL: u32 = 27;
+/// `CPython`'s `token.NOTEQUAL`.
+pub const NOTEQUAL: u32 = 28;
+/// `CPython`'s `token.LESSEQUAL`.
+pub const LESSEQUAL: u32 = 29;
+/// `CPython`'s `token.GREATEREQUAL`.
+pub const GREATEREQUAL: u32 = 30;
+/// `CPython`'s `token.TILDE`.
+pub const TILDE: u32 = 31;
+/// `CPython`'s `token.CIRCUMFLEX`.
+pub const CIRCUMFLEX: u32 = 32;
+/// `CPython`'s `token.LEFTSHIFT`.
+pub const LEFTSHIFT: u32 = 33;
+/// `CPython`'s `token.RIGHTSHIFT`.
+pub const RIGHTSHIFT: u32 = 34;
+/// `CPython`'s `token.DOUBLESTAR`.
+pub const DOUBLESTAR: u32 = 35;
+/// `CPython`'s `token.PLUSEQUAL`.
+pub const PLUSEQUAL: u32 = 36;
+/// `CPython`'s `token.MINEQUAL`.
+pub const MINEQUAL: u32 = 37;
+/// `CPython`'s `token.STAREQUAL`.
+pub const STAREQUAL: u32 = 38;
+/// `CPython`'s `token.SLASHEQUAL`.
+pub const SLASHEQUAL: u32 = 39;
+/// `CPython`'s `token.PERCENTEQUAL`.
+pub const PERCENTEQUAL: u32 = 40;
+/// `CPython`'s `token.AMPEREQUAL`.
+pub const AMPEREQUAL: u32 = 41;
+/// `CPython`'s `token.VBAREQUAL`.
+pub const VBAREQUAL: u32 = 42;
+/// `CPython`'s `token.CIRCUMFLEXEQUAL`.
+pub const CIRCUMFLEXEQUAL: u32 = 43;
+/// `CPython`'s `token.LEFTSHIFTEQUAL`.
+pub const LEFTSHIFTEQUAL: u32 = 44;
+/// `CPython`'s `token.RIGHTSHIFTEQUAL`.
+pub const RIGHTSHIFTEQUAL: u32 = 45;
+/// `CPython`'s `token.DOUBLESTAREQUAL`.
+pub const DOUBLESTAREQUAL: u32 = 46;
+/// `CPython`'s `token.DOUBLESLASH`.
+pub const DOUBLESLASH: u32 = 47;
+/// `CPython`'s `token.DOUBLESLASHEQUAL`.
+pub const DOUBLESLASHEQUAL: u32 = 48;
+/// `CPython`'s `token.AT`.
+pub const AT: u32 = 49;
+/// `CPython`'s `token.ATEQUAL`.
+pub const ATEQUAL: u32 = 50;
+/// `CPython`'s `token.RARROW`.
+pub const RARROW: u32 = 51;
+/// `CPython`'s `token.ELLIPSIS`.
+pub const ELLIPSIS: u32 = 52;
+/// `CPython`'s `token.COLONEQUAL`.
+pub const COLONEQUAL: u32 = 53;
+/// `CPython`'s `token.OP`, the exact type shared by any operator this table doesn't special-case.
+pub const OP: u32 = 54;
+/// `CPython`'s `token.FSTRING_START`.
+pub const FSTRING_START: u32 = 60;
+/// `CPython`'s `token.FSTRING_MIDDLE`.
+pub const FSTRING_MIDDLE: u32 = 61;
+/// `CPython`'s `token.FSTRING_END`.
+pub const FSTRING_END: u32 = 62;
+/// `CPython`'s `token.COMMENT`.
+pub const COMMENT: u32 = 63;
+/// `CPython`'s `token.NL`.
+pub const NL: u32 = 64;
+/// `CPython`'s `token.ERRORTOKEN`.
+pub const ERRORTOKEN: u32 = 65;
+
+/// The coarse token categories `tokenize` groups its `exact_type` numbers under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TokenizeKind {
+    EndMarker,
+    Name,
+    Number,
+    String,
+    Newline,
+    Indent,
+    Dedent,
+    Op,
+    Comment,
+    Nl,
+    FStringStart,
+    FStringMiddle,
+    FStringEnd,
+    /// A token with no `tokenize` equivalent, such as an IPython escape command.
+    ErrorToken,
+}
+
+/// One token in a `tokenize`-compatible stream, equivalent to a `tokenize.TokenInfo` named tuple.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenizeToken {
+    pub kind: TokenizeKind,
+    pub exact_type: u32,
+    /// The token's exact source text.
+    pub string: String,
+    /// `(row, column)`, 1-indexed row and 0-indexed `char` column, of the token's start.
+    pub start: (usize, usize),
+    /// `(row, column)`, 1-indexed row and 0-indexed `char` column, of the token's end.
+    pub end: (usize, usize),
+    /// The physical source line(s) the token spans.
+    pub line: String,
+}
+
+/// Lex `source` in the given `mode` and render the result with `tokenize` semantics.
+///
+/// Stops at the first lexical error, same as [`crate::tokenize`]. On success, the last token is
+/// always an `ENDMARKER`, matching `tokenize.generate_tokens` -- this crate's own lexer consumes
+/// its terminal [`Tok::EndOfFile`] internally rather than yielding it, so it's synthesized here.
+pub fn tokenize_compat(source: &str, mode: Mode) -> Result<Vec<TokenizeToken>, LexicalError> {
+    let line_index = LineIndex::from_source_text(source);
+    let mut out = Vec::new();
+    for result in lexer::lex(source, mode) {
+        let (tok, range) = result?;
+        if matches!(
+            tok,
+            Tok::StartModule | Tok::StartExpression | Tok::StartFunctionType
+        ) {
+            continue;
+        }
+        out.push(render(&tok, range, source, &line_index));
+    }
+    let eof = TextRange::empty(TextSize::try_from(source.len()).unwrap());
+    out.push(render(&Tok::EndOfFile, eof, source, &line_index));
+    Ok(out)
+}
+
+fn render(tok: &Tok, range: TextRange, source: &str, line_index: &LineIndex) -> TokenizeToken {
+    let (kind, exact_type) = classify(tok);
+    TokenizeToken {
+        kind,
+        exact_type,
+        string: source[range].to_string(),
+        start: char_position(line_index, source, range.start()),
+        end: char_position(line_index, source, range.end()),
+        line: spanned_lines(line_index, source, range),
+    }
+}
+
+/// `(row, column)` for `offset`: 1-indexed row, 0-indexed `char` column, matching `tokenize`.
+fn char_position(line_index: &LineIndex, source: &str, offset: TextSize) -> (usize, usize) {
+    let line = line_index.line_index(offset);
+    let line_start = line_index.line_start(line, source);
+    let column = source[TextRange::new(line_start, offset)].chars().count();
+    (line.get(), column)
+}
+
+/// The full text of every physical line `range` touches, concatenated -- `tokenize` gives a
+/// multi-line token (a triple-quoted string, a parenthesized expression) every line it spans.
+fn spanned_lines(line_index: &LineIndex, source: &str, range: TextRange) -> String {
+    let start_line = line_index.line_index(range.start());
+    let end_line = line_index.line_index(range.end());
+    let lines_range = TextRange::new(
+        line_index.line_start(start_line, source),
+        line_index.line_end(end_line, source),
+    );
+    source[lines_range].to_string()
+}
+
+fn classify(tok: &Tok) -> (TokenizeKind, u32) {
+    match tok {
+        Tok::Name { .. }
+        | Tok::False
+        | Tok::None
+        | Tok::True
+        | Tok::And
+        | Tok::As
+        | Tok::Assert
+        | Tok::Async
+        | Tok::Await
+        | Tok::Break
+        | Tok::Class
+        | Tok::Continue
+        | Tok::Def
+        | Tok::Del
+        | Tok::Elif
+        | Tok::Else
+        | Tok::Except
+        | Tok::Finally
+        | Tok::For
+        | Tok::From
+        | Tok::Global
+        | Tok::If
+        | Tok::Import
+        | Tok::In
+        | Tok::Is
+        | Tok::Lambda
+        | Tok::Nonlocal
+        | Tok::Not
+        | Tok::Or
+        | Tok::Pass
+        | Tok::Raise
+        | Tok::Return
+        | Tok::Try
+        | Tok::While
+        | Tok::Match
+        | Tok::Type
+        | Tok::Case
+        | Tok::With
+        | Tok::Yield => (TokenizeKind::Name, NAME),
+        Tok::Int { .. } | Tok::Float { .. } | Tok::Complex { .. } => (TokenizeKind::Number, NUMBER),
+        Tok::String { .. } => (TokenizeKind::String, STRING),
+        Tok::FStringStart => (TokenizeKind::FStringStart, FSTRING_START),
+        Tok::FStringMiddle { .. } => (TokenizeKind::FStringMiddle, FSTRING_MIDDLE),
+        Tok::FStringEnd => (TokenizeKind::FStringEnd, FSTRING_END),
+        Tok::Comment(_) => (TokenizeKind::Comment, COMMENT),
+        Tok::Newline => (TokenizeKind::Newline, NEWLINE),
+        Tok::NonLogicalNewline => (TokenizeKind::Nl, NL),
+        Tok::Indent => (TokenizeKind::Indent, INDENT),
+        Tok::Dedent => (TokenizeKind::Dedent, DEDENT),
+        Tok::EndOfFile => (TokenizeKind::EndMarker, ENDMARKER),
+        Tok::Lpar => (TokenizeKind::Op, LPAR),
+        Tok::Rpar => (TokenizeKind::Op, RPAR),
+        Tok::Lsqb => (TokenizeKind::Op, LSQB),
+        Tok::Rsqb => (TokenizeKind::Op, RSQB),
+        Tok::Colon => (TokenizeKind::Op, COLON),
+        Tok::Comma => (TokenizeKind::Op, COMMA),
+        Tok::Semi => (TokenizeKind::Op, SEMI),
+        Tok::Plus => (TokenizeKind::Op, PLUS),
+        Tok::Minus => (TokenizeKind::Op, MINUS),
+        Tok::Star => (TokenizeKind::Op, STAR),
+        Tok::Slash => (TokenizeKind::Op, SLASH),
+        Tok::Vbar => (TokenizeKind::Op, VBAR),
+        Tok::Amper => (TokenizeKind::Op, AMPER),
+        Tok::Less => (TokenizeKind::Op, LESS),
+        Tok::Greater => (TokenizeKind::Op, GREATER),
+        Tok::Equal => (TokenizeKind::Op, EQUAL),
+        Tok::Dot => (TokenizeKind::Op, DOT),
+        Tok::Percent => (TokenizeKind::Op, PERCENT),
+        Tok::Lbrace => (TokenizeKind::Op, LBRACE),
+        Tok::Rbrace => (TokenizeKind::Op, RBRACE),
+        Tok::EqEqual => (TokenizeKind::Op, EQEQUAL),
+        Tok::NotEqual => (TokenizeKind::Op, NOTEQUAL),
+        Tok::LessEqual => (TokenizeKind::Op, LESSEQUAL),
+        Tok::GreaterEqual => (TokenizeKind::Op, GREATEREQUAL),
+        Tok::Tilde => (TokenizeKind::Op, TILDE),
+        Tok::CircumFlex => (TokenizeKind::Op, CIRCUMFLEX),
+        Tok::LeftShift => (TokenizeKind::Op, LEFTSHIFT),
+        Tok::RightShift => (TokenizeKind::Op, RIGHTSHIFT),
+        Tok::DoubleStar => (TokenizeKind::Op, DOUBLESTAR),
+        Tok::DoubleStarEqual => (TokenizeKind::Op, DOUBLESTAREQUAL),
+        Tok::PlusEqual => (TokenizeKind::Op, PLUSEQUAL),
+        Tok::MinusEqual => (TokenizeKind::Op, MINEQUAL),
+        Tok::StarEqual => (TokenizeKind::Op, STAREQUAL),
+        Tok::SlashEqual => (TokenizeKind::Op, SLASHEQUAL),
+        Tok::PercentEqual => (TokenizeKind::Op, PERCENTEQUAL),
+        Tok::AmperEqual => (TokenizeKind::Op, AMPEREQUAL),
+        Tok::VbarEqual => (TokenizeKind::Op, VBAREQUAL),
+        Tok::CircumflexEqual => (TokenizeKind::Op, CIRCUMFLEXEQUAL),
+        Tok::LeftShiftEqual => (TokenizeKind::Op, LEFTSHIFTEQUAL),
+        Tok::RightShiftEqual => (TokenizeKind::Op, RIGHTSHIFTEQUAL),
+        Tok::DoubleSlash => (TokenizeKind::Op, DOUBLESLASH),
+        Tok::DoubleSlashEqual => (TokenizeKind::Op, DOUBLESLASHEQUAL),
+        Tok::ColonEqual => (TokenizeKind::Op, COLONEQUAL),
+        Tok::At => (TokenizeKind::Op, AT),
+        Tok::AtEqual => (TokenizeKind::Op, ATEQUAL),
+        Tok::Rarrow => (TokenizeKind::Op, RARROW),
+        Tok::Ellipsis => (TokenizeKind::Op, ELLIPSIS),
+        // IPython-only and internal sentinel tokens have no `tokenize` equivalent.
+        Tok::Question
+        | Tok::Exclamation
+        | Tok::IpyEscapeCommand { .. }
+        | Tok::StartModule
+        | Tok::StartExpression
+        | Tok::StartFunctionType => (TokenizeKind::ErrorToken, ERRORTOKEN),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize_compat, TokenizeKind, EQUAL, NAME, NEWLINE, NUMBER, PLUS};
+    use crate::Mode;
+
+    fn kinds(source: &str) -> Vec<TokenizeKind> {
+        tokenize_compat(source, Mode::Module)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn splits_logical_and_non_logical_newlines() {
+        let tokens = tokenize_compat("x = 1\n\n", Mode::Module).unwrap();
+        assert_eq!(
+            tokens.iter().map(|t| t.kind).collect::<Vec<_>>(),
+            [
+                TokenizeKind::Name,
+                TokenizeKind::Op,
+                TokenizeKind::Number,
+                TokenizeKind::Newline,
+                TokenizeKind::Nl,
+                TokenizeKind::EndMarker,
+            ]
+        );
+    }
+
+    #[test]
+    fn keywords_are_reported_as_names() {
+        assert_eq!(
+            kinds("def f(): pass\n"),
+            [
+                TokenizeKind::Name,
+                TokenizeKind::Name,
+                TokenizeKind::Op,
+                TokenizeKind::Op,
+                TokenizeKind::Op,
+                TokenizeKind::Name,
+                TokenizeKind::Newline,
+                TokenizeKind::EndMarker,
+            ]
+        );
+    }
+
+    #[test]
+    fn exact_type_distinguishes_operators() {
+        let tokens = tokenize_compat("a + 1\n", Mode::Module).unwrap();
+        assert_eq!(tokens[0].exact_type, NAME);
+        assert_eq!(tokens[1].exact_type, PLUS);
+        assert_eq!(tokens[1].kind, TokenizeKind::Op);
+        assert_eq!(tokens[2].exact_type, NUMBER);
+        assert_eq!(tokens[3].exact_type, NEWLINE);
+    }
+
+    #[test]
+    fn indent_and_dedent_carry_the_whitespace_text() {
+        let tokens = tokenize_compat("if x:\n    y\n", Mode::Module).unwrap();
+        let indent = tokens
+            .iter()
+            .find(|t| t.kind == TokenizeKind::Indent)
+            .unwrap();
+        assert_eq!(indent.string, "    ");
+        let dedent = tokens
+            .iter()
+            .find(|t| t.kind == TokenizeKind::Dedent)
+            .unwrap();
+        assert_eq!(dedent.string, "");
+    }
+
+    #[test]
+    fn positions_use_char_columns_not_byte_offsets() {
+        let tokens = tokenize_compat("désir = 1\n", Mode::Module).unwrap();
+        // `désir` is 5 chars but 6 bytes (the `é` is 2 bytes), so `=` starts at char column 6.
+        let equals = &tokens[1];
+        assert_eq!(equals.exact_type, EQUAL);
+        assert_eq!(equals.start, (1, 6));
+    }
+
+    #[test]
+    fn line_spans_every_physical_line_a_token_covers() {
+        let tokens = tokenize_compat("x = (\n    1,\n)\n", Mode::Module).unwrap();
+        let string_tok = tokens
+            .iter()
+            .find(|t| t.kind == TokenizeKind::Number)
+            .unwrap();
+        assert_eq!(string_tok.line, "    1,\n");
+    }
+
+    #[test]
+    fn the_start_sentinel_is_not_emitted() {
+        let tokens = tokenize_compat("1\n", Mode::Module).unwrap();
+        assert!(tokens
+            .iter()
+            .all(|t| t.kind != TokenizeKind::ErrorToken));
+    }
+
+    #[test]
+    fn stops_at_the_first_lexical_error() {
+        assert!(tokenize_compat("'unterminated", Mode::Module).is_err());
+    }
+}
@@ -0,0 +1,428 @@
+//! Parsing of [PEP 484] `# type:` comments.
+//!
+//! These predate real annotation syntax and are still emitted by some code generators and type
+//! stubs for Python 2-compatible sources, so `CPython`'s own `ast` module recognizes them when
+//! asked (`ast.parse(source, type_comments=True)`). This module plays the same role here:
+//! [`parse_type_comments`] walks a parsed module's statements, finds the `# type:` comment
+//! trailing each assignment, `for`, `with`, or function definition, and parses its contents, so a
+//! caller doesn't need a second, comment-aware tokenizer pass of their own.
+//!
+//! A comment only counts as trailing a statement if it starts on the same source line as that
+//! statement's header -- the value for a simple statement (`x = 1  # type: int`), or the `:` that
+//! opens a compound statement's suite (`for x in y:  # type: int`, including a multi-line `def`
+//! signature). `# type: ignore` comments, which suppress a checker rather than naming a type, are
+//! recognized and skipped rather than parsed.
+//!
+//! A function definition's type comment uses a different shape from the rest --
+//! `# type: (int, str) -> bool` rather than a single expression -- so it's parsed with
+//! [`Mode::FunctionType`] into [`TypeCommentKind::Function`] instead of
+//! [`TypeCommentKind::Expression`]. This doesn't cover a per-parameter type comment placed inline
+//! within a multi-line signature (PEP 484 allows both styles); only the whole-signature comment on
+//! the line with the closing `:` is recognized.
+//!
+//! `# type: ignore[...]` comments aren't a type to parse, but PEP 484 gives them their own
+//! defined semantics (suppress a checker, optionally scoped to the listed error codes), so
+//! they're not attached to a statement the way the rest of this module's comments are --
+//! [`parse_type_ignore_comments`] collects them independently of any statement, the way a type
+//! checker actually consumes them.
+//!
+//! [PEP 484]: https://peps.python.org/pep-0484/#type-comments
+use ruff_python_ast::statement_visitor::{walk_stmt, StatementVisitor};
+use ruff_python_ast::{self as ast, Stmt};
+use ruff_source_file::{LineIndex, OneIndexed};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+
+use crate::{lexer, parse_expression_starts_at, parse_function_type_starts_at, Mode, Tok};
+
+/// A `# type:` comment, parsed and attached to the statement it trails.
+#[derive(Debug, PartialEq)]
+pub struct TypeComment {
+    /// The range of the statement the comment annotates.
+    pub statement: TextRange,
+    /// The range of the comment itself, including the leading `#`.
+    pub comment: TextRange,
+    /// The comment's parsed contents.
+    pub kind: TypeCommentKind,
+}
+
+/// The parsed contents of a [`TypeComment`], with ranges mapped back into the original source.
+#[derive(Debug, PartialEq)]
+pub enum TypeCommentKind {
+    /// `# type: <expr>`, as used on assignments, `for` targets, and `with` targets.
+    Expression(ast::Expr),
+    /// `# type: (<expr>, ...) -> <expr>`, the whole-signature form used on function definitions.
+    ///
+    /// `argument_types` is the comma-separated list between the parens (e.g. `[int, str]` for
+    /// `(int, str) -> bool`), or a single [`ast::Expr::EllipsisLiteral`] for an untyped/variadic
+    /// signature (`(...) -> bool`) -- `CPython`'s `ast.parse(..., mode="func_type")` treats `...`
+    /// as an ordinary entry in the list rather than a dedicated variadic marker, and this mirrors
+    /// that.
+    Function {
+        argument_types: Vec<ast::Expr>,
+        returns: ast::Expr,
+    },
+}
+
+/// Finds every `# type:` comment trailing a statement in `body` and parses its contents.
+///
+/// `source` must be the same source `body` was parsed from, so comment and statement ranges line
+/// up. Returns the comments in the order their statements appear.
+pub fn parse_type_comments(source: &str, body: &[Stmt]) -> Vec<TypeComment> {
+    let comments: Vec<TextRange> = lexer::lex(source, Mode::Module)
+        .filter_map(|result| match result {
+            Ok((Tok::Comment(text), range)) if text.starts_with("# type:") => Some(range),
+            _ => None,
+        })
+        .collect();
+
+    if comments.is_empty() {
+        return Vec::new();
+    }
+
+    let mut collector = Collector {
+        source,
+        comments,
+        out: Vec::new(),
+    };
+    collector.visit_body(body);
+    collector.out
+}
+
+struct Collector<'a> {
+    source: &'a str,
+    comments: Vec<TextRange>,
+    out: Vec<TypeComment>,
+}
+
+impl<'a> StatementVisitor<'a> for Collector<'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if let Some(header_end) = header_end(stmt, self.source) {
+            if let Some(comment) = self.trailing_comment(header_end) {
+                if let Some((content, content_start)) = annotation_source(self.source, comment) {
+                    let kind = if matches!(stmt, Stmt::FunctionDef(_)) {
+                        parse_function_type_comment(content, content_start)
+                    } else {
+                        parse_expression_starts_at(content, content_start)
+                            .ok()
+                            .map(TypeCommentKind::Expression)
+                    };
+                    if let Some(kind) = kind {
+                        self.out.push(TypeComment {
+                            statement: stmt.range(),
+                            comment,
+                            kind,
+                        });
+                    }
+                }
+            }
+        }
+
+        walk_stmt(self, stmt);
+    }
+}
+
+impl Collector<'_> {
+    /// Returns the first recognized `# type:` comment starting on the same source line as
+    /// `header_end`, if any.
+    fn trailing_comment(&self, header_end: TextSize) -> Option<TextRange> {
+        let index = self
+            .comments
+            .partition_point(|comment| comment.start() < header_end);
+        let comment = *self.comments.get(index)?;
+        if self.source[TextRange::new(header_end, comment.start())].contains('\n') {
+            return None;
+        }
+        Some(comment)
+    }
+}
+
+/// Returns the position right after the part of `stmt` a trailing `# type:` comment attaches to:
+/// the value for a simple assignment, or the `:` that opens a compound statement's suite.
+///
+/// Returns `None` for statement kinds PEP 484 doesn't define type comments for.
+fn header_end(stmt: &Stmt, source: &str) -> Option<TextSize> {
+    match stmt {
+        Stmt::Assign(_) | Stmt::AugAssign(_) | Stmt::AnnAssign(_) => Some(stmt.end()),
+        Stmt::For(_) | Stmt::With(_) | Stmt::FunctionDef(_) => {
+            header_colon_end(stmt.range(), source)
+        }
+        _ => None,
+    }
+}
+
+/// Finds the end of the top-level `:` that closes a compound statement's header, given that
+/// statement's full range (decorators and all, for a `def`).
+///
+/// Tracks bracket depth so a slice's, annotation's, or call's own `:` isn't mistaken for the
+/// suite's, and tracks pending `lambda`s so a bare (unparenthesized) one in a return annotation
+/// doesn't consume the suite's colon either.
+fn header_colon_end(range: TextRange, source: &str) -> Option<TextSize> {
+    let mut depth = 0i32;
+    let mut pending_lambdas = 0i32;
+    for result in lexer::lex(&source[range], Mode::Module) {
+        let (tok, tok_range) = result.ok()?;
+        match tok {
+            Tok::Lpar | Tok::Lsqb | Tok::Lbrace => depth += 1,
+            Tok::Rpar | Tok::Rsqb | Tok::Rbrace => depth -= 1,
+            Tok::Lambda if depth == 0 => pending_lambdas += 1,
+            Tok::Colon if depth == 0 => {
+                if pending_lambdas > 0 {
+                    pending_lambdas -= 1;
+                } else {
+                    return Some(range.start() + tok_range.end());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits a `# type:` comment's text into its contents and the offset of those contents within
+/// the original source. Returns `None` for a `# type: ignore[...]` comment, which isn't a type
+/// comment to parse at all.
+fn annotation_source(source: &str, comment: TextRange) -> Option<(&str, TextSize)> {
+    let text = &source[comment];
+    let after_prefix = text.strip_prefix("# type:")?;
+    let trimmed_start = after_prefix.trim_start();
+    if is_ignore_comment(trimmed_start) {
+        return None;
+    }
+
+    let content = trimmed_start.trim_end();
+    if content.is_empty() {
+        return None;
+    }
+
+    let skipped = after_prefix.len() - trimmed_start.len();
+    let content_start = comment.start() + TextSize::try_from("# type:".len() + skipped).unwrap();
+    Some((content, content_start))
+}
+
+/// Whether `content` (the part of a `# type:` comment after the prefix, with leading whitespace
+/// already trimmed) is a `type: ignore` comment rather than a type to parse.
+fn is_ignore_comment(content: &str) -> bool {
+    content
+        .strip_prefix("ignore")
+        .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+/// Parses a function definition's whole-signature type comment, `(<types>) -> <type>`, using
+/// [`Mode::FunctionType`].
+fn parse_function_type_comment(content: &str, content_start: TextSize) -> Option<TypeCommentKind> {
+    let function_type = parse_function_type_starts_at(content, content_start).ok()?;
+    Some(TypeCommentKind::Function {
+        argument_types: function_type.argtypes,
+        returns: *function_type.returns,
+    })
+}
+
+/// A `# type: ignore[...]` comment, recognized independently of any statement.
+#[derive(Debug, PartialEq)]
+pub struct TypeIgnoreComment {
+    /// The comment's range, including the leading `#`.
+    pub range: TextRange,
+    /// The one-indexed source line the comment starts on.
+    pub line: OneIndexed,
+    /// The error codes listed in the comment's `[...]` suffix, e.g. `["union-attr"]` for
+    /// `# type: ignore[union-attr]`. Empty for a bare `# type: ignore` with no bracketed list,
+    /// which suppresses every error on its line rather than a specific set of codes.
+    pub codes: Vec<String>,
+}
+
+/// Finds every `# type: ignore[...]` comment in `source`, regardless of whether it trails a
+/// statement [`parse_type_comments`] would otherwise attach a comment to.
+pub fn parse_type_ignore_comments(source: &str) -> Vec<TypeIgnoreComment> {
+    let line_index = LineIndex::from_source_text(source);
+    lexer::lex(source, Mode::Module)
+        .filter_map(|result| {
+            let (Tok::Comment(text), range) = result.ok()? else {
+                return None;
+            };
+            let after_prefix = text.strip_prefix("# type:")?;
+            let after_ignore = after_prefix.trim_start().strip_prefix("ignore")?;
+            if after_ignore.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                return None;
+            }
+            Some(TypeIgnoreComment {
+                range,
+                line: line_index.line_index(range.start()),
+                codes: parse_ignore_codes(after_ignore),
+            })
+        })
+        .collect()
+}
+
+/// Parses the bracketed, comma-separated error-code list following `ignore` in a
+/// `# type: ignore[...]` comment. Returns an empty list if there's no `[...]` suffix at all.
+fn parse_ignore_codes(after_ignore: &str) -> Vec<String> {
+    let Some(bracketed) = after_ignore.trim_start().strip_prefix('[') else {
+        return Vec::new();
+    };
+    let Some(end) = bracketed.find(']') else {
+        return Vec::new();
+    };
+    bracketed[..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::Mod;
+    use ruff_text_size::{Ranged, TextSize};
+
+    use ruff_source_file::OneIndexed;
+
+    use super::{parse_type_comments, parse_type_ignore_comments, TypeCommentKind};
+    use crate::{parse, Mode};
+
+    fn module_body(source: &str) -> Vec<ruff_python_ast::Stmt> {
+        match parse(source, Mode::Module).unwrap() {
+            Mod::Module(module) => module.body,
+            Mod::Expression(_) | Mod::FunctionType(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn attaches_a_type_comment_to_an_assignment() {
+        let source = "x = []  # type: List[int]\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+        let TypeCommentKind::Expression(annotation) = &comments[0].kind else {
+            panic!("expected an expression type comment");
+        };
+        assert_eq!(&source[annotation.range()], "List[int]");
+    }
+
+    #[test]
+    fn attaches_a_type_comment_to_a_for_loop_header() {
+        let source = "for x in y:  # type: int\n    pass\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+        let TypeCommentKind::Expression(annotation) = &comments[0].kind else {
+            panic!("expected an expression type comment");
+        };
+        assert_eq!(&source[annotation.range()], "int");
+    }
+
+    #[test]
+    fn attaches_a_type_comment_to_a_with_statement() {
+        let source = "with f() as x:  # type: int\n    pass\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+        let TypeCommentKind::Expression(annotation) = &comments[0].kind else {
+            panic!("expected an expression type comment");
+        };
+        assert_eq!(&source[annotation.range()], "int");
+    }
+
+    #[test]
+    fn attaches_a_type_comment_to_a_multiline_function_signature() {
+        let source = "def f(\n    x,\n):  # type: (int) -> int\n    return x\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+        let TypeCommentKind::Function {
+            argument_types,
+            returns,
+        } = &comments[0].kind
+        else {
+            panic!("expected a function type comment");
+        };
+        assert_eq!(argument_types.len(), 1);
+        assert_eq!(&source[argument_types[0].range()], "int");
+        assert_eq!(&source[returns.range()], "int");
+    }
+
+    #[test]
+    fn a_variadic_function_type_comment_parses_the_ellipsis() {
+        let source = "def f(x):  # type: (...) -> int\n    return x\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+        let TypeCommentKind::Function { argument_types, .. } = &comments[0].kind else {
+            panic!("expected a function type comment");
+        };
+        assert_eq!(argument_types.len(), 1);
+        assert!(matches!(
+            argument_types[0],
+            ruff_python_ast::Expr::EllipsisLiteral(_)
+        ));
+        assert_eq!(&source[argument_types[0].range()], "...");
+    }
+
+    #[test]
+    fn ignores_type_ignore_comments() {
+        let source = "x = []  # type: ignore[assignment]\n";
+        let body = module_body(source);
+        assert!(parse_type_comments(source, &body).is_empty());
+    }
+
+    #[test]
+    fn ignores_comments_on_a_different_statement() {
+        let source = "x = 1\n# type: int\ny = 2\n";
+        let body = module_body(source);
+        assert!(parse_type_comments(source, &body).is_empty());
+    }
+
+    #[test]
+    fn ranges_are_mapped_into_the_real_source() {
+        let source = "def f():\n    y = 1  # type: int\n    return y\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+        let TypeCommentKind::Expression(annotation) = &comments[0].kind else {
+            panic!("expected an expression type comment");
+        };
+        let expected_start = TextSize::try_from(source.find("int").unwrap()).unwrap();
+        assert_eq!(annotation.range().start(), expected_start);
+        assert_eq!(&source[annotation.range()], "int");
+    }
+
+    #[test]
+    fn finds_type_comments_on_nested_statements() {
+        let source = "def f():\n    for x in y:  # type: int\n        pass\n";
+        let body = module_body(source);
+        let comments = parse_type_comments(source, &body);
+        assert_eq!(comments.len(), 1);
+    }
+
+    #[test]
+    fn collects_a_bare_type_ignore_comment_with_no_codes() {
+        let source = "x = bad_call()  # type: ignore\n";
+        let comments = parse_type_ignore_comments(source);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, OneIndexed::from_zero_indexed(0));
+        assert!(comments[0].codes.is_empty());
+    }
+
+    #[test]
+    fn collects_a_type_ignore_comment_s_error_codes() {
+        let source = "x: int = bad_call()  # type: ignore[assignment, arg-type]\n";
+        let comments = parse_type_ignore_comments(source);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].codes, vec!["assignment", "arg-type"]);
+    }
+
+    #[test]
+    fn collects_a_type_ignore_comment_on_any_line_not_just_a_statement_header() {
+        let source = "x = 1\n# type: ignore[unused-ignore]\ny = 2\n";
+        let comments = parse_type_ignore_comments(source);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, OneIndexed::from_zero_indexed(1));
+        assert_eq!(comments[0].codes, vec!["unused-ignore"]);
+    }
+
+    #[test]
+    fn does_not_collect_an_identifier_that_merely_starts_with_ignore() {
+        let source = "x = 1  # type: ignorant\n";
+        assert!(parse_type_ignore_comments(source).is_empty());
+    }
+}
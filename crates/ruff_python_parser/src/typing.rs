@@ -2,7 +2,7 @@ use crate::{parse_expression, parse_expression_starts_at};
 use anyhow::Result;
 use ruff_python_ast::relocate::relocate_expr;
 use ruff_python_ast::str;
-use ruff_python_ast::Expr;
+use ruff_python_ast::{Expr, ExprStringLiteral};
 use ruff_text_size::{TextLen, TextRange};
 
 #[derive(is_macro::Is, Copy, Clone)]
@@ -42,3 +42,12 @@ pub fn parse_type_annotation(
         Ok((expr, AnnotationKind::Complex))
     }
 }
+
+/// Parse a type annotation from a string literal node, taking its value and range directly from
+/// the node instead of requiring the caller to pull them out first.
+pub fn parse_type_annotation_from_literal(
+    literal: &ExprStringLiteral,
+    source: &str,
+) -> Result<(Expr, AnnotationKind)> {
+    parse_type_annotation(literal.value.to_str(), literal.range, source)
+}
@@ -0,0 +1,54 @@
+/*!
+Public predicates for validating assignment targets.
+
+These mirror the rules enforced internally by the parser (see `invalid::assignment_target`), but
+are exposed as plain boolean checks rather than as a [`LexicalError`]-producing validation pass.
+Fixers that synthesize new assignment statements (e.g. rewriting `x, y = y, x` or introducing a
+`with ... as target` binding) can use these to check that a target they're about to emit is
+actually legal, without duplicating the parser's rules or constructing a parser-specific error.
+*/
+
+use ruff_python_ast::Expr;
+use ruff_text_size::TextSize;
+
+use crate::lexer::{LexicalError, LexicalErrorType};
+
+/// Returns `true` if `target` is valid on the left-hand side of a `target = value` assignment.
+///
+/// This accepts names, attributes, subscripts and slices, and (recursively) lists, tuples, and
+/// starred expressions composed of valid targets.
+pub fn is_valid_assignment_target(target: &Expr) -> bool {
+    match target {
+        Expr::Starred(starred) => is_valid_assignment_target(&starred.value),
+        Expr::List(list) => list.elts.iter().all(is_valid_assignment_target),
+        Expr::Tuple(tuple) => tuple.elts.iter().all(is_valid_assignment_target),
+        Expr::Subscript(_) | Expr::Slice(_) | Expr::Attribute(_) | Expr::Name(_) => true,
+        _ => false,
+    }
+}
+
+/// Returns `true` if `target` is valid on the left-hand side of an augmented assignment
+/// (`target += value`).
+///
+/// Augmented assignment targets are more restrictive than plain assignment targets: lists,
+/// tuples, and starred expressions are never allowed, since there's no way to "add" to a
+/// sequence of independent targets.
+pub fn is_valid_aug_assignment_target(target: &Expr) -> bool {
+    matches!(
+        target,
+        Expr::Subscript(_) | Expr::Slice(_) | Expr::Attribute(_) | Expr::Name(_)
+    )
+}
+
+/// Constructs the [`LexicalError`] the parser reports when it rejects a value at `location` as an
+/// assignment target.
+///
+/// This is the same error the parser itself produces internally; it's exposed here so that
+/// callers validating a target with [`is_valid_assignment_target`] or
+/// [`is_valid_aug_assignment_target`] can surface a matching diagnostic on failure.
+pub fn assignment_error(location: TextSize) -> LexicalError {
+    LexicalError {
+        error: LexicalErrorType::AssignmentError,
+        location,
+    }
+}
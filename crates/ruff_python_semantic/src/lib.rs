@@ -8,6 +8,7 @@ mod model;
 mod nodes;
 mod reference;
 mod scope;
+pub mod scope_builder;
 mod star_import;
 
 pub use binding::*;
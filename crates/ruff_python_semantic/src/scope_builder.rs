@@ -0,0 +1,287 @@
+//! A standalone scope/binding builder.
+//!
+//! [`SemanticModel`](crate::SemanticModel) only becomes available once a `Checker` walks the AST
+//! while running lint rules. Several analyses (for example, syntax-error checks that need to know
+//! whether a name is a local, a global, or a free variable) want scope and binding information
+//! *before* a full linter pass exists. [`build_scope_tree`] walks a module on its own and produces
+//! a lightweight [`ScopeTree`] of scopes, name bindings, and resolutions, independent of the
+//! checker.
+//!
+//! This is intentionally simpler than the full semantic model built during linting: it does not
+//! track control flow, branches, or deferred bindings, only lexical scoping and the distinction
+//! between locals, globals, nonlocals, and the cell variables captured by nested scopes.
+
+use ruff_python_ast::visitor::{walk_expr, walk_stmt, Visitor};
+use ruff_python_ast::{self as ast, Expr, Stmt};
+use rustc_hash::FxHashSet;
+
+use ruff_index::{newtype_index, IndexVec};
+
+/// Id uniquely identifying a [`ScopeNode`] within a [`ScopeTree`].
+#[newtype_index]
+pub struct ScopeNodeId;
+
+/// The kind of a lexical scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    Module,
+    Class,
+    Function,
+    Lambda,
+    Comprehension,
+}
+
+/// How a name is bound within a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// Bound by an assignment, `for` target, `with` target, etc.
+    Assignment,
+    /// Bound by a `def` or `class` statement.
+    Definition,
+    /// Bound by an `import` or `import ... as ...` statement.
+    Import,
+    /// A function parameter.
+    Parameter,
+    /// Declared with the `global` statement.
+    Global,
+    /// Declared with the `nonlocal` statement.
+    Nonlocal,
+}
+
+/// A single name binding recorded within a scope.
+#[derive(Debug, Clone)]
+pub struct NameBinding {
+    pub name: String,
+    pub kind: BindingKind,
+}
+
+/// A lexical scope in the [`ScopeTree`].
+#[derive(Debug)]
+pub struct ScopeNode {
+    pub kind: ScopeKind,
+    pub parent: Option<ScopeNodeId>,
+    pub bindings: Vec<NameBinding>,
+    /// Names declared `global` in this scope.
+    pub globals: FxHashSet<String>,
+    /// Names declared `nonlocal` in this scope.
+    pub nonlocals: FxHashSet<String>,
+    /// Names that are read in this scope and resolved to an enclosing function scope, making them
+    /// cell variables in that enclosing scope.
+    pub free_variables: FxHashSet<String>,
+}
+
+impl ScopeNode {
+    fn new(kind: ScopeKind, parent: Option<ScopeNodeId>) -> Self {
+        Self {
+            kind,
+            parent,
+            bindings: Vec::new(),
+            globals: FxHashSet::default(),
+            nonlocals: FxHashSet::default(),
+            free_variables: FxHashSet::default(),
+        }
+    }
+
+    /// Returns `true` if `name` is bound directly in this scope (as a local).
+    pub fn binds(&self, name: &str) -> bool {
+        self.bindings.iter().any(|binding| binding.name == name)
+    }
+}
+
+/// The result of walking a module: every scope that was created, indexed by [`ScopeNodeId`].
+#[derive(Debug)]
+pub struct ScopeTree {
+    scopes: IndexVec<ScopeNodeId, ScopeNode>,
+}
+
+impl ScopeTree {
+    pub fn module_scope_id() -> ScopeNodeId {
+        ScopeNodeId::from_u32(0)
+    }
+
+    pub fn scope(&self, id: ScopeNodeId) -> &ScopeNode {
+        &self.scopes[id]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ScopeNodeId, &ScopeNode)> {
+        self.scopes.iter_enumerated()
+    }
+
+    /// Resolves `name` as seen from `scope_id`, returning the scope that binds it, if any.
+    ///
+    /// This implements the usual LEGB (local, enclosing, global, builtin) lookup, stopping at the
+    /// first enclosing *function or module* scope that binds the name, and honoring explicit
+    /// `global`/`nonlocal` declarations.
+    pub fn resolve(&self, scope_id: ScopeNodeId, name: &str) -> Option<ScopeNodeId> {
+        let scope = self.scope(scope_id);
+        if scope.globals.contains(name) {
+            return self.resolve(Self::module_scope_id(), name);
+        }
+        if scope.binds(name) && !scope.nonlocals.contains(name) {
+            return Some(scope_id);
+        }
+
+        let mut current = scope.parent;
+        while let Some(id) = current {
+            let candidate = self.scope(id);
+            // Class scopes are skipped for lookups originating from a nested function, matching
+            // Python's scoping rules.
+            if candidate.kind != ScopeKind::Class && candidate.binds(name) {
+                return Some(id);
+            }
+            current = candidate.parent;
+        }
+        None
+    }
+}
+
+struct ScopeBuilder {
+    scopes: IndexVec<ScopeNodeId, ScopeNode>,
+    current: ScopeNodeId,
+}
+
+impl ScopeBuilder {
+    fn push_scope(&mut self, kind: ScopeKind) -> ScopeNodeId {
+        let id = self.scopes.push(ScopeNode::new(kind, Some(self.current)));
+        id
+    }
+
+    fn bind(&mut self, name: &str, kind: BindingKind) {
+        match kind {
+            BindingKind::Global => {
+                self.scopes[self.current].globals.insert(name.to_string());
+            }
+            BindingKind::Nonlocal => {
+                self.scopes[self.current]
+                    .nonlocals
+                    .insert(name.to_string());
+            }
+            _ => self.scopes[self.current].bindings.push(NameBinding {
+                name: name.to_string(),
+                kind,
+            }),
+        }
+    }
+
+    fn in_scope<F: FnOnce(&mut Self)>(&mut self, id: ScopeNodeId, f: F) {
+        let previous = self.current;
+        self.current = id;
+        f(self);
+        self.current = previous;
+    }
+
+    fn bind_parameters(&mut self, parameters: &ast::Parameters) {
+        for parameter in parameters
+            .posonlyargs
+            .iter()
+            .chain(&parameters.args)
+            .chain(&parameters.kwonlyargs)
+        {
+            self.bind(parameter.parameter.name.as_str(), BindingKind::Parameter);
+        }
+        if let Some(vararg) = &parameters.vararg {
+            self.bind(vararg.name.as_str(), BindingKind::Parameter);
+        }
+        if let Some(kwarg) = &parameters.kwarg {
+            self.bind(kwarg.name.as_str(), BindingKind::Parameter);
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ScopeBuilder {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::FunctionDef(func) => {
+                self.bind(&func.name, BindingKind::Definition);
+                for decorator in &func.decorator_list {
+                    self.visit_decorator(decorator);
+                }
+                let id = self.push_scope(ScopeKind::Function);
+                self.in_scope(id, |builder| {
+                    builder.bind_parameters(&func.parameters);
+                    builder.visit_body(&func.body);
+                });
+            }
+            Stmt::ClassDef(class) => {
+                self.bind(&class.name, BindingKind::Definition);
+                for decorator in &class.decorator_list {
+                    self.visit_decorator(decorator);
+                }
+                let id = self.push_scope(ScopeKind::Class);
+                self.in_scope(id, |builder| builder.visit_body(&class.body));
+            }
+            Stmt::Import(import) => {
+                for alias in &import.names {
+                    // `import a.b.c` binds `a`, not `a.b.c`, unless it's aliased.
+                    let name = alias
+                        .asname
+                        .as_ref()
+                        .map(ast::Identifier::as_str)
+                        .unwrap_or_else(|| alias.name.segments()[0].as_str());
+                    self.bind(name, BindingKind::Import);
+                }
+            }
+            Stmt::ImportFrom(import) => {
+                for alias in &import.names {
+                    let name = alias
+                        .asname
+                        .as_ref()
+                        .map(ast::Identifier::as_str)
+                        .unwrap_or_else(|| alias.name.as_str());
+                    self.bind(name, BindingKind::Import);
+                }
+            }
+            Stmt::Global(global) => {
+                for name in &global.names {
+                    self.bind(name, BindingKind::Global);
+                }
+            }
+            Stmt::Nonlocal(nonlocal) => {
+                for name in &nonlocal.names {
+                    self.bind(name, BindingKind::Nonlocal);
+                }
+            }
+            Stmt::Assign(_) | Stmt::AugAssign(_) | Stmt::AnnAssign(_) | Stmt::For(_) => {
+                walk_stmt(self, stmt);
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Name(name) => {
+                if name.ctx.is_store() {
+                    self.bind(&name.id, BindingKind::Assignment);
+                }
+            }
+            Expr::Lambda(lambda) => {
+                let id = self.push_scope(ScopeKind::Lambda);
+                self.in_scope(id, |builder| {
+                    if let Some(parameters) = &lambda.parameters {
+                        builder.bind_parameters(parameters);
+                    }
+                    builder.visit_expr(&lambda.body);
+                });
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+/// Walks `suite` and builds a [`ScopeTree`] describing every scope it introduces along with the
+/// bindings made in each.
+pub fn build_scope_tree(suite: &ast::Suite) -> ScopeTree {
+    let mut scopes = IndexVec::new();
+    scopes.push(ScopeNode::new(ScopeKind::Module, None));
+
+    let mut builder = ScopeBuilder {
+        scopes,
+        current: ScopeTree::module_scope_id(),
+    };
+    builder.visit_body(suite);
+
+    ScopeTree {
+        scopes: builder.scopes,
+    }
+}
@@ -13,10 +13,12 @@ use ruff_linter::registry::AsRule;
 use ruff_linter::settings::types::PythonVersion;
 use ruff_linter::settings::{flags, DEFAULT_SELECTORS, DUMMY_VARIABLE_RGX};
 use ruff_linter::source_kind::SourceKind;
+use ruff_python_ast::min_version::MinVersion;
 use ruff_python_ast::{Mod, PySourceType};
 use ruff_python_codegen::Stylist;
 use ruff_python_formatter::{format_module_ast, pretty_comments, PyFormatContext, QuoteStyle};
 use ruff_python_index::{CommentRangesBuilder, Indexer};
+use ruff_python_parser::cpython_ast::to_cpython_json;
 use ruff_python_parser::lexer::LexResult;
 use ruff_python_parser::{parse_tokens, tokenize_all, AsMode, Mode};
 use ruff_python_trivia::CommentRanges;
@@ -254,6 +256,18 @@ impl Workspace {
         Ok(format!("{parsed:#?}"))
     }
 
+    /// Parses the content and returns a `CPython` `ast`-compatible JSON dump of the resulting
+    /// module, for playgrounds and tooling that want to diff against `ast.dump`-derived output
+    /// rather than this crate's own `Debug` representation. Parse errors are surfaced the same
+    /// way as [`Workspace::parse`]'s: as a rejected promise on the JavaScript side.
+    #[wasm_bindgen(js_name = parseJson)]
+    pub fn parse_json(&self, contents: &str) -> Result<JsValue, Error> {
+        let parsed = ruff_python_parser::parse(contents, Mode::Module).map_err(into_error)?;
+        let json = to_cpython_json(&parsed, contents, MinVersion::PY312);
+
+        serde_wasm_bindgen::to_value(&json).map_err(into_error)
+    }
+
     pub fn tokens(&self, contents: &str) -> Result<String, Error> {
         let tokens: Vec<_> = ruff_python_parser::lexer::lex(contents, Mode::Module).collect();
 
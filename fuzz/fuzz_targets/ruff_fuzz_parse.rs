@@ -0,0 +1,15 @@
+//! Fuzzer harness for `ruff_python_parser::fuzz::fuzz_parse`, the crate's panic-proof parsing
+//! entry point: unlike `ruff_parse_simple`, this doesn't reject non-UTF-8 input, since
+//! `fuzz_parse` is supposed to handle that itself.
+
+#![no_main]
+
+use libfuzzer_sys::{fuzz_target, Corpus};
+use ruff_python_parser::fuzz::fuzz_parse;
+
+fn do_fuzz(case: &[u8]) -> Corpus {
+    let _ = fuzz_parse(case);
+    Corpus::Keep
+}
+
+fuzz_target!(|case: &[u8]| -> Corpus { do_fuzz(case) });
@@ -0,0 +1,16 @@
+//! Fuzzer harness that drives the parser with grammar-aware generated source instead of raw
+//! bytes, so it spends its time on the parser's own logic (match statements, f-strings, error
+//! recovery) rather than dying in the lexer on unterminated strings and mismatched brackets.
+
+#![no_main]
+
+use libfuzzer_sys::{fuzz_target, Corpus};
+use ruff_python_parser::fuzz_generator::ArbitrarySource;
+use ruff_python_parser::{parse, Mode};
+
+fn do_fuzz(source: ArbitrarySource) -> Corpus {
+    let _ = parse(&source.0, Mode::Module);
+    Corpus::Keep
+}
+
+fuzz_target!(|source: ArbitrarySource| -> Corpus { do_fuzz(source) });